@@ -0,0 +1,54 @@
+//! Compares the JSON `Value` round trip of [`domni::serde_transmute`] with the streaming
+//! [`domni::transmute`] on the vanilla raws in `raw/objects`, converting the parsed df_ls
+//! structures into our `DFRaw` like `parse_lossy` does.
+//!
+//! Run with `cargo bench --bench transmute` from the crate root.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use df_ls_structure::DFRaw as ParsedDFRaw;
+
+fn parse_all() -> Vec<ParsedDFRaw> {
+    let mut paths: Vec<_> = std::fs::read_dir("./raw/objects")
+        .expect("run from the crate root")
+        .map(|entry| entry.expect("`raw/objects` can be listed").path())
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            domni::read_raw_file(&path, domni::Encoding::Auto)
+                .unwrap_or_else(|error| panic!("could not read {}: {}", path.display(), error))
+        })
+        .filter(|source| source.contains("[OBJECT:"))
+        .map(|source| {
+            let (tree, _) = df_ls_lexical_analysis::do_lexical_analysis(&source);
+            let (structure, _) = df_ls_syntax_analysis::do_syntax_analysis(&tree, &source);
+            structure
+        })
+        .collect()
+}
+
+fn transmute(c: &mut Criterion) {
+    let parsed = parse_all();
+    let via_json = || {
+        for structure in &parsed {
+            let raw: domni::DFRaw = domni::serde_transmute(structure).unwrap();
+            criterion::black_box(raw);
+        }
+    };
+    let via_stream = || {
+        for structure in &parsed {
+            let raw: domni::DFRaw = domni::transmute(structure).unwrap();
+            criterion::black_box(raw);
+        }
+    };
+    let mut group = c.benchmark_group("transmute raw/objects");
+    group.sample_size(10);
+    group.bench_function("serde_json::Value", |b| b.iter(via_json));
+    group.bench_function("transmute", |b| b.iter(via_stream));
+    group.finish();
+}
+
+criterion_group!(benches, transmute);
+criterion_main!(benches);
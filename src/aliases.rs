@@ -0,0 +1,177 @@
+//! The `alias` attributes of `crate::structure`, read from its source for the tests that check
+//! token lists against them.
+//!
+//! Serde only gives the names of a struct or enum in one list, aliases and member names mixed,
+//! which loses what member an alias belongs to. So the source is read, and checked against
+//! those lists.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use serde::de::{self, value, DeserializeOwned, Deserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+/// The aliases of every field and variant, by struct or enum name and then by member name.
+pub type Aliases = BTreeMap<String, BTreeMap<String, Vec<String>>>;
+
+/// Reads the aliases of every struct and enum in `src/structure`.
+pub fn structure_aliases() -> Aliases {
+    let mut aliases = Aliases::new();
+    read_dir(Path::new("./src/structure"), &mut aliases);
+    aliases
+}
+
+/// Every alias of the members of a struct or enum.
+pub fn item_aliases<'a>(aliases: &'a Aliases, item: &str) -> BTreeSet<&'a str> {
+    aliases[item]
+        .values()
+        .flatten()
+        .map(String::as_str)
+        .collect()
+}
+
+/// The names the derived `Deserialize` of a struct or enum accepts for its members, aliases
+/// included, as it gives them to `deserialize_struct` or `deserialize_enum`.
+pub fn serde_names<T: DeserializeOwned>() -> &'static [&'static str] {
+    let mut names = None;
+    // Fails once the names are read.
+    let _ = T::deserialize(NameProbe(&mut names));
+    names.unwrap_or_else(|| panic!("{} is not a struct or enum", std::any::type_name::<T>()))
+}
+
+struct NameProbe<'a>(&'a mut Option<&'static [&'static str]>);
+
+impl<'de> Deserializer<'de> for NameProbe<'_> {
+    type Error = value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, value::Error> {
+        Err(de::Error::custom("only structs and enums have names"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, value::Error> {
+        *self.0 = Some(fields);
+        Err(de::Error::custom("names read"))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, value::Error> {
+        *self.0 = Some(variants);
+        Err(de::Error::custom("names read"))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map identifier ignored_any
+    }
+}
+
+fn read_dir(dir: &Path, aliases: &mut Aliases) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            read_dir(&path, aliases);
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            read_file(&fs::read_to_string(&path).unwrap(), aliases);
+        }
+    }
+}
+
+/// Reads the items declared with a body at the top level of a file. Their members are indented
+/// by four spaces and attributes spanning several lines end on a line ending in `]`.
+fn read_file(source: &str, aliases: &mut Aliases) {
+    let mut item: Option<String> = None;
+    let mut pending: Vec<String> = Vec::new();
+    let mut in_attribute = false;
+    for line in source.lines() {
+        let code = line.split("//").next().unwrap_or_default().trim();
+        if code.is_empty() {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        if indent == 0 {
+            item = None;
+            pending.clear();
+            in_attribute = false;
+            let words: Vec<&str> = code.split_whitespace().collect();
+            if let ["pub", "struct" | "enum", name, ..] = words[..] {
+                if code.ends_with('{') {
+                    let name = name.split(['<', '{']).next().unwrap_or(name);
+                    aliases.entry(name.to_owned()).or_default();
+                    item = Some(name.to_owned());
+                }
+            }
+            continue;
+        }
+        let members = match &item {
+            Some(item) => aliases.get_mut(item).unwrap(),
+            None => continue,
+        };
+        if in_attribute || (indent == 4 && code.starts_with("#[")) {
+            pending.extend(
+                code.split("alias = \"")
+                    .skip(1)
+                    .filter_map(|rest| rest.split('"').next())
+                    .map(str::to_owned),
+            );
+            in_attribute = !code.ends_with(']');
+            continue;
+        }
+        if indent != 4 {
+            continue;
+        }
+        let member = code.strip_prefix("pub ").unwrap_or(code);
+        let end = member
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(member.len());
+        if member.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            members.insert(member[..end].to_owned(), std::mem::take(&mut pending));
+        }
+        pending.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::{item_aliases, serde_names, structure_aliases};
+    use crate::*;
+
+    #[test]
+    fn aliases_match_serde() {
+        let aliases = structure_aliases();
+        let count: usize = aliases
+            .values()
+            .flat_map(|members| members.values())
+            .map(Vec::len)
+            .sum();
+        assert!(count > 5000, "only {} aliases read", count);
+        for (item, names) in [
+            ("CreatureToken", serde_names::<CreatureToken>()),
+            ("Caste", serde_names::<Caste>()),
+            ("MaterialToken", serde_names::<MaterialToken>()),
+            ("UseMaterialTemplate", serde_names::<UseMaterialTemplate>()),
+            ("UseMaterial", serde_names::<UseMaterial>()),
+            ("SelectMaterial", serde_names::<SelectMaterial>()),
+            ("StandardPluralEnum", serde_names::<StandardPluralEnum>()),
+        ] {
+            // Members are named in snake or camel case, aliases in upper case.
+            let serde_aliases: BTreeSet<&str> = names
+                .iter()
+                .copied()
+                .filter(|name| !name.contains(|c: char| c.is_ascii_lowercase()))
+                .collect();
+            assert_eq!(item_aliases(&aliases, item), serde_aliases, "{}", item);
+        }
+    }
+}
@@ -0,0 +1,555 @@
+//! Bodies assembled the way the game does it: the parts of every `BODY` in order, each connected
+//! to its parent by `CON`, `CON_CAT` or `CONTYPE`, with body glosses applied to their names.
+
+use std::fmt;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::core::Choose;
+use crate::raw_set::RawSet;
+use crate::structure::{
+    BodyGlossToken, BodyPartToken, BodyToken, BpRelationEnum, ConTypeEnum, CreatureToken,
+    PositionEnum,
+};
+
+/// One body part of an assembled body. Parts connected with `CON_CAT` or `CONTYPE` get a copy
+/// for each part they connect to, like the fingers of each hand.
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct BodyPart {
+    /// The ID from `[BP:...]`, shared by all copies of the part.
+    pub id: String,
+    /// The singular name, after body glosses.
+    pub name: String,
+    /// The plural name, after body glosses.
+    pub plural: String,
+    /// The `BODY` the part comes from.
+    pub body: String,
+    pub token: BodyPartToken,
+    /// The part this one is connected to, `None` for the root.
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    /// Tissue layers from body detail plans and `TISSUE_LAYER`, innermost first.
+    pub layers: Vec<BodyLayer>,
+    /// Where the part sits within its parent, from `BP_POSITION`.
+    pub position: Option<PositionEnum>,
+    /// Size relative to the other parts, from `DEFAULT_RELSIZE`, `BP_RELSIZE` or `RELSIZE`.
+    pub relsize: Option<u32>,
+    /// How the part relates to other parts in the same parent, from `BP_RELATION`.
+    pub relations: Vec<BodyRelation>,
+}
+
+/// A tissue layer of a body part.
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct BodyLayer {
+    /// The local tissue ID.
+    pub tissue: String,
+    /// Thickness relative to the other layers of the part.
+    pub thickness: u32,
+    pub position: Option<PositionEnum>,
+    pub relation: Option<BodyRelation>,
+}
+
+/// How a part or layer relates to other parts, like a cheek `AROUND` the teeth.
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BodyRelation {
+    pub relation: BpRelationEnum,
+    /// The parts the criteria matched.
+    pub parts: Vec<usize>,
+    /// How far the relation holds.
+    pub extent: Option<u8>,
+}
+
+/// Something wrong with how the parts of a body connect.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum BodyIssue {
+    /// A `BODY` that isn't loaded.
+    UnknownBody(String),
+    /// A `BODYGLOSS` that isn't loaded.
+    UnknownGloss(String),
+    /// A part whose `CON` names a part that isn't in the body.
+    Orphan { part: String, con: String },
+    /// A part whose `CON_CAT` names a category no part has.
+    UnresolvedCategory { part: String, category: String },
+    /// A part whose `CONTYPE` matches no part.
+    UnresolvedType { part: String, contype: String },
+    /// More than one part connects to nothing.
+    MultipleRoots(Vec<String>),
+    /// Every part connects to another, so there is no root.
+    NoRoot,
+    /// Parts that only connect to each other, directly or not.
+    Cycle(Vec<String>),
+    /// A `BODY_DETAIL_PLAN` that isn't loaded.
+    UnknownDetailPlan(String),
+    /// A body detail plan uses an `ARGn` it wasn't given.
+    MissingPlanArgument { plan: String, arg: usize },
+}
+
+impl fmt::Display for BodyIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BodyIssue::UnknownBody(id) => write!(f, "Unknown body `{}`", id),
+            BodyIssue::UnknownGloss(id) => write!(f, "Unknown body gloss `{}`", id),
+            BodyIssue::Orphan { part, con } => {
+                write!(
+                    f,
+                    "`{}` connects to `{}`, which is not in the body",
+                    part, con
+                )
+            }
+            BodyIssue::UnresolvedCategory { part, category } => {
+                write!(
+                    f,
+                    "`{}` connects to category `{}`, which no part has",
+                    part, category
+                )
+            }
+            BodyIssue::UnresolvedType { part, contype } => {
+                write!(
+                    f,
+                    "`{}` connects to `{}` parts, but there are none",
+                    part, contype
+                )
+            }
+            BodyIssue::MultipleRoots(roots) => write!(f, "Multiple roots: {}", roots.join(", ")),
+            BodyIssue::NoRoot => write!(f, "No part is the root"),
+            BodyIssue::Cycle(parts) => write!(f, "Parts connect in a cycle: {}", parts.join(", ")),
+            BodyIssue::UnknownDetailPlan(id) => write!(f, "Unknown body detail plan `{}`", id),
+            BodyIssue::MissingPlanArgument { plan, arg } => {
+                write!(
+                    f,
+                    "Body detail plan `{}` uses ARG{}, which is not given",
+                    plan, arg
+                )
+            }
+        }
+    }
+}
+
+/// How a part names what it connects to.
+enum Connection<'a> {
+    Root,
+    Con(&'a str),
+    Category(&'a str),
+    Type(&'a ConTypeEnum),
+}
+
+impl<'a> Connection<'a> {
+    fn of(token: &'a BodyPartToken) -> Self {
+        if let Some(con) = &token.con {
+            Connection::Con(&con.0)
+        } else if let Some(category) = &token.con_cat {
+            Connection::Category(&category.0)
+        } else if let Some(contype) = &token.contype {
+            Connection::Type(contype)
+        } else {
+            Connection::Root
+        }
+    }
+
+    fn matches(&self, token: &BodyPartToken) -> bool {
+        match self {
+            Connection::Root => false,
+            Connection::Con(id) => part_id(token) == *id,
+            Connection::Category(category) => {
+                token.category.as_ref().is_some_and(|c| c.0 == *category)
+            }
+            Connection::Type(ConTypeEnum::Upperbody) => token.upperbody.is_some(),
+            Connection::Type(ConTypeEnum::Lowerbody) => token.lowerbody.is_some(),
+            Connection::Type(ConTypeEnum::Head) => token.head.is_some(),
+            Connection::Type(ConTypeEnum::Grasp) => token.grasp.is_some(),
+            Connection::Type(ConTypeEnum::Stance) => token.stance.is_some(),
+        }
+    }
+
+    fn issue(&self, part: &str) -> BodyIssue {
+        let part = part.to_owned();
+        match self {
+            Connection::Con(con) => BodyIssue::Orphan {
+                part,
+                con: con.to_string(),
+            },
+            Connection::Category(category) => BodyIssue::UnresolvedCategory {
+                part,
+                category: category.to_string(),
+            },
+            Connection::Type(contype) => BodyIssue::UnresolvedType {
+                part,
+                contype: format!("{:?}", contype).to_uppercase(),
+            },
+            Connection::Root => BodyIssue::NoRoot,
+        }
+    }
+}
+
+fn part_id(token: &BodyPartToken) -> &str {
+    token
+        .bp
+        .as_ref()
+        .map(|bp| bp.0 .0.as_str())
+        .unwrap_or_default()
+}
+
+/// Where a part stands while the body is being assembled.
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    Waiting,
+    Placed,
+    Failed,
+}
+
+/// The body parts of a creature connected into a tree.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct BodyGraph {
+    pub parts: Vec<BodyPart>,
+    /// Local materials added by body detail plans, as (ID, material template).
+    pub materials: Vec<(String, String)>,
+    /// Local tissues added by body detail plans, as (ID, tissue template).
+    pub tissues: Vec<(String, String)>,
+    pub issues: Vec<BodyIssue>,
+}
+
+impl BodyGraph {
+    /// Assembles the parts of the given bodies in order and applies the glosses to their names.
+    pub fn new(bodies: &[&BodyToken], glosses: &[&BodyGlossToken]) -> Self {
+        let templates: Vec<(&str, &BodyPartToken)> = bodies
+            .iter()
+            .flat_map(|body| {
+                let id = body
+                    .reference
+                    .as_ref()
+                    .map(|r| r.0.as_str())
+                    .unwrap_or_default();
+                body.bp.iter().map(move |part| (id, part))
+            })
+            .collect();
+        let mut graph = Self::default();
+        let mut states = vec![State::Waiting; templates.len()];
+        let mut copies: Vec<Vec<usize>> = vec![vec![]; templates.len()];
+        let mut progress = true;
+        while progress {
+            progress = false;
+            for (index, (body, token)) in templates.iter().enumerate() {
+                if states[index] != State::Waiting {
+                    continue;
+                }
+                let connection = Connection::of(token);
+                let parents: Vec<usize> = (0..templates.len())
+                    .filter(|&other| other != index && connection.matches(templates[other].1))
+                    .collect();
+                if matches!(connection, Connection::Root) {
+                    copies[index].push(graph.add(body, token, None));
+                } else if parents.is_empty() {
+                    graph.issues.push(connection.issue(part_id(token)));
+                    states[index] = State::Failed;
+                    continue;
+                } else if parents
+                    .iter()
+                    .any(|&parent| states[parent] == State::Waiting)
+                {
+                    continue;
+                } else {
+                    for parent in parents {
+                        for at in copies[parent].clone() {
+                            copies[index].push(graph.add(body, token, Some(at)));
+                        }
+                    }
+                }
+                // Parts below a part that couldn't be placed are left out without another issue.
+                states[index] = if copies[index].is_empty() {
+                    State::Failed
+                } else {
+                    State::Placed
+                };
+                progress = true;
+            }
+        }
+        let cycle: Vec<String> = (0..templates.len())
+            .filter(|&index| states[index] == State::Waiting)
+            .map(|index| part_id(templates[index].1).to_owned())
+            .collect();
+        if !cycle.is_empty() {
+            graph.issues.push(BodyIssue::Cycle(cycle));
+        }
+        let roots: Vec<String> = graph
+            .roots()
+            .map(|root| graph.parts[root].id.clone())
+            .collect();
+        if roots.len() > 1 {
+            graph.issues.push(BodyIssue::MultipleRoots(roots));
+        } else if roots.is_empty() && !templates.is_empty() {
+            graph.issues.push(BodyIssue::NoRoot);
+        }
+        for gloss in glosses {
+            graph.apply_gloss(gloss);
+        }
+        graph
+    }
+
+    /// Assembles the body of a creature from its `BODY` and `BODYGLOSS` tokens, then applies its
+    /// body detail plans, `TISSUE_LAYER` and `RELSIZE` tokens. The plans are read from their
+    /// source again for the order of their layers, see [`BodyGraph::apply_detail_plan`].
+    pub fn for_creature(creature: &CreatureToken, raw_set: &RawSet) -> Result<Self> {
+        let registry = raw_set.registry();
+        let mut issues = vec![];
+        let mut bodies = vec![];
+        for reference in creature.body.iter().flat_map(|(bodies,)| bodies) {
+            match reference.resolve(&registry) {
+                Some(body) => bodies.push(body),
+                None => issues.push(BodyIssue::UnknownBody(reference.0.clone())),
+            }
+        }
+        let mut glosses = vec![];
+        for reference in creature.bodygloss.iter().flat_map(|(glosses,)| glosses) {
+            match reference.resolve(&registry) {
+                Some(gloss) => glosses.push(gloss),
+                None => issues.push(BodyIssue::UnknownGloss(reference.0.clone())),
+            }
+        }
+        let mut graph = Self::new(&bodies, &glosses);
+        issues.append(&mut graph.issues);
+        graph.issues = issues;
+        for (reference, arg1, arg2, arg3, arg4, arg5) in &creature.body_detail_plan {
+            let args: Vec<String> = [arg1, arg2, arg3, arg4, arg5]
+                .into_iter()
+                .map_while(|arg| arg.as_ref().map(|arg| arg.0.clone()))
+                .collect();
+            match reference.resolve(&registry) {
+                Some(plan) => {
+                    let tags = match raw_set.find("BODY_DETAIL_PLAN", &reference.0) {
+                        Some(sourced) => sourced.tags()?,
+                        None => vec![],
+                    };
+                    graph.apply_detail_plan(plan, &tags, &args);
+                }
+                None => graph
+                    .issues
+                    .push(BodyIssue::UnknownDetailPlan(reference.0.clone())),
+            }
+        }
+        for tissue_layer in &creature.tissue_layer {
+            graph.add_tissue_layer(tissue_layer);
+        }
+        for (criteria, relsize) in &creature.relsize {
+            for index in graph.select(criteria) {
+                graph.parts[index].relsize = Some(*relsize);
+            }
+        }
+        Ok(graph)
+    }
+
+    fn add(&mut self, body: &str, token: &BodyPartToken, parent: Option<usize>) -> usize {
+        let (name, plural) = match &token.bp {
+            Some((_, name, Choose::Choice1(_))) => (name.clone(), format!("{}s", name)),
+            Some((_, name, Choose::Choice2(plural))) => (name.clone(), plural.clone()),
+            None => Default::default(),
+        };
+        let index = self.parts.len();
+        self.parts.push(BodyPart {
+            id: part_id(token).to_owned(),
+            name,
+            plural,
+            body: body.to_owned(),
+            token: token.clone(),
+            parent,
+            children: vec![],
+            layers: vec![],
+            position: None,
+            relsize: token.default_relsize,
+            relations: vec![],
+        });
+        if let Some(parent) = parent {
+            self.parts[parent].children.push(index);
+        }
+        index
+    }
+
+    /// Replaces whole words of the part names, e.g. `foot` with `paw` and `feet` with `paws`.
+    fn apply_gloss(&mut self, gloss: &BodyGlossToken) {
+        let (_, singular, singular_gloss, plural, plural_gloss) = match &gloss.bodygloss {
+            Some(gloss) => gloss,
+            None => return,
+        };
+        let replace = |text: &str, word: &str, with: &str| {
+            text.split(' ')
+                .map(|part| if part == word { with } else { part })
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        for part in &mut self.parts {
+            part.name = replace(&part.name, singular, singular_gloss);
+            part.plural = replace(&part.plural, plural, plural_gloss);
+        }
+    }
+
+    /// Parts that connect to nothing; a complete body has exactly one.
+    pub fn roots(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.parts.len()).filter(|&index| self.parts[index].parent.is_none())
+    }
+
+    /// Every part under the given one, depth first.
+    pub fn under(&self, index: usize) -> Vec<usize> {
+        let mut under = vec![];
+        let mut stack: Vec<usize> = self.parts[index].children.iter().rev().copied().collect();
+        while let Some(next) = stack.pop() {
+            under.push(next);
+            stack.extend(self.parts[next].children.iter().rev());
+        }
+        under
+    }
+
+    /// The parts the given one hangs from, nearest first.
+    pub fn ancestors(&self, index: usize) -> Vec<usize> {
+        std::iter::successors(self.parts[index].parent, |&parent| {
+            self.parts[parent].parent
+        })
+        .collect()
+    }
+
+    /// Parts whose token matches, e.g. `graph.parts_where(|part| part.grasp.is_some())`.
+    pub fn parts_where<'a>(
+        &'a self,
+        predicate: impl Fn(&BodyPartToken) -> bool + 'a,
+    ) -> impl Iterator<Item = usize> + 'a {
+        (0..self.parts.len()).filter(move |&index| predicate(&self.parts[index].token))
+    }
+
+    /// Every copy of the part with this `BP` ID.
+    pub fn with_id<'a>(&'a self, id: &'a str) -> impl Iterator<Item = usize> + 'a {
+        (0..self.parts.len()).filter(move |&index| self.parts[index].id == id)
+    }
+
+    /// Parts with this `CATEGORY`.
+    pub fn in_category<'a>(&'a self, category: &'a str) -> impl Iterator<Item = usize> + 'a {
+        self.parts_where(move |part| part.category.as_ref().is_some_and(|c| c.0 == category))
+    }
+}
+
+impl RawSet {
+    /// The body of each caste of a creature, see [`BodyGraph::for_creature`].
+    pub fn body_graphs(&self, id: &str) -> Result<Vec<(Option<String>, BodyGraph)>> {
+        self.effective_castes(id)?
+            .into_iter()
+            .map(|caste| {
+                let graph = BodyGraph::for_creature(&caste.creature()?, self)?;
+                Ok((caste.id, graph))
+            })
+            .collect()
+    }
+}
+
+/// Body parts and criteria shared by the tests of bodies, body detail plans and selectors.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use crate::core::{Choose, Reference};
+    use crate::*;
+
+    /// A body part named like its ID, of a category, connected to the part `con`.
+    pub(crate) fn part(id: &str, category: &str, con: Option<&str>) -> BodyPartToken {
+        BodyPartToken {
+            bp: Some((
+                Reference(id.to_owned()),
+                id.to_lowercase(),
+                Choose::Choice1(StandardPluralEnum::Stp),
+            )),
+            category: Some(Reference(category.to_owned())),
+            con: con.map(|con| Reference(con.to_owned())),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn by_category(category: &str) -> BpCriteriaTokenArg {
+        BpCriteriaTokenArg::ByCategory(Reference(category.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn body_graph() {
+        use crate::core::{Choose, Reference};
+        let part = |id: &str, name: &str, connection: (&str, &str)| {
+            let mut part = BodyPartToken {
+                bp: Some((
+                    Reference(id.to_owned()),
+                    name.to_owned(),
+                    Choose::Choice1(StandardPluralEnum::Stp),
+                )),
+                ..Default::default()
+            };
+            match connection {
+                ("CON", to) => part.con = Some(Reference(to.to_owned())),
+                ("CON_CAT", to) => part.con_cat = Some(Reference(to.to_owned())),
+                _ => {}
+            }
+            part
+        };
+        let upper_body = BodyToken {
+            reference: Some(ReferenceTo::new("HUMANOID".to_owned())),
+            bp: vec![
+                BodyPartToken {
+                    upperbody: Some(()),
+                    ..part("UB", "upper body", ("", ""))
+                },
+                part("LB", "lower body", ("CON", "UB")),
+                BodyPartToken {
+                    category: Some(Reference("HAND".to_owned())),
+                    grasp: Some(()),
+                    ..part("RH", "right hand", ("CON", "UB"))
+                },
+                BodyPartToken {
+                    category: Some(Reference("HAND".to_owned())),
+                    grasp: Some(()),
+                    ..part("LH", "left hand", ("CON", "UB"))
+                },
+            ],
+        };
+        let extras = BodyToken {
+            reference: Some(ReferenceTo::new("EXTRAS".to_owned())),
+            bp: vec![
+                part("NAIL", "nail", ("CON", "F1")),
+                part("F1", "finger", ("CON_CAT", "HAND")),
+                part("TAIL", "tail", ("CON_CAT", "TAIL_BASE")),
+                BodyPartToken {
+                    contype: Some(ConTypeEnum::Upperbody),
+                    ..part("HEAD", "head", ("", ""))
+                },
+            ],
+        };
+        let gloss = BodyGlossToken {
+            bodygloss: Some((
+                ReferenceTo::new("CLAW_HAND".to_owned()),
+                "hand".to_owned(),
+                "claw".to_owned(),
+                "hands".to_owned(),
+                "claws".to_owned(),
+            )),
+        };
+        let graph = BodyGraph::new(&[&upper_body, &extras], &[&gloss]);
+        let ids = |indices: Vec<usize>| -> Vec<String> {
+            indices.into_iter().map(|index| graph.parts[index].id.clone()).collect()
+        };
+        assert_eq!(ids(graph.roots().collect()), ["UB"]);
+        assert_eq!(
+            ids(graph.under(0)),
+            ["LB", "RH", "F1", "NAIL", "LH", "F1", "NAIL", "HEAD"]
+        );
+        assert_eq!(ids(graph.parts_where(|part| part.grasp.is_some()).collect()), ["RH", "LH"]);
+        let nail = graph.with_id("NAIL").last().unwrap();
+        assert_eq!(ids(graph.ancestors(nail)), ["F1", "LH", "UB"]);
+        assert_eq!(graph.parts[2].name, "right claw");
+        assert_eq!(graph.parts[2].plural, "right claws");
+        assert_eq!(
+            graph.issues,
+            [BodyIssue::UnresolvedCategory {
+                part: "TAIL".to_owned(),
+                category: "TAIL_BASE".to_owned(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,381 @@
+//! Body detail plans applied to an assembled body: tissue layers, positions, relations and
+//! relative sizes of the parts they select.
+
+use crate::body::{BodyGraph, BodyIssue, BodyLayer, BodyRelation};
+use crate::core::{Choose, Reference};
+use crate::structure::{
+    ArgEnum, BodyDetailPlanToken, BodyPartToken, BodyPartTypeEnum, BpCriteriaTokenArg,
+    BpLayerTokenArg, BpRelationEnum,
+};
+use crate::tags::Tag;
+
+/// Whether a body part has the flag a `BY_TYPE` criteria names.
+fn has_type(part: &BodyPartToken, body_part_type: &BodyPartTypeEnum) -> bool {
+    let flag = match body_part_type {
+        BodyPartTypeEnum::Aperture => part.aperture,
+        BodyPartTypeEnum::Breathe => part.breathe,
+        BodyPartTypeEnum::Circulation => part.circulation,
+        BodyPartTypeEnum::Connector => part.connector,
+        BodyPartTypeEnum::Digit => part.digit,
+        BodyPartTypeEnum::Embedded => part.embedded,
+        BodyPartTypeEnum::Flier => part.flier,
+        BodyPartTypeEnum::Grasp => part.grasp,
+        BodyPartTypeEnum::Guts => part.guts,
+        BodyPartTypeEnum::Head => part.head,
+        BodyPartTypeEnum::Hear => part.hear,
+        BodyPartTypeEnum::Internal => part.internal,
+        BodyPartTypeEnum::Joint => part.joint,
+        BodyPartTypeEnum::Left => part.left,
+        BodyPartTypeEnum::Limb => part.limb,
+        BodyPartTypeEnum::Lowerbody => part.lowerbody,
+        BodyPartTypeEnum::Mouth => part.mouth,
+        BodyPartTypeEnum::Nervous => part.nervous,
+        BodyPartTypeEnum::Right => part.right,
+        BodyPartTypeEnum::Sight => part.sight,
+        BodyPartTypeEnum::Skeleton => part.skeleton,
+        BodyPartTypeEnum::Small => part.small,
+        BodyPartTypeEnum::Smell => part.smell,
+        BodyPartTypeEnum::Socket => part.socket,
+        BodyPartTypeEnum::Stance => part.stance,
+        BodyPartTypeEnum::Thought => part.thought,
+        BodyPartTypeEnum::Throat => part.throat,
+        BodyPartTypeEnum::Totemable => part.totemable,
+        BodyPartTypeEnum::UnderPressure => part.under_pressure,
+        BodyPartTypeEnum::Upperbody => part.upperbody,
+        BodyPartTypeEnum::VerminButcherItem => part.vermin_butcher_item,
+        BodyPartTypeEnum::Geldable => part.geldable,
+    };
+    flag.is_some()
+}
+
+/// The tissue of a `BP_LAYERS` layer, with `ARGn` filled in, or the number of the missing
+/// argument.
+fn plan_tissue(tissue: &Choose<ArgEnum, Reference>, args: &[String]) -> Result<String, usize> {
+    match tissue {
+        Choose::Choice1(arg) => {
+            let number = match arg {
+                ArgEnum::Arg1 => 1,
+                ArgEnum::Arg2 => 2,
+                ArgEnum::Arg3 => 3,
+                ArgEnum::Arg4 => 4,
+                ArgEnum::Arg5 => 5,
+            };
+            args.get(number - 1).cloned().ok_or(number)
+        }
+        Choose::Choice2(Reference(tissue)) => Ok(tissue.clone()),
+    }
+}
+
+/// Where `BP_LAYERS` variants put their layers.
+#[derive(Clone, Copy)]
+enum Placement {
+    Outside,
+    Inside,
+}
+
+/// The `BP_LAYERS`, `BP_LAYERS_OVER` and `BP_LAYERS_UNDER` tokens of a plan in the order of its
+/// tags, each with where it puts its layers. Tokens missing from the tags follow in the order of
+/// the structure.
+fn layers_in_order<'a>(
+    plan: &'a BodyDetailPlanToken,
+    tags: &[Tag],
+) -> Vec<(&'a (BpCriteriaTokenArg, Vec<BpLayerTokenArg>), Placement)> {
+    let mut layers = plan.bp_layers.iter();
+    let mut over = plan.bp_layers_over.iter();
+    let mut under = plan.bp_layers_under.iter();
+    let mut ordered: Vec<_> = tags
+        .iter()
+        .filter_map(|tag| match tag.name.as_str() {
+            "BP_LAYERS" => layers.next().map(|token| (token, Placement::Outside)),
+            "BP_LAYERS_OVER" => over.next().map(|token| (token, Placement::Outside)),
+            "BP_LAYERS_UNDER" => under.next().map(|token| (token, Placement::Inside)),
+            _ => None,
+        })
+        .collect();
+    ordered.extend(layers.chain(over).map(|token| (token, Placement::Outside)));
+    ordered.extend(under.map(|token| (token, Placement::Inside)));
+    ordered
+}
+
+impl BodyGraph {
+    /// Whether a part matches `BY_CATEGORY`, `BY_TYPE` or `BY_TOKEN` criteria. `BY_CATEGORY:ALL`
+    /// matches every part.
+    pub fn matches(&self, index: usize, criteria: &BpCriteriaTokenArg) -> bool {
+        let part = &self.parts[index];
+        match criteria {
+            BpCriteriaTokenArg::ByCategory(category) => {
+                category.0 == "ALL" || part.token.category.as_ref() == Some(category)
+            }
+            BpCriteriaTokenArg::ByType(body_part_type) => has_type(&part.token, body_part_type),
+            BpCriteriaTokenArg::ByToken(id) => part.id == id.0,
+        }
+    }
+
+    /// Every part matching the criteria, in body order.
+    pub fn select(&self, criteria: &BpCriteriaTokenArg) -> Vec<usize> {
+        (0..self.parts.len())
+            .filter(|&index| self.matches(index, criteria))
+            .collect()
+    }
+
+    /// Applies a body detail plan, with the arguments given after its ID in
+    /// `[BODY_DETAIL_PLAN:...]` filling in `ARGn` tissues.
+    ///
+    /// Layers are added in the order of the plan's tags, as from [`SourcedObject::tags`], since
+    /// the structure keeps `BP_LAYERS`, `BP_LAYERS_OVER` and `BP_LAYERS_UNDER` apart.
+    ///
+    /// [`SourcedObject::tags`]: crate::SourcedObject::tags
+    pub fn apply_detail_plan(
+        &mut self,
+        plan: &BodyDetailPlanToken,
+        tags: &[Tag],
+        args: &[String],
+    ) {
+        let plan_id = plan
+            .reference
+            .as_ref()
+            .map(|reference| reference.0.clone())
+            .unwrap_or_default();
+        for (id, template) in &plan.add_material {
+            self.materials.push((id.0.clone(), template.0.clone()));
+        }
+        for (id, template) in &plan.add_tissue {
+            self.tissues.push((id.0.clone(), template.0.clone()));
+        }
+        for ((criteria, layers), placement) in layers_in_order(plan, tags) {
+            // The arguments are the same for every part, so a missing one is reported once.
+            let mut tissues = vec![];
+            let mut missing = vec![];
+            for layer in layers {
+                match plan_tissue(&layer.tissue.0, args) {
+                    Ok(tissue) => tissues.push((layer, tissue)),
+                    Err(arg) if !missing.contains(&arg) => missing.push(arg),
+                    Err(_) => {}
+                }
+            }
+            self.issues.extend(
+                missing
+                    .into_iter()
+                    .map(|arg| BodyIssue::MissingPlanArgument {
+                        plan: plan_id.clone(),
+                        arg,
+                    }),
+            );
+            for index in self.select(criteria) {
+                let new_layers: Vec<_> = tissues
+                    .iter()
+                    .map(|(layer, tissue)| self.layer(index, layer, tissue.clone()))
+                    .collect();
+                let part_layers = &mut self.parts[index].layers;
+                match placement {
+                    Placement::Outside => part_layers.extend(new_layers),
+                    Placement::Inside => {
+                        part_layers.splice(0..0, new_layers);
+                    }
+                }
+            }
+        }
+        for (criteria, position) in &plan.bp_position {
+            for index in self.select(criteria) {
+                self.parts[index].position = Some(position.clone());
+            }
+        }
+        for (criteria, relation, target, extent) in &plan.bp_relation {
+            for index in self.select(criteria) {
+                // Relations hold between parts within the same parent.
+                let parts = match self.parts[index].parent {
+                    Some(parent) => self.parts[parent].children.clone(),
+                    None => vec![],
+                };
+                let relation = BodyRelation {
+                    relation: relation.clone(),
+                    parts: parts
+                        .into_iter()
+                        .filter(|&other| other != index && self.matches(other, target))
+                        .collect(),
+                    extent: *extent,
+                };
+                self.parts[index].relations.push(relation);
+            }
+        }
+        for (criteria, relsize) in &plan.bp_relsize {
+            for index in self.select(criteria) {
+                self.parts[index].relsize = Some(*relsize);
+            }
+        }
+    }
+
+    /// A layer of a `BP_LAYERS` token for one part, made of the given tissue.
+    fn layer(&self, index: usize, layer: &BpLayerTokenArg, tissue: String) -> BodyLayer {
+        let (position, relation) = match &layer.position_or_relation {
+            None => (None, None),
+            Some(Choose::Choice1(position)) => (Some(position.clone()), None),
+            Some(Choose::Choice2((relation, target, extent))) => (
+                None,
+                Some(self.layer_relation(index, relation, target, *extent)),
+            ),
+        };
+        BodyLayer {
+            tissue,
+            thickness: layer.tissue.1,
+            position,
+            relation,
+        }
+    }
+
+    /// The relation of a layer of a part, with the target matched among the parts inside it, like
+    /// a moustache below the nose on the head.
+    pub(crate) fn layer_relation(
+        &self,
+        index: usize,
+        relation: &BpRelationEnum,
+        target: &BpCriteriaTokenArg,
+        extent: Option<u8>,
+    ) -> BodyRelation {
+        BodyRelation {
+            relation: relation.clone(),
+            parts: self
+                .under(index)
+                .into_iter()
+                .filter(|&part| self.matches(part, target))
+                .collect(),
+            extent,
+        }
+    }
+
+    /// How much of the thickness of a part a layer makes up, from 0 to 1.
+    pub fn relative_thickness(&self, index: usize, layer: usize) -> f64 {
+        let layers = &self.parts[index].layers;
+        let total: u32 = layers.iter().map(|layer| layer.thickness).sum();
+        if total == 0 {
+            return 0.0;
+        }
+        f64::from(layers[layer].thickness) / f64::from(total)
+    }
+
+    /// The parts with a layer of the given local tissue.
+    pub fn parts_with_tissue<'a>(&'a self, tissue: &'a str) -> impl Iterator<Item = usize> + 'a {
+        (0..self.parts.len()).filter(move |&index| {
+            self.parts[index]
+                .layers
+                .iter()
+                .any(|layer| layer.tissue == tissue)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::body::fixtures::{by_category, part};
+    use crate::*;
+
+    #[test]
+    fn body_detail_plan() {
+        use crate::core::{Choose, Reference};
+        let body = BodyToken {
+            reference: Some(ReferenceTo::new("HEAD_ONLY".to_owned())),
+            bp: vec![
+                part("HEAD", "HEAD", None),
+                part("NOSE", "NOSE", Some("HEAD")),
+                part("MOUTH", "MOUTH", Some("HEAD")),
+                part("TEETH", "TEETH", Some("HEAD")),
+            ],
+        };
+        let layer = |tissue: Choose<ArgEnum, Reference>, thickness, relation| BpLayerTokenArg {
+            tissue: (tissue, thickness),
+            position_or_relation: relation,
+        };
+        let plan = BodyDetailPlanToken {
+            reference: Some(ReferenceTo::new("FACE".to_owned())),
+            add_tissue: vec![(
+                Reference("SKIN".to_owned()),
+                ReferenceTo::new("SKIN_TEMPLATE".to_owned()),
+            )],
+            bp_layers: vec![
+                (
+                    by_category("HEAD"),
+                    vec![
+                        layer(Choose::Choice1(ArgEnum::Arg2), 9, None),
+                        layer(Choose::Choice1(ArgEnum::Arg1), 1, None),
+                    ],
+                ),
+                (
+                    by_category("HEAD"),
+                    vec![layer(
+                        Choose::Choice2(Reference("MOUSTACHE".to_owned())),
+                        1,
+                        Some(Choose::Choice2((BpRelationEnum::Below, by_category("NOSE"), None))),
+                    )],
+                ),
+                (
+                    by_category("ALL"),
+                    vec![
+                        layer(Choose::Choice1(ArgEnum::Arg3), 1, None),
+                        layer(Choose::Choice1(ArgEnum::Arg3), 2, None),
+                    ],
+                ),
+            ],
+            bp_layers_over: vec![(
+                by_category("HEAD"),
+                vec![layer(
+                    Choose::Choice2(Reference("HAIR".to_owned())),
+                    1,
+                    None,
+                )],
+            )],
+            bp_layers_under: vec![(
+                by_category("HEAD"),
+                vec![layer(Choose::Choice2(Reference("BONE".to_owned())), 40, None)],
+            )],
+            bp_position: vec![(by_category("NOSE"), PositionEnum::Front)],
+            bp_relation: vec![(
+                by_category("MOUTH"),
+                BpRelationEnum::Around,
+                by_category("TEETH"),
+                Some(100),
+            )],
+            bp_relsize: vec![(by_category("NOSE"), 10)],
+            ..Default::default()
+        };
+        // The hair goes on before the moustache, as the source has it.
+        let tags = parse_tags(
+            "[BODY_DETAIL_PLAN:FACE]
+                [ADD_TISSUE:SKIN:SKIN_TEMPLATE]
+                [BP_LAYERS_UNDER:BY_CATEGORY:HEAD:BONE:40]
+                [BP_LAYERS:BY_CATEGORY:HEAD:ARG2:9:ARG1:1]
+                [BP_LAYERS_OVER:BY_CATEGORY:HEAD:HAIR:1]
+                [BP_LAYERS:BY_CATEGORY:HEAD:MOUSTACHE:1:BELOW:BY_CATEGORY:NOSE]
+                [BP_LAYERS:BY_CATEGORY:ALL:ARG3:1:ARG3:2]",
+        );
+        let mut graph = BodyGraph::new(&[&body], &[]);
+        graph.apply_detail_plan(&plan, &tags, &["SKIN".to_owned(), "MUSCLE".to_owned()]);
+        let tissues = |index: usize| -> Vec<&str> {
+            graph.parts[index].layers.iter().map(|layer| layer.tissue.as_str()).collect()
+        };
+        assert_eq!(tissues(0), ["BONE", "MUSCLE", "SKIN", "HAIR", "MOUSTACHE"]);
+        assert_eq!(graph.parts[0].layers[4].relation.as_ref().unwrap().parts, [1]);
+        assert_eq!(graph.relative_thickness(0, 0), 40.0 / 52.0);
+        assert_eq!(tissues(1), Vec::<&str>::new());
+        assert_eq!(
+            graph.issues,
+            [BodyIssue::MissingPlanArgument {
+                plan: "FACE".to_owned(),
+                arg: 3,
+            }]
+        );
+        assert_eq!(graph.parts[1].position, Some(PositionEnum::Front));
+        assert_eq!(graph.parts[1].relsize, Some(10));
+        assert_eq!(graph.parts[2].relations[0].parts, [3]);
+        assert_eq!(graph.parts_with_tissue("SKIN").collect::<Vec<_>>(), [0]);
+        assert_eq!(graph.tissues, [("SKIN".to_owned(), "SKIN_TEMPLATE".to_owned())]);
+        // Without tags the layers go on in the order of the structure.
+        let mut graph = BodyGraph::new(&[&body], &[]);
+        graph.apply_detail_plan(&plan, &[], &["SKIN".to_owned(), "MUSCLE".to_owned()]);
+        let tissues: Vec<&str> = graph.parts[0]
+            .layers
+            .iter()
+            .map(|layer| layer.tissue.as_str())
+            .collect();
+        assert_eq!(tissues, ["BONE", "MUSCLE", "SKIN", "MOUSTACHE", "HAIR"]);
+    }
+}
@@ -0,0 +1,235 @@
+//! The castes of a creature as the game sees them, with every caste selection resolved.
+
+mod token_levels;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::gait::Gait;
+use crate::raw_set::RawSet;
+use crate::structure::CreatureToken;
+use crate::tags::Tag;
+use crate::variation::{parse_creature_tags, CreatureExpander};
+
+use self::token_levels::{CASTE_TOKENS, CREATURE_TOKENS};
+
+/// Everything that applies to one caste of a creature.
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct EffectiveCaste {
+    /// The caste ID, `None` for the only caste of a creature that doesn't declare any.
+    pub id: Option<String>,
+    /// The creature header followed by the creature-level tokens and the tokens of this caste,
+    /// in file order, without any caste selection tokens.
+    pub tags: Vec<Tag>,
+}
+
+impl EffectiveCaste {
+    /// Parses the tokens, giving a creature without castes that has the tokens of this caste.
+    pub fn creature(&self) -> Result<CreatureToken> {
+        parse_creature_tags(&self.tags)
+    }
+
+    /// The gaits of the caste, see [`Gait`] for their speeds.
+    pub fn gaits(&self) -> Result<Vec<Gait>> {
+        Ok(self.creature()?.gait.iter().map(Gait::from_token).collect())
+    }
+}
+
+/// Whether the last token that isn't nested in another applies to the creature or to the castes.
+#[derive(Clone, Copy, PartialEq)]
+enum Level {
+    Creature,
+    Caste,
+}
+
+/// Splits the tokens of a creature (header first, usually from [`apply_creature_variations`]) into
+/// its castes, the way the game applies caste selections:
+///
+/// - Creature-level tokens apply to every caste, whatever is selected.
+/// - Caste-level tokens before the first `CASTE` or `USE_CASTE` apply to every caste declared
+///   with `CASTE`, even later ones.
+/// - `CASTE` declares and selects a caste, `USE_CASTE:NEW:OLD` declares a caste starting from
+///   what `OLD` has so far.
+/// - `SELECT_CASTE` selects a declared caste, or all castes declared so far with `ALL`, and
+///   `SELECT_ADDITIONAL_CASTE` adds one to the selection.
+///
+/// Tokens nested in another token, like those of an attack or a material, follow it.
+///
+/// [`apply_creature_variations`]: crate::apply_creature_variations
+pub fn effective_castes(creature: &[Tag]) -> Vec<EffectiveCaste> {
+    let (header, body) = match creature.split_first() {
+        Some(split) => split,
+        None => return vec![],
+    };
+    // Tokens are kept as positions in `body`, so every caste can be put back in file order.
+    let mut common = vec![];
+    let mut before_castes = vec![];
+    let mut castes: Vec<(String, Vec<usize>)> = vec![];
+    // `None` until a caste is declared.
+    let mut selected: Option<Vec<usize>> = None;
+    let mut level = Level::Creature;
+    let find = |castes: &[(String, Vec<usize>)], id: &str| castes.iter().position(|c| c.0 == id);
+    for (index, tag) in body.iter().enumerate() {
+        let arg = |n: usize| tag.args.get(n).map(String::as_str).unwrap_or_default();
+        match tag.name.as_str() {
+            "CASTE" => {
+                let caste = find(&castes, arg(0)).unwrap_or_else(|| {
+                    castes.push((arg(0).to_owned(), before_castes.clone()));
+                    castes.len() - 1
+                });
+                selected = Some(vec![caste]);
+            }
+            "USE_CASTE" => {
+                let tags = match find(&castes, arg(1)) {
+                    Some(old) => castes[old].1.clone(),
+                    None => before_castes.clone(),
+                };
+                castes.push((arg(0).to_owned(), tags));
+                selected = Some(vec![castes.len() - 1]);
+            }
+            "SELECT_CASTE" if castes.is_empty() => {}
+            "SELECT_CASTE" if arg(0) == "ALL" => selected = Some((0..castes.len()).collect()),
+            "SELECT_CASTE" => selected = Some(find(&castes, arg(0)).into_iter().collect()),
+            "SELECT_ADDITIONAL_CASTE" => {
+                if let (Some(selected), Some(caste)) = (&mut selected, find(&castes, arg(0))) {
+                    selected.push(caste);
+                }
+            }
+            name => {
+                if CREATURE_TOKENS.contains(&name) {
+                    level = Level::Creature;
+                } else if CASTE_TOKENS.contains(&name) {
+                    level = Level::Caste;
+                }
+                match (level, &selected) {
+                    (Level::Creature, _) => common.push(index),
+                    (Level::Caste, None) => before_castes.push(index),
+                    (Level::Caste, Some(selected)) => {
+                        for &caste in selected {
+                            castes[caste].1.push(index);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    let castes: Vec<(Option<String>, Vec<usize>)> = if castes.is_empty() {
+        vec![(None, before_castes)]
+    } else {
+        castes
+            .into_iter()
+            .map(|(id, tags)| (Some(id), tags))
+            .collect()
+    };
+    castes
+        .into_iter()
+        .map(|(id, mut positions)| {
+            positions.extend(&common);
+            positions.sort_unstable();
+            positions.dedup();
+            let tags = std::iter::once(header.clone())
+                .chain(positions.into_iter().map(|index| body[index].clone()))
+                .collect();
+            EffectiveCaste { id, tags }
+        })
+        .collect()
+}
+
+impl CreatureExpander<'_> {
+    /// The castes of a creature after expanding it, see [`effective_castes`].
+    pub fn effective_castes(&mut self, id: &str) -> Result<Vec<EffectiveCaste>> {
+        Ok(effective_castes(&self.creature_tags(id)?))
+    }
+}
+
+impl RawSet {
+    /// The castes of a creature as the game sees them, with `COPY_TAGS_FROM`, creature
+    /// variations and caste selections resolved.
+    pub fn effective_castes(&self, id: &str) -> Result<Vec<EffectiveCaste>> {
+        CreatureExpander::new(self).effective_castes(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn caste_selection() {
+        let creature = parse_tags(
+            "[CREATURE:TOAD_WOMAN]
+                [BODY_SIZE:0:0:1000]
+                [CASTE:FEMALE]
+                    [FEMALE]
+                [CASTE:MALE]
+                    [MALE]
+                [USE_CASTE:QUEEN:FEMALE]
+                    [CASTE_NAME:queen:queens:queen]
+                [SELECT_CASTE:MALE]
+                [SELECT_ADDITIONAL_CASTE:QUEEN]
+                    [NAME:toad woman:toad women:toad woman]
+                    [USE_MATERIAL_TEMPLATE:SKIN:SKIN_TEMPLATE]
+                        [STATE_COLOR:ALL_SOLID:GREEN]
+                    [BODY_SIZE:1:0:2000]
+                [SELECT_CASTE:ALL]
+                    [ATTACK:KICK:BODYPART:BY_CATEGORY:FOOT]
+                        [ATTACK_SKILL:STANCE_STRIKE]",
+        );
+        let castes: Vec<_> = effective_castes(&creature)
+            .into_iter()
+            .map(|caste| (caste.id.unwrap_or_default(), write_tags(&caste.tags)))
+            .collect();
+        let common = "[NAME:toad woman:toad women:toad woman]\n\
+                      [USE_MATERIAL_TEMPLATE:SKIN:SKIN_TEMPLATE]\n\
+                      [STATE_COLOR:ALL_SOLID:GREEN]\n";
+        let attack = "[ATTACK:KICK:BODYPART:BY_CATEGORY:FOOT]\n[ATTACK_SKILL:STANCE_STRIKE]\n";
+        let header = "[CREATURE:TOAD_WOMAN]\n[BODY_SIZE:0:0:1000]\n";
+        assert_eq!(
+            castes,
+            [
+                ("FEMALE".to_owned(), format!("{}[FEMALE]\n{}{}", header, common, attack)),
+                (
+                    "MALE".to_owned(),
+                    format!("{}[MALE]\n{}[BODY_SIZE:1:0:2000]\n{}", header, common, attack)
+                ),
+                (
+                    "QUEEN".to_owned(),
+                    format!(
+                        "{}[FEMALE]\n[CASTE_NAME:queen:queens:queen]\n{}[BODY_SIZE:1:0:2000]\n{}",
+                        header, common, attack
+                    )
+                ),
+            ]
+        );
+        assert_eq!(
+            effective_castes(&parse_tags("[CREATURE:TOAD][BODY_SIZE:0:0:10]")),
+            [EffectiveCaste {
+                id: None,
+                tags: parse_tags("[CREATURE:TOAD][BODY_SIZE:0:0:10]"),
+            }]
+        );
+    }
+    #[test]
+    fn token_levels_match_aliases() {
+        use std::collections::BTreeSet;
+
+        use super::token_levels::{CASTE_TOKENS, CREATURE_TOKENS};
+        use crate::aliases::{item_aliases, structure_aliases};
+
+        let aliases = structure_aliases();
+        let caste = item_aliases(&aliases, "Caste");
+        let creature = item_aliases(&aliases, "CreatureToken");
+        // Caste declarations and selections are handled before looking at either list.
+        let selections = BTreeSet::from(["CASTE", "USE_CASTE", "SELECT_CASTE"]);
+        let caste_tokens: BTreeSet<&str> = CASTE_TOKENS.iter().copied().collect();
+        let creature_tokens: BTreeSet<&str> = CREATURE_TOKENS.iter().copied().collect();
+        assert_eq!(caste_tokens.len(), CASTE_TOKENS.len());
+        assert_eq!(creature_tokens.len(), CREATURE_TOKENS.len());
+        assert_eq!(caste_tokens, &caste - &selections);
+        assert_eq!(
+            creature_tokens,
+            &(&creature - &caste) - &BTreeSet::from(["CREATURE", "SELECT_CASTE"])
+        );
+    }
+}
@@ -0,0 +1,358 @@
+//! Which tokens of a creature belong to the creature and which to its castes, taken from the
+//! aliases of [`CreatureToken`](crate::CreatureToken) and [`Caste`](crate::Caste), which the
+//! tests check them against. Tokens in neither list belong to the token before them.
+
+/// Tokens that apply to the creature as a whole, whichever castes are selected.
+pub(super) const CREATURE_TOKENS: &[&str] = &[
+    "TISSUE",
+    "USE_TISSUE",
+    "USE_TISSUE_TEMPLATE",
+    "SELECT_TISSUE",
+    "REMOVE_TISSUE",
+    "MATERIAL",
+    "USE_MATERIAL",
+    "USE_MATERIAL_TEMPLATE",
+    "SELECT_MATERIAL",
+    "REMOVE_MATERIAL",
+    "ALTTILE",
+    "ARTIFICIAL_HIVEABLE",
+    "BIOME",
+    "CHANGE_FREQUENCY_PERC",
+    "CLUSTER_NUMBER",
+    "COLOR",
+    "CREATURE_SOLDIER_TILE",
+    "CREATURE_TILE",
+    "DOES_NOT_EXIST",
+    "EQUIPMENT_WAGON",
+    "EVIL",
+    "FANCIFUL",
+    "FREQUENCY",
+    "GENERAL_BABY_NAME",
+    "GENERAL_CHILD_NAME",
+    "GENERATED",
+    "GLOWCOLOR",
+    "GLOWTILE",
+    "GOOD",
+    "HFID",
+    "HIVE_PRODUCT",
+    "LARGE_ROAMING",
+    "LOCAL_POPS_CONTROLLABLE",
+    "LOOSE_CLUSTERS",
+    "MUNDANE",
+    "NAME",
+    "PREFSTRING",
+    "PROFESSION_NAME",
+    "SAVAGE",
+    "SMELL_TRIGGER",
+    "SOLDIER_ALTTILE",
+    "SPEECH",
+    "SPEECH_FEMALE",
+    "SPEECH_MALE",
+    "SPHERE",
+    "TRIGGERABLE_GROUP",
+    "UBIQUITOUS",
+    "UNDERGROUND_DEPTH",
+    "VERMIN_EATER",
+    "VERMIN_FISH",
+    "VERMIN_GROUNDER",
+    "VERMIN_ROTTER",
+    "VERMIN_SOIL",
+    "VERMIN_SOIL_COLONY",
+    "APPLY_CURRENT_CREATURE_VARIATION",
+    "COPY_TAGS_FROM",
+    "CV_ADD_TAG",
+    "CV_NEW_TAG",
+    "CV_REMOVE_TAG",
+    "GO_TO_TAG",
+    "GO_TO_END",
+    "GO_TO_START",
+    "CV_CONVERT_TAG",
+];
+
+/// Tokens that apply to the selected castes.
+pub(super) const CASTE_TOKENS: &[&str] = &[
+    "ATTACK",
+    "CAN_DO_INTERACTION",
+    "SET_BP_GROUP",
+    "SET_TL_GROUP",
+    "SELECT_TISSUE_LAYER",
+    "TISSUE_LAYER",
+    "TISSUE_LAYER_OVER",
+    "TISSUE_LAYER_UNDER",
+    "ADOPTS_OWNER",
+    "ALCOHOL_DEPENDENT",
+    "ALL_ACTIVE",
+    "AMBUSHPREDATOR",
+    "AMPHIBIOUS",
+    "APPLY_CREATURE_VARIATION",
+    "AQUATIC",
+    "ARENA_RESTRICTED",
+    "AT_PEACE_WITH_WILDLIFE",
+    "ATTACK_TRIGGER",
+    "BABY",
+    "BABYNAME",
+    "BEACH_FREQUENCY",
+    "BENIGN",
+    "BLOOD",
+    "BLOODSUCKER",
+    "BODY",
+    "BODY_APPEARANCE_MODIFIER",
+    "BODY_DETAIL_PLAN",
+    "BODY_SIZE",
+    "BODYGLOSS",
+    "BONECARN",
+    "BUILDINGDESTROYER",
+    "CAN_LEARN",
+    "CAN_SPEAK",
+    "CANNOT_CLIMB",
+    "CANNOT_JUMP",
+    "CANNOT_UNDEAD",
+    "CANOPENDOORS",
+    "CARNIVORE",
+    "CASTE_ALTTILE",
+    "CASTE_COLOR",
+    "CASTE_GLOWCOLOR",
+    "CASTE_GLOWTILE",
+    "CASTE_NAME",
+    "CASTE_PROFESSION_NAME",
+    "CASTE_SOLDIER_ALTTILE",
+    "CASTE_SOLDIER_TILE",
+    "CASTE_SPEECH",
+    "CASTE_TILE",
+    "CAVE_ADAPT",
+    "CHANGE_BODY_SIZE_PERC",
+    "CHILD",
+    "CHILDNAME",
+    "CLUTCH_SIZE",
+    "COLONY_EXTERNAL",
+    "COMMON_DOMESTIC",
+    "CONVERTED_SPOUSE",
+    "COOKABLE_LIVE",
+    "CRAZED",
+    "CREATURE_CLASS",
+    "CREPUSCULAR",
+    "CURIOUSBEAST_EATER",
+    "CURIOUSBEAST_GUZZLER",
+    "CURIOUSBEAST_ITEM",
+    "DEMON",
+    "DESCRIPTION",
+    "DIE_WHEN_VERMIN_BITE",
+    "DIFFICULTY",
+    "DIURNAL",
+    "DIVE_HUNTS_VERMIN",
+    "EGG_MATERIAL",
+    "EGG_SIZE",
+    "EQUIPS",
+    "EXTRA_BUTCHER_OBJECT",
+    "EXTRACT",
+    "EXTRAVISION",
+    "FEATURE_ATTACK_GROUP",
+    "FEATURE_BEAST",
+    "FEMALE",
+    "FIREIMMUNE",
+    "FIREIMMUNE_SUPER",
+    "FISHITEM",
+    "FIXED_TEMP",
+    "FLEEQUICK",
+    "FLIER",
+    "GAIT",
+    "GENERAL_MATERIAL_FORCE_MULTIPLIER",
+    "GETS_INFECTIONS_FROM_ROT",
+    "GETS_WOUND_INFECTIONS",
+    "GNAWER",
+    "GOBBLE_VERMIN_CLASS",
+    "GOBBLE_VERMIN_CREATURE",
+    "GRASSTRAMPLE",
+    "GRAVITATE_BODY_SIZE",
+    "GRAZER",
+    "HABIT",
+    "HABIT_NUM",
+    "HAS_NERVES",
+    "HASSHELL",
+    "HOMEOTHERM",
+    "HUNTS_VERMIN",
+    "IMMOBILE",
+    "IMMOBILE_LAND",
+    "IMMOLATE",
+    "INTELLIGENT",
+    "ITEMCORPSE",
+    "ITEMCORPSE_QUALITY",
+    "LAIR",
+    "LAIR_CHARACTERISTIC",
+    "LAIR_HUNTER",
+    "LAIR_HUNTER_SPEECH",
+    "LARGE_PREDATOR",
+    "LAYS_EGGS",
+    "LAYS_UNUSUAL_EGGS",
+    "LIGAMENTS",
+    "LIGHT_GEN",
+    "LIKES_FIGHTING",
+    "LISP",
+    "LITTERSIZE",
+    "LOCAL_POPS_PRODUCE_HEROES",
+    "LOCKPICKER",
+    "LOW_LIGHT_VISION",
+    "MAGICAL",
+    "MAGMA_VISION",
+    "MALE",
+    "MANNERISM_ARMS",
+    "MANNERISM_BREATH",
+    "MANNERISM_CHEEK",
+    "MANNERISM_EAR",
+    "MANNERISM_EYELIDS",
+    "MANNERISM_EYES",
+    "MANNERISM_FEET",
+    "MANNERISM_FINGERS",
+    "MANNERISM_HAIR",
+    "MANNERISM_HANDS",
+    "MANNERISM_HEAD",
+    "MANNERISM_KNUCKLES",
+    "MANNERISM_LAUGH",
+    "MANNERISM_LEG",
+    "MANNERISM_LIPS",
+    "MANNERISM_MOUTH",
+    "MANNERISM_NAILS",
+    "MANNERISM_NOSE",
+    "MANNERISM_POSTURE",
+    "MANNERISM_SIT",
+    "MANNERISM_SMILE",
+    "MANNERISM_STRETCH",
+    "MANNERISM_TONGUE",
+    "MANNERISM_WALK",
+    "MATERIAL_FORCE_MULTIPLIER",
+    "MATUTINAL",
+    "MAXAGE",
+    "MEANDERER",
+    "MEGABEAST",
+    "MENT_ATT_CAP_PERC",
+    "MENT_ATT_RANGE",
+    "MENT_ATT_RATES",
+    "MILKABLE",
+    "MISCHIEVOUS",
+    "MISCHIEVIOUS",
+    "MODVALUE",
+    "MOUNT",
+    "MOUNT_EXOTIC",
+    "MULTIPART_FULL_VISION",
+    "MULTIPLE_LITTER_RARE",
+    "NATURAL",
+    "NATURAL_ANIMAL",
+    "NATURAL_SKILL",
+    "NIGHT_CREATURE_BOGEYMAN",
+    "NIGHT_CREATURE_EXPERIMENTER",
+    "NIGHT_CREATURE_HUNTER",
+    "NIGHT_CREATURE_NIGHTMARE",
+    "NO_AUTUMN",
+    "NO_CONNECTIONS_FOR_MOVEMENT",
+    "NO_DIZZINESS",
+    "NO_DRINK",
+    "NO_EAT",
+    "NO_FEVERS",
+    "NO_GENDER",
+    "NO_PHYS_ATT_GAIN",
+    "NO_PHYS_ATT_RUST",
+    "NO_SLEEP",
+    "NO_SPRING",
+    "NO_SUMMER",
+    "NO_THOUGHT_CENTER_FOR_MOVEMENT",
+    "NO_UNIT_TYPE_COLOR",
+    "NO_VEGETATION_PERTURB",
+    "NO_WINTER",
+    "NOBONES",
+    "NOBREATHE",
+    "NOCTURNAL",
+    "NOEMOTION",
+    "NOEXERT",
+    "NOFEAR",
+    "NOMEAT",
+    "NONAUSEA",
+    "NOPAIN",
+    "NOSKIN",
+    "NOSKULL",
+    "NOSMELLYROT",
+    "NOSTUCKINS",
+    "NOSTUN",
+    "NOT_BUTCHERABLE",
+    "NOT_LIVING",
+    "NOTHOUGHT",
+    "ODOR_LEVEL",
+    "ODOR_STRING",
+    "OPPOSED_TO_LIFE",
+    "ORIENTATION",
+    "OUTSIDER_CONTROLLABLE",
+    "PACK_ANIMAL",
+    "PARALYZEIMMUNE",
+    "PATTERNFLIER",
+    "PEARL",
+    "PENETRATEPOWER",
+    "PERSONALITY",
+    "PET",
+    "PET_EXOTIC",
+    "PETVALUE",
+    "PETVALUE_DIVISOR",
+    "PHYS_ATT_CAP_PERC",
+    "PHYS_ATT_RANGE",
+    "PHYS_ATT_RATES",
+    "POP_RATIO",
+    "POPULATION_NUMBER",
+    "POWER",
+    "PRONE_TO_RAGE",
+    "PUS",
+    "RELSIZE",
+    "REMAINS",
+    "REMAINS_COLOR",
+    "REMAINS_ON_VERMIN_BITE_DEATH",
+    "REMAINS_UNDETERMINED",
+    "RETRACT_INTO_BP",
+    "RETURNS_VERMIN_KILLS_TO_OWNER",
+    "ROOT_AROUND",
+    "SECRETION",
+    "SEMIMEGABEAST",
+    "SENSE_CREATURE_CLASS",
+    "SKILL_LEARN_RATE",
+    "SKILL_LEARN_RATES",
+    "SKILL_RATE",
+    "SKILL_RATES",
+    "SKILL_RUST_RATE",
+    "SKILL_RUST_RATES",
+    "SLOW_LEARNER",
+    "SMALL_REMAINS",
+    "SOUND",
+    "SPECIFIC_FOOD",
+    "SPOUSE_CONVERSION_TARGET",
+    "SPOUSE_CONVERTER",
+    "SPREAD_EVIL_SPHERES_IF_RULER",
+    "STANCE_CLIMBER",
+    "STANDARD_GRAZER",
+    "STRANGE_MOODS",
+    "SUPERNATURAL",
+    "SWIMS_INNATE",
+    "SWIMS_LEARNED",
+    "SYNDROME_DILUTION_FACTOR",
+    "TENDONS",
+    "THICKWEB",
+    "TITAN",
+    "TRADE_CAPACITY",
+    "TRAINABLE",
+    "TRAINABLE_HUNTING",
+    "TRAINABLE_WAR",
+    "TRANCES",
+    "TRAPAVOID",
+    "UNDERSWIM",
+    "UNIQUE_DEMON",
+    "UTTERANCES",
+    "VEGETATION",
+    "VERMIN_BITE",
+    "VERMIN_HATEABLE",
+    "VERMIN_MICRO",
+    "VERMIN_NOFISH",
+    "VERMIN_NOROAM",
+    "VERMIN_NOTRAP",
+    "VERMINHUNTER",
+    "VESPERTINE",
+    "VIEWRANGE",
+    "VISION_ARC",
+    "WAGON_PULLER",
+    "WEBBER",
+    "WEBIMMUNE",
+];
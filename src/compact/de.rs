@@ -0,0 +1,565 @@
+//! Reads the compact form, filling in what it leaves out from what the structures expect: IDs go
+//! back into the first field of their token, left out arguments and tokens come back as unset.
+
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    Unexpected, VariantAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+use serde_yaml::{Mapping, Value};
+
+use super::{ARGUMENT_KINDS, LIST_TOKENS, NAMED_TOKENS, USUAL_KINDS};
+use crate::node::NodeError;
+use crate::units::parse_quantity;
+
+type Result<T> = std::result::Result<T, NodeError>;
+
+pub(super) fn from_value<T: DeserializeOwned>(value: Value) -> Result<T> {
+    T::deserialize(Compact::new(value))
+}
+
+pub(super) fn from_keyed_value<T: DeserializeOwned>(id: String, value: Value) -> Result<T> {
+    T::deserialize(Compact {
+        value: Some(value),
+        id: Some(id),
+    })
+}
+
+macro_rules! deserialize_scalar {
+    ( $fn:ident ) => {
+        fn $fn<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+            match self.value {
+                Some(value @ (Value::Bool(_) | Value::Number(_) | Value::String(_))) => {
+                    value.$fn(visitor).map_err(de::Error::custom)
+                }
+                _ => self.deserialize_any(visitor),
+            }
+        }
+    };
+}
+
+struct Compact {
+    /// `None` for what the compact form leaves out.
+    value: Option<Value>,
+    /// The ID of a keyed token, from the key it is listed under.
+    id: Option<String>,
+}
+
+impl Compact {
+    fn new(value: Value) -> Self {
+        Self {
+            value: Some(value),
+            id: None,
+        }
+    }
+
+    fn missing() -> Self {
+        Self {
+            value: None,
+            id: None,
+        }
+    }
+
+    fn unexpected(&self) -> Unexpected<'_> {
+        match &self.value {
+            None | Some(Value::Null) => Unexpected::Unit,
+            Some(Value::Bool(value)) => Unexpected::Bool(*value),
+            Some(Value::Number(_)) => Unexpected::Other("number"),
+            Some(Value::String(value)) => Unexpected::Str(value),
+            Some(Value::Sequence(_)) => Unexpected::Seq,
+            Some(Value::Mapping(_)) => Unexpected::Map,
+            Some(Value::Tagged(_)) => Unexpected::Other("tagged value"),
+        }
+    }
+
+    fn invalid_type(&self, expected: &dyn de::Expected) -> NodeError {
+        de::Error::invalid_type(self.unexpected(), expected)
+    }
+
+    /// The fields of a token: the ID first if it has one, then every field the compact form
+    /// has, then the ones it leaves out.
+    fn token_fields(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+    ) -> Result<Vec<(String, Compact)>> {
+        // Serde lists the aliases of fields, the token names, along with them.
+        let fields: Vec<&str> = fields
+            .iter()
+            .copied()
+            .filter(|field| !field.contains(char::is_uppercase))
+            .collect();
+        let mut compact = match self.value {
+            None | Some(Value::Null) => Mapping::new(),
+            Some(Value::Mapping(compact)) => compact,
+            Some(args) if self.id.is_some() => {
+                let mut compact = Mapping::new();
+                compact.insert(fields.first().copied().unwrap_or_default().into(), args);
+                compact
+            }
+            Some(value) => return Err(Compact::new(value).invalid_type(&"map")),
+        };
+        let field_of = |key: &Value| {
+            let key = key.as_str()?.to_ascii_lowercase();
+            fields.iter().find(|field| **field == key).copied()
+        };
+        if let Some((_, list)) = LIST_TOKENS.iter().find(|(token, _)| *token == name) {
+            if !compact.is_empty() && compact.keys().all(|key| field_of(key).is_none()) {
+                let mut list_token = Mapping::new();
+                list_token.insert((*list).into(), compact.into());
+                compact = list_token;
+            }
+        }
+        let mut compact = flatten_args(compact, &fields)?;
+        let mut entries = vec![];
+        if let (Some(id), Some(id_field)) = (self.id, fields.first()) {
+            let args = if NAMED_TOKENS.contains(&name) {
+                compact.remove("name").map(name_args)
+            } else {
+                compact.remove(*id_field).map(|args| match args {
+                    Value::Sequence(args) => args,
+                    arg => vec![arg],
+                })
+            };
+            let value = match args {
+                Some(mut args) => {
+                    args.insert(0, id.into());
+                    Value::Sequence(args)
+                }
+                None => id.into(),
+            };
+            entries.push((id_field.to_string(), Compact::new(value)));
+        }
+        for (key, value) in compact {
+            let field = field_of(&key).ok_or_else(|| match key.as_str() {
+                Some(key) => de::Error::custom(format!("unknown token `{}`", key)),
+                None => de::Error::custom("token names must be strings"),
+            })?;
+            // `flag:` without a value sets the flag, like `flag: true`.
+            let value = match value {
+                Value::Null => Value::Bool(true),
+                Value::String(text) => match parse_quantity(field, &text) {
+                    Ok(Some(quantity)) => quantity.into(),
+                    Ok(None) => text.into(),
+                    Err(error) => return Err(de::Error::custom(format!("`{}`: {}", field, error))),
+                },
+                value => value,
+            };
+            entries.push((field.to_owned(), Compact::new(value)));
+        }
+        for field in &fields {
+            if !entries.iter().any(|(key, _)| key == field) {
+                entries.push((field.to_string(), Compact::missing()));
+            }
+        }
+        Ok(entries)
+    }
+}
+
+/// Reads `field_arg: value` as `field: {arg: value}`, like `state_name_all_solid: steel` for
+/// `state_name: {all_solid: steel}`.
+fn flatten_args(compact: Mapping, fields: &[&str]) -> Result<Mapping> {
+    let mut flattened = Mapping::new();
+    for (key, value) in compact {
+        let name = key.as_str().unwrap_or_default().to_ascii_lowercase();
+        let field = fields
+            .iter()
+            .filter(|field| name.len() > field.len() + 1 && name.starts_with(*field))
+            .filter(|field| name.as_bytes()[field.len()] == b'_')
+            .max_by_key(|field| field.len());
+        let field = match field {
+            Some(field) if !fields.contains(&name.as_str()) => *field,
+            _ => {
+                flattened.insert(key, value);
+                continue;
+            }
+        };
+        let args = flattened
+            .entry(field.into())
+            .or_insert_with(|| Mapping::new().into());
+        match args {
+            Value::Mapping(args) => {
+                args.insert(name[field.len() + 1..].into(), value);
+            }
+            _ => {
+                return Err(de::Error::custom(format!(
+                    "`{}` is given both as a list and as `{}`",
+                    field, name
+                )))
+            }
+        }
+    }
+    Ok(flattened)
+}
+
+/// The arguments of a name: `singular` for a standard plural, or `{singular, plural}`.
+fn name_args(name: Value) -> Vec<Value> {
+    match name {
+        Value::Mapping(mut name) => vec![
+            name.remove("singular").unwrap_or_default(),
+            name.remove("plural").unwrap_or_else(|| "STP".into()),
+        ],
+        Value::Sequence(args) => args,
+        singular => vec![singular, "STP".into()],
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Compact {
+    type Error = NodeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            None | Some(Value::Null) => visitor.visit_unit(),
+            Some(Value::Bool(value)) => visitor.visit_bool(value),
+            Some(value @ Value::Number(_)) => {
+                value.deserialize_any(visitor).map_err(de::Error::custom)
+            }
+            Some(Value::String(value)) => visitor.visit_string(value),
+            Some(Value::Sequence(values)) => {
+                visit_seq(values.into_iter().map(Compact::new).collect(), visitor)
+            }
+            Some(Value::Mapping(entries)) => {
+                let entries = entries
+                    .into_iter()
+                    .map(|(key, value)| Ok((map_key(key)?, Compact::new(value))))
+                    .collect::<Result<_>>()?;
+                visit_map(entries, visitor)
+            }
+            Some(Value::Tagged(_)) => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    deserialize_scalar!(deserialize_bool);
+    deserialize_scalar!(deserialize_i8);
+    deserialize_scalar!(deserialize_i16);
+    deserialize_scalar!(deserialize_i32);
+    deserialize_scalar!(deserialize_i64);
+    deserialize_scalar!(deserialize_u8);
+    deserialize_scalar!(deserialize_u16);
+    deserialize_scalar!(deserialize_u32);
+    deserialize_scalar!(deserialize_u64);
+    deserialize_scalar!(deserialize_f32);
+    deserialize_scalar!(deserialize_f64);
+    deserialize_scalar!(deserialize_char);
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            Some(Value::String(value)) => visitor.visit_string(value),
+            // YAML reads names like `2` as numbers.
+            Some(Value::Number(value)) => visitor.visit_string(value.to_string()),
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            None | Some(Value::Null) => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            None | Some(Value::Null) | Some(Value::Bool(true)) => visitor.visit_unit(),
+            _ => Err(self.invalid_type(&"`true` for a flag")),
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            None => visit_seq(vec![], visitor),
+            Some(Value::Sequence(values)) => {
+                visit_seq(values.into_iter().map(Compact::new).collect(), visitor)
+            }
+            // Flags by name, like `{miner: true}`, listing those set.
+            Some(Value::Mapping(entries)) if entries.values().all(Value::is_bool) => {
+                let values = entries
+                    .into_iter()
+                    .filter(|(_, set)| *set == Value::Bool(true))
+                    .map(|(key, _)| Ok(Compact::new(map_key(key)?.into())))
+                    .collect::<Result<_>>()?;
+                visit_seq(values, visitor)
+            }
+            // Keyed tokens, each getting its ID from its key.
+            Some(Value::Mapping(entries)) => {
+                let values = entries
+                    .into_iter()
+                    .map(|(key, value)| {
+                        Ok(Compact {
+                            value: Some(value),
+                            id: Some(map_key(key)?),
+                        })
+                    })
+                    .collect::<Result<_>>()?;
+                visit_seq(values, visitor)
+            }
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        let values = match self.value {
+            // Arguments listed as a map, keyed by their first argument, which is a token name.
+            value if self.id.is_some() => {
+                let id = self.id.unwrap_or_default().to_ascii_uppercase();
+                let mut args = vec![id.into()];
+                match value {
+                    None => {}
+                    Some(Value::Sequence(values)) => args.extend(values),
+                    Some(value) => args.push(value),
+                }
+                args
+            }
+            None => vec![],
+            Some(Value::Sequence(values)) if len != 1 => values,
+            Some(Value::Mapping(name)) if len == 2 && name.contains_key("singular") => {
+                name_args(name.into())
+            }
+            Some(singular @ Value::String(_)) if len == 2 => name_args(singular),
+            Some(value) => vec![value],
+        };
+        let mut values: Vec<Compact> = values.into_iter().map(Compact::new).collect();
+        while values.len() < len {
+            values.push(Compact::missing());
+        }
+        visit_seq(values, visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            None => visit_map(vec![], visitor),
+            Some(Value::Mapping(_)) => self.deserialize_any(visitor),
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let id = self.id.clone();
+        let entries = self.token_fields(name, fields);
+        let entries = match id {
+            Some(id) => entries.map_err(|error| de::Error::custom(format!("{}: {}", id, error))),
+            None => entries,
+        }?;
+        visit_map(entries, visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let usual_kind = USUAL_KINDS.iter().find(|(kind, _)| *kind == name);
+        let (variant, value) = match self.value {
+            // Token names in lower case, like `punish_exile`.
+            Some(Value::String(variant)) if !variants.contains(&variant.as_str()) => {
+                (variant.to_ascii_uppercase(), Compact::missing())
+            }
+            Some(Value::String(variant)) => (variant, Compact::missing()),
+            Some(Value::Mapping(entries))
+                if entries.len() == 1
+                    && (usual_kind.is_none()
+                        || entries
+                            .keys()
+                            .all(|key| variants.contains(&key.as_str().unwrap_or_default()))) =>
+            {
+                let (variant, value) = entries.into_iter().next().unwrap_or_default();
+                let value = Compact {
+                    value: Some(value),
+                    id: self.id,
+                };
+                (map_key(variant)?, value)
+            }
+            Some(args @ Value::Sequence(_)) => {
+                let (_, variant) = ARGUMENT_KINDS
+                    .iter()
+                    .find(|(kind, _)| *kind == name)
+                    .ok_or_else(|| de::Error::invalid_type(Unexpected::Seq, &visitor))?;
+                let value = Compact {
+                    value: Some(args),
+                    id: self.id,
+                };
+                (variant.to_string(), value)
+            }
+            value => match usual_kind {
+                Some((_, variant)) => (variant.to_string(), Compact { value, id: self.id }),
+                None => {
+                    let compact = Compact { value, id: None };
+                    return Err(compact.invalid_type(&"string or map with a single key"));
+                }
+            },
+        };
+        visitor.visit_enum(Enum { variant, value })
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 bytes byte_buf
+    }
+}
+
+fn map_key(key: Value) -> Result<String> {
+    match key {
+        Value::String(key) => Ok(key),
+        Value::Number(key) => Ok(key.to_string()),
+        key => Err(Compact::new(key).invalid_type(&"string key")),
+    }
+}
+
+fn visit_seq<'de, V: Visitor<'de>>(values: Vec<Compact>, visitor: V) -> Result<V::Value> {
+    let len = values.len();
+    let mut seq = Seq {
+        iter: values.into_iter(),
+    };
+    let value = visitor.visit_seq(&mut seq)?;
+    if seq.iter.len() == 0 {
+        Ok(value)
+    } else {
+        Err(de::Error::invalid_length(len, &"fewer arguments"))
+    }
+}
+
+fn visit_map<'de, V: Visitor<'de>>(
+    entries: Vec<(String, Compact)>,
+    visitor: V,
+) -> Result<V::Value> {
+    let mut map = Map {
+        iter: entries.into_iter(),
+        value: None,
+    };
+    visitor.visit_map(&mut map)
+}
+
+struct Seq {
+    iter: std::vec::IntoIter<Compact>,
+}
+
+impl<'de> SeqAccess<'de> for Seq {
+    type Error = NodeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        self.iter
+            .next()
+            .map(|value| seed.deserialize(value))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct Map {
+    iter: std::vec::IntoIter<(String, Compact)>,
+    value: Option<(String, Compact)>,
+}
+
+impl<'de> MapAccess<'de> for Map {
+    type Error = NodeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some((key.clone(), value));
+                seed.deserialize(key.into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<T::Value> {
+        match self.value.take() {
+            Some((key, value)) => seed
+                .deserialize(value)
+                .map_err(|error| de::Error::custom(format!("{}: {}", key, error))),
+            None => Err(de::Error::custom("value is missing")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct Enum {
+    variant: String,
+    value: Compact,
+}
+
+impl<'de> EnumAccess<'de> for Enum {
+    type Error = NodeError;
+    type Variant = Compact;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Compact)> {
+        let variant = seed.deserialize(self.variant.into_deserializer())?;
+        Ok((variant, self.value))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Compact {
+    type Error = NodeError;
+
+    fn unit_variant(self) -> Result<()> {
+        match self.value {
+            None => Ok(()),
+            Some(_) => Err(self.invalid_type(&"unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        de::Deserializer::deserialize_struct(self, "", fields, visitor)
+    }
+}
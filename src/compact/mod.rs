@@ -0,0 +1,636 @@
+//! The compact form of raws, for writing and reviewing mods as YAML, TOML or JSON (see
+//! `example.yml`):
+//!
+//! - Objects are grouped by object type, like `body` or `creature`, and keyed by their ID.
+//! - Body parts are keyed by their ID too, and a body that is nothing but parts is just its
+//!   parts.
+//! - Flags are `true`, or left empty in YAML. Unset tokens and empty lists are left out.
+//! - The arguments of a token are a list without the unset arguments at the end, a single
+//!   argument is written as is.
+//! - Names are `singular` when the plural is the standard one, `{singular, plural}` otherwise.
+//! - Objects of another kind than the usual one for their type are `{Kind: {...}}`, except body
+//!   glosses, which are the list of their arguments.
+//!
+//! When reading, a list of arguments can be a map keyed by the first argument, like
+//! `state_name: {all_solid: steel}`, and `state_name_all_solid: steel` is short for that. A list
+//! of names can be a map of flags, like `permitted_job: {miner: true, cook: false}`.
+//!
+//! Enums take their variant names, like `contype: Head`, token names like `HEAD` or `head` work
+//! as well.
+//!
+//! TOML has no null, so unset arguments before set ones are written as empty strings there.
+//!
+//! A whole raw set can be written too, see [`RawSet::to_compact`]. There an ID defined twice
+//! keeps the object loaded last and reports the other, instead of failing.
+
+mod de;
+
+use std::collections::HashMap;
+use std::fmt;
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+
+use crate::node::{to_node, Node, Number};
+use crate::raw_set::{RawSet, Source};
+use crate::structure::{DFRaw, ObjectToken};
+
+/// The key of each object type in the compact form, with its field in `ObjectToken`.
+const OBJECT_TYPES: &[(&str, &str)] = &[
+    ("body", "body_tokens"),
+    ("body_detail_plan", "body_detail_plan_tokens"),
+    ("building", "building_tokens"),
+    ("creature", "creature_tokens"),
+    ("creature_variation", "creature_variation_tokens"),
+    ("descriptor_color", "color_tokens"),
+    ("descriptor_pattern", "pattern_tokens"),
+    ("descriptor_shape", "shape_tokens"),
+    ("entity", "entity_tokens"),
+    ("graphics", "graphics_tokens"),
+    ("interaction", "interaction_tokens"),
+    ("inorganic", "inorganic_tokens"),
+    ("item", "item_tokens"),
+    ("language", "language_tokens"),
+    ("material_template", "material_tokens"),
+    ("plant", "plant_tokens"),
+    ("reaction", "reaction_tokens"),
+    ("tissue_template", "tissue_template_tokens"),
+];
+
+/// Object types that come in several kinds, with the kind that is written without its name.
+const USUAL_KINDS: &[(&str, &str)] = &[
+    ("BodyObjectToken", "BodyToken"),
+    ("BuildingToken", "Workshop"),
+    ("GraphicsToken", "TilePage"),
+    ("ItemToken", "AmmoToken"),
+    ("LanguageToken", "WordToken"),
+];
+
+/// Kinds of objects that are written as the arguments after their ID, with their object type.
+const ARGUMENT_KINDS: &[(&str, &str)] = &[("BodyObjectToken", "BodyGlossToken")];
+
+/// Tokens other than objects that are keyed by ID wherever they are listed, with the field that
+/// holds their ID. Objects keep it in `reference`.
+const KEYED_TOKENS: &[(&str, &str)] = &[("BodyPartToken", "bp"), ("BodyGlossToken", "bodygloss")];
+
+/// Tokens made of nothing but a list of keyed tokens, with the field of that list.
+const LIST_TOKENS: &[(&str, &str)] = &[("BodyToken", "bp")];
+
+/// Tokens with a name after their ID, written as `name`.
+const NAMED_TOKENS: &[&str] = &["BodyPartToken"];
+
+/// Fields holding a list of names.
+const NAME_LISTS: &[(&str, &str)] = &[("BodyPartToken", "individual_name")];
+
+/// Converts raws to the compact form.
+pub fn to_compact(raw: &DFRaw) -> Result<Value> {
+    let mut compact = Mapping::new();
+    if !raw.header.is_empty() {
+        compact.insert("header".into(), raw.header.clone().into());
+    }
+    for object_token in &raw.object_tokens {
+        add_objects(&mut compact, object_token)?;
+    }
+    Ok(compact.into())
+}
+
+/// Converts the objects of an object token to the compact form, grouped by object type too.
+pub fn object_to_compact(object_token: &ObjectToken) -> Result<Value> {
+    let mut compact = Mapping::new();
+    add_objects(&mut compact, object_token)?;
+    Ok(compact.into())
+}
+
+/// Reads raws from the compact form.
+pub fn from_compact(compact: Value) -> Result<DFRaw> {
+    let compact = match compact {
+        Value::Mapping(compact) => compact,
+        _ => bail!("expected a map of object types"),
+    };
+    let mut raw = DFRaw::default();
+    for (key, objects) in compact {
+        let key = key.as_str().context("object types must be strings")?;
+        if key == "header" {
+            raw.header = objects
+                .as_str()
+                .context("`header` must be a string")?
+                .to_owned();
+            continue;
+        }
+        let (_, field) = OBJECT_TYPES
+            .iter()
+            .find(|(object_type, _)| object_type.eq_ignore_ascii_case(key))
+            .with_context(|| format!("unknown object type `{}`", key))?;
+        let mut object_token = Mapping::new();
+        object_token.insert((*field).into(), objects);
+        let object_token =
+            de::from_value(object_token.into()).with_context(|| format!("in `{}`", key))?;
+        raw.object_tokens.push(object_token);
+    }
+    Ok(raw)
+}
+
+/// Reads an object from its ID and the rest of it in the compact form.
+pub fn from_compact_object<T: DeserializeOwned>(id: &str, compact: Value) -> Result<T> {
+    Ok(de::from_keyed_value(id.to_owned(), compact)?)
+}
+
+/// Writes raws as compact YAML.
+pub fn to_yaml(raw: &DFRaw) -> Result<String> {
+    Ok(serde_yaml::to_string(&to_compact(raw)?)?)
+}
+
+/// Reads raws from compact YAML.
+pub fn from_yaml(yaml: &str) -> Result<DFRaw> {
+    from_compact(serde_yaml::from_str(yaml)?)
+}
+
+/// Writes the objects of an object token as compact YAML.
+pub fn object_to_yaml(object_token: &ObjectToken) -> Result<String> {
+    Ok(serde_yaml::to_string(&object_to_compact(object_token)?)?)
+}
+
+/// Writes raws as compact TOML, where unset arguments before set ones are empty strings, since
+/// TOML has no null.
+pub fn to_toml(raw: &DFRaw) -> Result<String> {
+    Ok(toml::to_string(&without_nulls(to_compact(raw)?))?)
+}
+
+/// Writes the objects of an object token as compact TOML, see [`to_toml`].
+pub fn object_to_toml(object_token: &ObjectToken) -> Result<String> {
+    Ok(toml::to_string(&without_nulls(object_to_compact(
+        object_token,
+    )?))?)
+}
+
+/// Writes raws as compact JSON.
+pub fn to_compact_json(raw: &DFRaw) -> Result<String> {
+    Ok(serde_json::to_string_pretty(&to_compact(raw)?)?)
+}
+
+/// Reads raws from compact JSON.
+pub fn from_compact_json(json: &str) -> Result<DFRaw> {
+    from_compact(serde_json::from_str(json)?)
+}
+
+/// A raw set in the compact form, see [`RawSet::to_compact`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompactRawSet {
+    /// The objects grouped by object type and keyed by ID, like [`to_compact`] gives them.
+    pub compact: Value,
+    /// Objects left out because one loaded later has the same type and ID.
+    pub overridden: Vec<OverriddenObject>,
+}
+
+impl CompactRawSet {
+    /// Writes the raw set as compact YAML.
+    pub fn to_yaml(&self) -> Result<String> {
+        Ok(serde_yaml::to_string(&self.compact)?)
+    }
+
+    /// Writes the raw set as compact TOML, see [`to_toml`].
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string(&without_nulls(self.compact.clone()))?)
+    }
+}
+
+/// An object left out of the compact form of a raw set, because an object loaded later has the
+/// same type and ID.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct OverriddenObject {
+    /// Where the object that was left out was defined.
+    pub source: Source,
+    /// The type used in `[OBJECT:...]`, e.g. `CREATURE`.
+    pub object_type: &'static str,
+    pub id: String,
+    /// Where the object that was kept was defined.
+    pub overridden_by: Source,
+}
+
+impl fmt::Display for OverriddenObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}:{} is overridden by {}",
+            self.source, self.object_type, self.id, self.overridden_by
+        )
+    }
+}
+
+impl RawSet {
+    /// Converts every object to the compact form. When an ID is defined twice, the object loaded
+    /// last is kept, the same way later mods override earlier ones, and the other is reported.
+    ///
+    /// ```no_run
+    /// let raws = domni::RawSet::load(["./raw/objects"]);
+    /// let compact = raws.to_compact()?;
+    /// for overridden in &compact.overridden {
+    ///     eprintln!("{}", overridden);
+    /// }
+    /// std::fs::write("raws.yml", compact.to_yaml()?)?;
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn to_compact(&self) -> Result<CompactRawSet> {
+        let mut compact = Mapping::new();
+        let mut overridden = vec![];
+        let mut sources: HashMap<(&str, String), &Source> = HashMap::new();
+        for sourced in self.iter() {
+            let object = match to_node(&sourced.object)? {
+                Node::Variant {
+                    value: Some(object),
+                    ..
+                } => *object,
+                node => bail!("expected an object, got {:?}", node),
+            };
+            let (id, object) =
+                keyed_entry(object).with_context(|| format!("in {}", sourced.source))?;
+            let object_type = sourced.object.object_type();
+            insert_object(&mut compact, &object_type.to_lowercase(), &id, object)?;
+            if let Some(source) = sources.insert((object_type, id.clone()), &sourced.source) {
+                overridden.push(OverriddenObject {
+                    source: source.clone(),
+                    object_type,
+                    id,
+                    overridden_by: sourced.source.clone(),
+                });
+            }
+        }
+        Ok(CompactRawSet {
+            compact: compact.into(),
+            overridden,
+        })
+    }
+}
+
+/// Adds the objects of an object token to the compact form, keyed by ID under their object type.
+fn add_objects(compact: &mut Mapping, object_token: &ObjectToken) -> Result<()> {
+    let fields = match to_node(object_token)? {
+        Node::Struct(_, fields) => fields,
+        node => bail!("expected object lists, got {:?}", node),
+    };
+    for (field, objects) in fields {
+        let objects = match objects {
+            Node::Seq(objects) if !objects.is_empty() => objects,
+            _ => continue,
+        };
+        let (key, _) = OBJECT_TYPES
+            .iter()
+            .find(|(_, object_field)| *object_field == field)
+            .with_context(|| format!("no object type for `{}`", field))?;
+        for object in objects {
+            let (id, object) = keyed_entry(object)?;
+            if insert_object(compact, key, &id, object)?.is_some() {
+                bail!("{} `{}` is defined twice", key, id);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Adds an object under its object type, giving back the object with the same ID it replaces.
+fn insert_object(
+    compact: &mut Mapping,
+    object_type: &str,
+    id: &str,
+    object: Value,
+) -> Result<Option<Value>> {
+    let entry = compact
+        .entry(object_type.into())
+        .or_insert_with(|| Mapping::new().into());
+    let by_id = entry.as_mapping_mut().context("object type is not a map")?;
+    Ok(by_id.insert(id.into(), object))
+}
+
+/// The compact form without nulls: they are left out of maps and empty strings in lists, where
+/// they hold the place of unset arguments.
+fn without_nulls(value: Value) -> Value {
+    match value {
+        Value::Null => String::new().into(),
+        Value::Sequence(values) => values.into_iter().map(without_nulls).collect(),
+        Value::Mapping(entries) => entries
+            .into_iter()
+            .filter(|(_, value)| !value.is_null())
+            .map(|(key, value)| (key, without_nulls(value)))
+            .collect::<Mapping>()
+            .into(),
+        value => value,
+    }
+}
+
+/// The ID of an object or keyed token, and the rest of it.
+fn keyed_entry(node: Node) -> Result<(String, Value)> {
+    match node {
+        Node::Variant {
+            enum_name,
+            variant,
+            value: Some(value),
+        } => {
+            let (name, fields) = match *value {
+                Node::Struct(name, fields) => (name, fields),
+                node => bail!("expected a token in {}, got {:?}", variant, node),
+            };
+            let (id, id_field, mut fields) = keyed_struct(name, fields)?;
+            if ARGUMENT_KINDS.contains(&(enum_name, variant)) && fields.len() == 1 {
+                if let Some(args @ Value::Sequence(_)) = fields.remove(id_field) {
+                    return Ok((id, args));
+                }
+            }
+            let fields = token_value(name, fields);
+            if USUAL_KINDS.contains(&(enum_name, variant)) {
+                return Ok((id, fields));
+            }
+            let mut kind = Mapping::new();
+            kind.insert(variant.into(), fields);
+            Ok((id, kind.into()))
+        }
+        Node::Struct(name, fields) => {
+            let (id, _, fields) = keyed_struct(name, fields)?;
+            Ok((id, token_value(name, fields)))
+        }
+        node => bail!("expected a token, got {:?}", node),
+    }
+}
+
+/// The fields of a keyed token, or just its list for list tokens.
+fn token_value(name: &str, mut fields: Mapping) -> Value {
+    match LIST_TOKENS.iter().find(|(token, _)| *token == name) {
+        Some((_, list)) if fields.len() == 1 => fields
+            .remove(*list)
+            .unwrap_or_else(|| Value::Mapping(fields)),
+        _ => fields.into(),
+    }
+}
+
+/// Splits the ID off a keyed token, giving the ID, the field it was in and the other fields.
+/// Arguments after the ID stay in that field, or go in `name` for named tokens.
+fn keyed_struct(
+    name: &'static str,
+    fields: Vec<(&'static str, Node)>,
+) -> Result<(String, &'static str, Mapping)> {
+    let id_field = KEYED_TOKENS
+        .iter()
+        .find(|(token, _)| *token == name)
+        .map_or("reference", |(_, field)| field);
+    let mut id = None;
+    let mut compact = Mapping::new();
+    for (field, value) in fields {
+        if field != id_field {
+            if let Some(value) = compact_field(name, field, value) {
+                compact.insert(field.into(), value);
+            }
+            continue;
+        }
+        let args = match value {
+            Node::Str(value) | Node::Reference(_, value) => {
+                id = Some(value);
+                continue;
+            }
+            Node::Tuple(mut args) if !args.is_empty() => match args.remove(0) {
+                Node::Str(value) | Node::Reference(_, value) => {
+                    id = Some(value);
+                    args
+                }
+                node => bail!("{} has {:?} for an ID", name, node),
+            },
+            node => bail!("{} has {:?} for an ID", name, node),
+        };
+        if NAMED_TOKENS.contains(&name) {
+            compact.insert("name".into(), name_value(args));
+        } else if let Some(args) = non_empty(compact_node(Node::Tuple(args))) {
+            compact.insert(id_field.into(), args);
+        }
+    }
+    let id = id.with_context(|| format!("{} without an ID", name))?;
+    Ok((id, id_field, compact))
+}
+
+/// A field of a token, `None` if it is unset or empty.
+fn compact_field(token: &str, field: &str, value: Node) -> Option<Value> {
+    let value = match value {
+        Node::Seq(names) if NAME_LISTS.contains(&(token, field)) => {
+            Value::Sequence(names.into_iter().map(name_node).collect())
+        }
+        value => compact_node(value),
+    };
+    non_empty(value)
+}
+
+fn non_empty(value: Value) -> Option<Value> {
+    match &value {
+        Value::Null => None,
+        Value::Sequence(values) if values.is_empty() => None,
+        Value::Mapping(values) if values.is_empty() => None,
+        _ => Some(value),
+    }
+}
+
+/// The compact form of any value.
+fn compact_node(node: Node) -> Value {
+    match node {
+        Node::None => Value::Null,
+        Node::Unit => Value::Bool(true),
+        Node::Bool(value) => Value::Bool(value),
+        Node::Number(Number::PosInt(value)) => value.into(),
+        Node::Number(Number::NegInt(value)) => value.into(),
+        Node::Number(Number::Float(value)) => value.into(),
+        Node::Char(value) => value.to_string().into(),
+        Node::Str(value) | Node::Reference(_, value) => value.into(),
+        Node::Seq(values) => {
+            let keyed = values.iter().all(|value| {
+                matches!(value, Node::Struct(name, _)
+                    if KEYED_TOKENS.iter().any(|(token, _)| token == name))
+            });
+            if keyed && !values.is_empty() {
+                if let Some(by_id) = keyed_map(values.clone()) {
+                    return by_id.into();
+                }
+            }
+            Value::Sequence(values.into_iter().map(compact_node).collect())
+        }
+        Node::Tuple(mut values) => {
+            if is_standard_plural(&values) {
+                return compact_node(values.remove(0));
+            }
+            let single = values.len() == 1;
+            let mut values: Vec<Value> = values.into_iter().map(compact_node).collect();
+            while values.last() == Some(&Value::Null) {
+                values.pop();
+            }
+            if single {
+                values.pop().unwrap_or_default()
+            } else {
+                Value::Sequence(values)
+            }
+        }
+        Node::Map(entries) => Value::Mapping(
+            entries
+                .into_iter()
+                .map(|(key, value)| (compact_node(key), compact_node(value)))
+                .collect(),
+        ),
+        Node::Struct(name, fields) => Value::Mapping(
+            fields
+                .into_iter()
+                .filter_map(|(field, value)| {
+                    compact_field(name, field, value).map(|value| (field.into(), value))
+                })
+                .collect(),
+        ),
+        Node::Variant {
+            variant,
+            value: None,
+            ..
+        } => variant.into(),
+        Node::Variant {
+            variant,
+            value: Some(value),
+            ..
+        } => {
+            let mut variant_value = Mapping::new();
+            variant_value.insert(variant.into(), compact_node(*value));
+            variant_value.into()
+        }
+    }
+}
+
+/// Keyed tokens by ID, `None` if some of them share an ID.
+fn keyed_map(values: Vec<Node>) -> Option<Mapping> {
+    let mut by_id = Mapping::new();
+    for value in values {
+        let (id, value) = keyed_entry(value).ok()?;
+        if by_id.insert(id.into(), value).is_some() {
+            return None;
+        }
+    }
+    Some(by_id)
+}
+
+/// Whether a tuple is a name with the standard plural, like `throat:STP`.
+fn is_standard_plural(values: &[Node]) -> bool {
+    matches!(
+        values,
+        [
+            Node::Str(_),
+            Node::Variant {
+                enum_name: "StandardPluralEnum",
+                value: None,
+                ..
+            }
+        ]
+    )
+}
+
+/// A singular and plural name, `{singular, plural}` unless the plural is the standard one.
+fn name_value(values: Vec<Node>) -> Value {
+    match <[Node; 2]>::try_from(values) {
+        Ok([Node::Str(singular), Node::Str(plural)]) => {
+            let mut name = Mapping::new();
+            name.insert("singular".into(), singular.into());
+            name.insert("plural".into(), plural.into());
+            name.into()
+        }
+        Ok(values) => compact_node(Node::Tuple(values.into())),
+        Err(values) => compact_node(Node::Tuple(values)),
+    }
+}
+
+fn name_node(node: Node) -> Value {
+    match node {
+        Node::Tuple(values) => name_value(values),
+        node => compact_node(node),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn compact_yaml() -> Result<()> {
+        use crate::core::Choose;
+
+        let raw = from_yaml(&std::fs::read_to_string("example.yml")?)?;
+        assert!(raw == from_compact_json(&std::fs::read_to_string("example.json")?)?);
+        let bodies = &raw.object_tokens[0].body_tokens;
+        assert_eq!(bodies.len(), 4);
+        let parts = |index: usize| match &bodies[index] {
+            BodyObjectToken::BodyToken(body) => body.bp.clone(),
+            body => panic!("expected a body, got {:?}", body),
+        };
+        let throat = &parts(0)[0];
+        assert_eq!(throat.bp.as_ref().map(|bp| bp.1.as_str()), Some("throat"));
+        assert!(throat.throat.is_some() && throat.embedded.is_some());
+        let tooth = &parts(1)[0];
+        assert_eq!(tooth.contype, Some(ConTypeEnum::Head));
+        assert_eq!(tooth.number, Some(6));
+        assert_eq!(tooth.individual_name.len(), 6);
+        assert_eq!(
+            tooth.individual_name[5],
+            ("left eye tooth".to_owned(), Choose::Choice2("left eye teeth".to_owned()))
+        );
+        let upper_body = parts(2)[0].bp.clone().unwrap();
+        assert_eq!(upper_body.2, Choose::Choice2("upper bodies".to_owned()));
+        match &bodies[3] {
+            BodyObjectToken::BodyGlossToken(gloss) => {
+                let (id, singular, ..) = gloss.bodygloss.as_ref().unwrap();
+                assert_eq!((id.0.as_str(), singular.as_str()), ("PAW", "foot"));
+            }
+            body => panic!("expected a gloss, got {:?}", body),
+        }
+        assert!(from_yaml(&to_yaml(&raw)?)? == raw);
+        assert!(from_yaml("body:\n  THROAT:\n    THROAT:\n      wings: true\n").is_err());
+        Ok(())
+    }
+    #[test]
+    fn compact_toml() -> Result<()> {
+        let raw = from_yaml(&std::fs::read_to_string("example.yml")?)?;
+        let toml = to_toml(&raw)?;
+        assert!(toml.contains("[body.THROAT.THROAT]\nname = \"throat\"\n"));
+        assert_eq!(raw.object_tokens.len(), 1);
+        assert_eq!(object_to_yaml(&raw.object_tokens[0])?, to_yaml(&raw)?);
+        let raw = from_yaml("inorganic:\n  IRON: { melting_point: 1538C, is_metal: }")?;
+        let toml = object_to_toml(&raw.object_tokens[0])?;
+        assert!(toml.contains("[inorganic.IRON]\n"));
+        assert!(toml.contains("is_metal = true\n"));
+        Ok(())
+    }
+    #[test]
+    fn compact_raw_set() -> Result<()> {
+        let objects = |path: &str, yaml: &str| -> Result<Vec<SourcedObject>> {
+            Ok(from_yaml(yaml)?
+                .object_tokens
+                .into_iter()
+                .flat_map(RawObject::from_object_token)
+                .map(|object| SourcedObject {
+                    source: Source {
+                        path: path.into(),
+                        ..Default::default()
+                    },
+                    object,
+                })
+                .collect())
+        };
+        let mut raws = RawSet::default();
+        raws.extend(objects(
+            "vanilla.txt",
+            "inorganic:\n  IRON: { is_metal: }\n  TIN: { is_metal: }",
+        )?);
+        raws.extend(objects("mod.txt", "inorganic:\n  IRON: { is_stone: }")?);
+        let compact = raws.to_compact()?;
+        assert_eq!(compact.overridden.len(), 1);
+        assert_eq!(
+            compact.overridden[0].to_string(),
+            "vanilla.txt: INORGANIC:IRON is overridden by mod.txt"
+        );
+        let raw = from_yaml(&compact.to_yaml()?)?;
+        let inorganics = &raw.object_tokens[0].inorganic_tokens;
+        assert_eq!(inorganics.len(), 2);
+        assert!(inorganics[0].is_stone.is_some() && inorganics[0].is_metal.is_none());
+        let toml = compact.to_toml()?;
+        assert!(toml.contains("[inorganic.IRON]\nis_stone = true\n"));
+        assert!(toml.contains("[inorganic.TIN]\nis_metal = true\n"));
+        Ok(())
+    }
+}
@@ -2,18 +2,13 @@
 use serde::{Deserialize, Serialize};
 
 /// Work same way as `Option<T>` except for `TokenDeserialize` trait
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub enum AllowEmpty<T> {
+    #[default]
     None,
     Some(T),
 }
 
-impl<T> Default for AllowEmpty<T> {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
 /// Work same way as `Option<T>`
 impl<T> AllowEmpty<T> {
     pub const fn is_some(&self) -> bool {
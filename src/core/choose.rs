@@ -8,12 +8,6 @@ pub enum Choose<T1, T2> {
     Choice2(T2),
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
-pub enum ChooseTagged<T1, T2> {
-    Choice1(T1),
-    Choice2(T2),
-}
-
 impl<T1, T2> From<ChooseMaybeTagged<T1, T2>> for Choose<T1, T2> {
     fn from(val: ChooseMaybeTagged<T1, T2>) -> Self {
         match val {
@@ -16,6 +16,7 @@ pub use clamp::Clamp;
 pub use df_char::DFChar;
 pub use reference::Reference;
 pub use reference_to::ReferenceTo;
+pub(crate) use referenceable::impl_referenceable;
 pub use referenceable::Referenceable;
 
 pub type Flag = Option<()>;
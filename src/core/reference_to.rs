@@ -3,20 +3,24 @@ use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 
+use super::Referenceable;
+
 #[derive(Clone, Default)]
 pub struct ReferenceTo<T>(pub String, PhantomData<T>);
 
-fn get_ref_type<T>() -> &'static str {
-    "ReferenceTo"
+fn get_ref_type<T: Referenceable>() -> &'static str {
+    T::get_ref_type()
 }
-use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor, IgnoredAny};
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor, IgnoredAny};
 
-impl<T> Serialize for ReferenceTo<T> {
+/// Serialized as a newtype struct named after the DF type, so serializers that care (like the
+/// reference checks) can tell what it points to. For everything else it is just a string.
+impl<T: Referenceable> Serialize for ReferenceTo<T> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_str(&self.0)
+        serializer.serialize_newtype_struct(get_ref_type::<T>(), &self.0)
     }
 }
 
@@ -60,7 +64,7 @@ impl<'de, T> Deserialize<'de> for ReferenceTo<T> {
     }
 }
 
-impl<T> fmt::Debug for ReferenceTo<T> {
+impl<T: Referenceable> fmt::Debug for ReferenceTo<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("ReferenceTo")
             .field(&self.0)
@@ -80,7 +84,9 @@ impl<T> ReferenceTo<T> {
     pub fn new(reference: String) -> Self {
         ReferenceTo::<T>(reference, PhantomData)
     }
+}
 
+impl<T: Referenceable> ReferenceTo<T> {
     pub fn get_type() -> &'static str {
         get_ref_type::<T>()
     }
@@ -5,5 +5,34 @@ pub trait Referenceable: Clone {
     where
         Self: Sized;
 
+    /// The name DF uses for this type of object, e.g. `CREATURE` or `ITEM_WEAPON`.
     fn get_ref_type() -> &'static str;
 }
+
+/// Implements `Referenceable` for a token whose ID is stored in `reference`, or in the first
+/// argument of the given field.
+macro_rules! impl_referenceable {
+    ( $token:ty, $ref_type:literal ) => {
+        impl $crate::core::Referenceable for $token {
+            fn get_reference(&self) -> Option<$crate::core::ReferenceTo<Self>> {
+                self.reference.clone()
+            }
+
+            fn get_ref_type() -> &'static str {
+                $ref_type
+            }
+        }
+    };
+    ( $token:ty, $ref_type:literal, $field:ident.0 ) => {
+        impl $crate::core::Referenceable for $token {
+            fn get_reference(&self) -> Option<$crate::core::ReferenceTo<Self>> {
+                self.$field.as_ref().map(|args| args.0.clone())
+            }
+
+            fn get_ref_type() -> &'static str {
+                $ref_type
+            }
+        }
+    };
+}
+pub(crate) use impl_referenceable;
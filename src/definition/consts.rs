@@ -0,0 +1,217 @@
+//! Named constants, from `consts.yml` and the `consts` of a definition document: the 16 colors of
+//! the palette, the descriptor colors under `descriptor_colors` if given, and the arguments of the
+//! standard gait variations by speed.
+
+use std::sync::OnceLock;
+
+use anyhow::{bail, Context, Result};
+use serde_yaml::{Mapping, Value};
+
+use crate::palette::{distance, named_color, parse_hex, Palette, Rgb, COLOR_NAMES};
+use crate::ColorToken;
+
+/// The fastest speed in the gait table, for it and everything faster.
+const FASTEST_KPH: u32 = 87;
+
+/// Named constants.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Consts {
+    consts: Mapping,
+}
+
+impl Consts {
+    pub fn new(consts: Mapping) -> Self {
+        Self { consts }
+    }
+
+    /// Reads the constants under `const` of a file like `consts.yml`.
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        let mut file: Mapping = serde_yaml::from_str(yaml)?;
+        match file.remove("const") {
+            Some(Value::Mapping(consts)) => Ok(Self::new(consts)),
+            _ => bail!("expected a map of constants under `const`"),
+        }
+    }
+
+    /// The constants of `consts.yml`.
+    pub fn standard() -> &'static Self {
+        static STANDARD: OnceLock<Consts> = OnceLock::new();
+        STANDARD.get_or_init(|| {
+            Self::from_yaml(include_str!("../../consts.yml")).expect("`consts.yml` is valid")
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.consts.get(name)
+    }
+
+    /// Adds constants, replacing those with the same name.
+    pub fn extend(&mut self, consts: Mapping) {
+        self.consts.extend(consts);
+    }
+
+    /// A named color as `(fg, bg, bright)`, like `DISPLAY_COLOR` takes it.
+    pub fn color(&self, name: &str) -> Option<(u8, u8, u8)> {
+        self.consts.get(name)?;
+        named_color(name)
+    }
+
+    /// The RGB value of a named color.
+    pub fn rgb(&self, name: &str) -> Option<Rgb> {
+        rgb(self.consts.get(name)?)
+    }
+
+    /// The palette of the named colors.
+    pub fn palette(&self) -> Option<Palette> {
+        let mut colors = [(0, 0, 0); 16];
+        for (color, name) in colors.iter_mut().zip(COLOR_NAMES) {
+            *color = self.rgb(name)?;
+        }
+        Some(Palette::new(colors))
+    }
+
+    /// Replaces the named colors with those of a palette.
+    pub fn set_palette(&mut self, palette: &Palette) {
+        for (name, &(r, g, b)) in COLOR_NAMES.iter().zip(palette.colors()) {
+            let color = Mapping::from_iter([
+                ("r".into(), r.into()),
+                ("g".into(), g.into()),
+                ("b".into(), b.into()),
+            ]);
+            self.consts.insert((*name).into(), color.into());
+        }
+    }
+
+    /// Replaces the descriptor colors with those given, like `descriptor_color_standard.txt`.
+    pub fn set_descriptor_colors<'a>(&mut self, colors: impl IntoIterator<Item = &'a ColorToken>) {
+        let colors = colors
+            .into_iter()
+            .filter_map(|color| {
+                let (r, g, b) = color.rgb?;
+                let rgb = Mapping::from_iter([
+                    ("r".into(), r.into()),
+                    ("g".into(), g.into()),
+                    ("b".into(), b.into()),
+                ]);
+                Some((String::from(color.reference.clone()?).into(), rgb.into()))
+            })
+            .collect::<Mapping>();
+        self.consts
+            .insert("descriptor_colors".into(), colors.into());
+    }
+
+    /// The arguments of `STANDARD_WALKING_GAITS` and the other standard gait variations for a
+    /// speed in kph: the walk, jog, run, sprint, stroll and creep speeds.
+    pub fn gait_speeds(&self, kph: u32) -> Option<[u32; 6]> {
+        let name = match kph {
+            kph if kph >= FASTEST_KPH => format!("speed_{}_plus_kph", FASTEST_KPH),
+            kph => format!("speed_{}_kph", kph),
+        };
+        let speeds = self.consts.get(name)?.as_sequence()?;
+        let mut gait_speeds = [0; 6];
+        if speeds.len() != gait_speeds.len() {
+            return None;
+        }
+        for (gait_speed, speed) in gait_speeds.iter_mut().zip(speeds) {
+            *gait_speed = u32::try_from(speed.as_u64()?).ok()?;
+        }
+        Some(gait_speeds)
+    }
+}
+
+/// The speed of a value like `12kph`.
+pub fn parse_kph(text: &str) -> Option<u32> {
+    text.strip_suffix("kph")?.trim().parse().ok()
+}
+
+/// `{call: color2vec, color}`: a named color, `(fg, bg, bright)` or the color of the palette
+/// nearest to a hex color like `#c0c0c0` as `(fg, bg, bright)`.
+pub(super) fn color2vec(args: &Mapping, consts: &Consts) -> Result<Value> {
+    match args.get("color").context("no `color`")? {
+        Value::String(color) if hex(color).is_some() => {
+            let palette = consts.palette().context("the palette is missing colors")?;
+            let (fg, bg, bright) = palette.nearest(hex(color).unwrap_or_default());
+            Ok(vec![fg, bg, bright].into())
+        }
+        Value::String(name) => {
+            let (fg, bg, bright) = consts
+                .color(name)
+                .with_context(|| format!("unknown color `{}`", name))?;
+            Ok(vec![fg, bg, bright].into())
+        }
+        color @ Value::Sequence(values) if values.len() == 3 => Ok(color.clone()),
+        _ => bail!("expected the name of a color"),
+    }
+}
+
+/// `{call: color2ref, color}`: the descriptor color nearest to a hex color like `#c0c0c0`, from
+/// the `descriptor_colors`, or a descriptor color as is.
+pub(super) fn color2ref(args: &Mapping, consts: &Consts) -> Result<Value> {
+    let color = match args.get("color").context("no `color`")? {
+        Value::String(color) => color,
+        _ => bail!("expected a hex color or descriptor color"),
+    };
+    let hex = match hex(color) {
+        Some(hex) => hex,
+        None => return Ok(color.as_str().into()),
+    };
+    let colors = match consts.get("descriptor_colors") {
+        Some(Value::Mapping(colors)) => colors,
+        _ => bail!("no `descriptor_colors` to pick from"),
+    };
+    let nearest = colors
+        .iter()
+        .filter_map(|(id, value)| Some((id, rgb(value)?)))
+        .min_by_key(|(_, rgb)| distance(*rgb, hex));
+    match nearest {
+        Some((id, _)) => Ok(id.clone()),
+        None => bail!("no `descriptor_colors` to pick from"),
+    }
+}
+
+/// The RGB value of a color written as hex, like `#c0c0c0`.
+fn hex(text: &str) -> Option<Rgb> {
+    text.strip_prefix('#').and_then(parse_hex)
+}
+
+/// The RGB value of a color written as `{r, g, b}`.
+fn rgb(color: &Value) -> Option<Rgb> {
+    let channel = |channel: &str| u8::try_from(color.get(channel)?.as_u64()?).ok();
+    Some((channel("r")?, channel("g")?, channel("b")?))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn constants() -> Result<()> {
+        let consts = Consts::standard();
+        assert_eq!(consts.color("lred"), Some((4, 0, 1)));
+        assert_eq!(consts.rgb("lred"), Some((255, 0, 0)));
+        assert_eq!(consts.gait_speeds(12), Some([2990, 2257, 1525, 731, 4300, 6100]));
+        assert_eq!(consts.gait_speeds(100), consts.gait_speeds(87));
+
+        let definitions = Definitions::from_yaml(
+            "
+consts:
+  glow: lred
+ember:
+  type: inorganic
+  display_color: { call: color2vec, color: { const: glow } }
+  build_color: { call: color2vec, color: yellow }
+  walk_speed: 12kph
+ash:
+  display_color: { call: color2vec, color: gray }
+",
+        )?;
+        let ember = definitions.evaluate("ember")?;
+        assert_eq!(ember.fields["display_color"], serde_yaml::to_value([4, 0, 1])?);
+        assert_eq!(ember.fields["build_color"], serde_yaml::to_value([6, 0, 1])?);
+        let walk_speed = serde_yaml::to_value([2990, 2257, 1525, 731, 4300, 6100])?;
+        assert_eq!(ember.fields["walk_speed"], walk_speed);
+        let error = definitions.evaluate("ash").unwrap_err();
+        assert!(error.to_string().starts_with("`ash.display_color`"), "{}", error);
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+//! The `items` of entities, each an item the entity has and what it uses it as, like
+//! `{has: ITEM_WEAPON_PICK, as: digger}` for `[DIGGER:ITEM_WEAPON_PICK]`. Items with a rarity take
+//! it as `rarity`, and `ammo` is for the weapon before it.
+
+use anyhow::{bail, Result};
+use serde_yaml::{Mapping, Value};
+
+/// What an entity can use items as, with whether the item takes a rarity.
+const ITEM_ROLES: &[(&str, bool)] = &[
+    ("ammo", true),
+    ("armor", true),
+    ("digger", false),
+    ("gloves", true),
+    ("helm", true),
+    ("instrument", false),
+    ("pants", true),
+    ("shield", false),
+    ("shoes", true),
+    ("siegeammo", false),
+    ("tool", false),
+    ("toy", false),
+    ("trapcomp", false),
+    ("weapon", true),
+];
+
+/// Turns the `items` of an entity into the fields for each role.
+pub(super) fn items(fields: &mut Mapping, id: &str) -> Result<()> {
+    let items = match fields.remove("items") {
+        None | Some(Value::Null) => return Ok(()),
+        Some(Value::Sequence(items)) => items,
+        Some(_) => bail!("`{}.items`: expected a list of items", id),
+    };
+    for (index, item) in items.into_iter().enumerate() {
+        let path = format!("{}.items[{}]", id, index);
+        let (has, role, rarity) = match &item {
+            Value::Mapping(item)
+                if item.keys().all(|key| {
+                    ["has", "as", "rarity"].contains(&key.as_str().unwrap_or_default())
+                }) =>
+            {
+                match (item.get("has"), item.get("as").and_then(Value::as_str)) {
+                    (Some(has), Some(role)) => (has.clone(), role, item.get("rarity").cloned()),
+                    _ => bail!("`{}`: expected `has` and `as`", path),
+                }
+            }
+            _ => bail!("`{}`: expected `has`, `as` and maybe `rarity`", path),
+        };
+        let takes_rarity = match ITEM_ROLES.iter().find(|(name, _)| *name == role) {
+            Some((_, takes_rarity)) => *takes_rarity,
+            None => bail!("`{}.as`: unknown role `{}`", path, role),
+        };
+        let value = match rarity {
+            Some(rarity) if takes_rarity => vec![has, rarity].into(),
+            Some(_) => bail!("`{}.rarity`: `{}` takes no rarity", path, role),
+            // Written as a list, a single name would be read as the characters of it.
+            None if takes_rarity => vec![has].into(),
+            None => has,
+        };
+        match role {
+            "weapon" => push(
+                fields,
+                role,
+                Mapping::from_iter([("reference".into(), value)]).into(),
+            ),
+            "ammo" => {
+                let weapon = match fields.get_mut("weapon") {
+                    Some(Value::Sequence(weapons)) => {
+                        weapons.last_mut().and_then(Value::as_mapping_mut)
+                    }
+                    _ => None,
+                };
+                match weapon {
+                    Some(weapon) => weapon.insert("ammo".into(), value),
+                    None => bail!("`{}`: no weapon before this ammo", path),
+                };
+            }
+            role => push(fields, role, value),
+        }
+    }
+    Ok(())
+}
+
+/// Adds a value to the end of a list field.
+fn push(fields: &mut Mapping, field: &str, value: Value) {
+    match fields
+        .entry(field.into())
+        .or_insert_with(|| Value::Sequence(vec![]))
+    {
+        Value::Sequence(values) => values.push(value),
+        other => *other = vec![other.clone(), value].into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn entity_composition() -> Result<()> {
+        let dwarf = std::fs::read_to_string("dwarf.yml")?;
+        let yaml = format!(
+            "{}\n{}",
+            dwarf,
+            "
+kobold:
+  base: entity
+  add: [kobold_ethics, evil_jobs]
+  ethic: { lying: acceptable }
+  permitted_job: { miner: false }
+  items:
+    - { has: ITEM_WEAPON_SPEAR, as: weapon, rarity: common }
+    - { has: ITEM_AMMO_BOLTS, as: ammo }
+evil_jobs:
+  permitted_job: { miner: true, cook: true }
+"
+        );
+        let definitions = Definitions::from_yaml(&yaml)?;
+        let evil: EntityToken = definitions.evaluate("evil")?.to_object()?;
+        assert_eq!(evil.reference, Some(ReferenceTo::from("EVIL".to_owned())));
+        assert_eq!(evil.ethic.len(), 22);
+        assert!(evil
+            .ethic
+            .contains(&(EthicTypeEnum::KillNeutral, EthicReactionEnum::Required)));
+        assert_eq!(evil.permitted_job.len(), 47);
+        assert!(evil.permitted_job.contains(&UnitTypeEnum::Miner));
+        assert_eq!(evil.digger, [ReferenceTo::from("ITEM_WEAPON_PICK".to_owned())]);
+        assert_eq!(evil.abuse_bodies, Some(()));
+        assert_eq!(evil.all_main_pops_controllable, None);
+
+        let kobold: EntityToken = definitions.evaluate("kobold")?.to_object()?;
+        assert!(kobold
+            .ethic
+            .contains(&(EthicTypeEnum::Lying, EthicReactionEnum::Acceptable)));
+        assert_eq!(kobold.permitted_job, [UnitTypeEnum::Cook]);
+        assert!(kobold.weapon[0].ammo.is_some());
+
+        let raw = write_raw(&definitions.to_raw()?)?;
+        assert!(raw.contains("[ENTITY:EVIL]"));
+        assert!(raw.contains("[DIGGER:ITEM_WEAPON_PICK]"));
+        Ok(())
+    }
+}
@@ -0,0 +1,750 @@
+//! Definitions in the YAML language sketched by `steel.yml`. Every top level key defines
+//! something, made of fields and these keys:
+//!
+//! - `type`: the object type, keyed like in the compact form, like `inorganic`.
+//! - `base`: the definition this one extends. Its fields come first and the fields here override
+//!   them, a field set to nothing unsets it.
+//! - `args`: the arguments this definition takes, with their default. Arguments without one are
+//!   required. `{ arg }` in a field stands for the value of `arg`.
+//! - `with`: arguments for the bases.
+//! - `add`: fragments, definitions whose fields are added after those of the base, in order.
+//! - `do`: mixins run on this definition, before its own fields.
+//! - `late`: mixins run on definitions extending this one, after their own fields.
+//! - `set`: fields, for fields named like one of these keys.
+//! - `tag`: a group of flags, `false` unsets one.
+//! - `test`: fields the evaluated definition must have, with their value or nothing for any.
+//! - `on`: the kinds of objects a mixin can be used on.
+//! - `abstract`: `true` for a definition that is only there to be extended, which is no object
+//!   itself even with a `type`.
+//!
+//! A mixin is a definition with `args`. `mixin: {arg: value}` calls it, or `mixin: value` when it
+//! takes one argument, and adds its fields to the definition calling it. A definition calling a
+//! mixin that a base runs `late` gives the arguments of that late call instead. `{call: function,
+//! arg}` calls a function added with [`Definitions::add_function`], and `{template: "molten
+//! {{name}}"}` renders a template with the arguments, see [`render`]. Every definition has an
+//! implicit `name` argument, its key.
+//!
+//! Maps merge key by key, so the fragments and the definition can each set some of `ethic` or
+//! `permitted_job`, and the last one to set a key wins. A field set to `false` is unset too, like
+//! a flag of the base. The `items` of entities, like `{has: ITEM_WEAPON_PICK, as: digger}`, go to
+//! the field of their role, with an optional `rarity`.
+//!
+//! Values with a unit, like `melting_point: 1370C`, become numbers in DF units, see
+//! [`parse_quantity`].
+//!
+//! Constants come from `consts.yml` and the `consts` of the document. `{const: name}` is the
+//! value of one, a speed like `12kph` is the six arguments of the standard gait variations for
+//! it, and `{call: color2vec, color}` turns a color like `lred` into `(fg, bg, bright)`. Color
+//! names stay names elsewhere, since descriptor colors like `red` share them. Colors can be hex,
+//! like `#c0c0c0`: `color2vec` picks the nearest color of the palette, see
+//! [`Definitions::set_palette`], and `{call: color2ref, color}` the nearest descriptor color for
+//! `state_color`, see [`Definitions::set_descriptor_colors`].
+//!
+//! Errors start with the YAML key they are about, like `` `steel.base` ``.
+
+mod consts;
+mod entity;
+mod template;
+
+pub use self::consts::{parse_kph, Consts};
+pub use self::template::{plural, render};
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde_yaml::{Mapping, Value};
+
+use crate::compact::{from_compact, from_compact_object};
+use crate::palette::Palette;
+use crate::patch::Patch;
+use crate::structure::DFRaw;
+use crate::units::parse_quantity;
+use crate::ColorToken;
+
+/// Keys of a definition that are not fields.
+const KEYWORDS: &[&str] = &[
+    "type", "base", "args", "with", "add", "do", "late", "set", "tag", "test", "on", "abstract",
+];
+
+/// Top level keys that are not definitions.
+const RESERVED: &[&str] = &["def", "patches", "consts"];
+
+/// Kinds of objects a mixin can be used `on`, with the object types of that kind.
+const KINDS: &[(&str, &[&str])] = &[("material", &["inorganic", "material_template"])];
+
+/// Turns fields of an evaluated object into others, from its fields and key.
+type Lowering = fn(&mut Mapping, &str) -> Result<()>;
+
+/// Object types with fields that are lowered into others once evaluated.
+const LOWERINGS: &[(&str, Lowering)] = &[("entity", entity::items)];
+
+/// A function definitions can call, from its arguments and the constants.
+pub type Function = fn(&Mapping, &Consts) -> Result<Value>;
+
+/// The definitions of a YAML document.
+pub struct Definitions {
+    definitions: Mapping,
+    consts: Consts,
+    functions: Vec<(String, Function)>,
+}
+
+/// An evaluated definition.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Definition {
+    /// The key of the definition.
+    pub id: String,
+    /// The object type, if the definition is an object.
+    pub object_type: Option<String>,
+    /// The definitions this one extends, nearest first.
+    pub bases: Vec<String>,
+    /// The fields of the object in the compact form.
+    pub fields: Mapping,
+}
+
+impl Definition {
+    /// The ID of the object, the key in upper case.
+    pub fn object_id(&self) -> String {
+        self.id.to_ascii_uppercase()
+    }
+
+    /// The object, like an [`InorganicToken`](crate::InorganicToken).
+    pub fn to_object<T: DeserializeOwned>(&self) -> Result<T> {
+        from_compact_object(&self.object_id(), self.fields.clone().into())
+            .with_context(|| format!("`{}`", self.id))
+    }
+}
+
+/// A mixin call.
+struct Call {
+    /// The definition making the call.
+    owner: String,
+    /// Where the call is made, for errors.
+    site: String,
+    mixin: String,
+    args: Mapping,
+}
+
+/// A definition being evaluated.
+#[derive(Default)]
+struct Evaluation {
+    object_type: Option<String>,
+    bases: Vec<String>,
+    fields: Mapping,
+    /// The fields the last definition evaluated sets itself.
+    own: Vec<Value>,
+    late: Vec<Call>,
+    /// The tests of the definitions, with where they are.
+    tests: Vec<(String, Mapping)>,
+}
+
+impl Evaluation {
+    fn set(&mut self, field: Value, value: Value) {
+        self.add(field.clone(), value);
+        self.own.push(field);
+    }
+
+    /// Sets a field without making it one of the own fields.
+    fn add(&mut self, field: Value, value: Value) {
+        match (self.fields.get_mut(&field), value) {
+            (_, Value::Null | Value::Bool(false)) => {
+                self.fields.remove(&field);
+            }
+            (Some(target), value) => merge(target, value),
+            (None, value) => {
+                self.fields.insert(field, value);
+            }
+        }
+    }
+}
+
+impl Definitions {
+    pub fn new(definitions: Mapping) -> Self {
+        let mut consts = Consts::standard().clone();
+        if let Some(Value::Mapping(own)) = definitions.get("consts") {
+            consts.extend(own.clone());
+        }
+        Self {
+            definitions,
+            consts,
+            functions: vec![
+                ("color2vec".to_owned(), consts::color2vec),
+                ("color2ref".to_owned(), consts::color2ref),
+            ],
+        }
+    }
+
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        match serde_yaml::from_str(yaml)? {
+            Value::Mapping(definitions) => Ok(Self::new(definitions)),
+            _ => bail!("expected a map of definitions"),
+        }
+    }
+
+    /// Adds a function for `{call: name, ...}`, replacing any with that name.
+    pub fn add_function(&mut self, name: &str, function: Function) {
+        self.functions.retain(|(other, _)| other != name);
+        self.functions.push((name.to_owned(), function));
+    }
+
+    /// The constants of the definitions.
+    pub fn consts(&self) -> &Consts {
+        &self.consts
+    }
+
+    /// Picks the colors of a palette for hex colors, instead of those of `consts.yml`.
+    pub fn set_palette(&mut self, palette: &Palette) {
+        self.consts.set_palette(palette);
+    }
+
+    /// Sets the descriptor colors `color2ref` picks from.
+    pub fn set_descriptor_colors<'a>(&mut self, colors: impl IntoIterator<Item = &'a ColorToken>) {
+        self.consts.set_descriptor_colors(colors);
+    }
+
+    /// The keys of the definitions, leaving out keys that are not maps.
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.definitions
+            .iter()
+            .filter(|(_, definition)| definition.is_mapping())
+            .filter_map(|(id, _)| id.as_str())
+            .filter(|id| !RESERVED.contains(id))
+    }
+
+    /// A definition as written.
+    pub fn get(&self, id: &str) -> Option<&Mapping> {
+        self.definitions
+            .get(id)
+            .and_then(Value::as_mapping)
+            .filter(|_| !RESERVED.contains(&id))
+    }
+
+    /// Evaluates a definition: its bases, mixins and arguments.
+    pub fn evaluate(&self, id: &str) -> Result<Definition> {
+        let mut scope = Mapping::new();
+        scope.insert("name".into(), id.into());
+        let mut evaluation = self.resolve(id, &scope, id, &mut vec![])?;
+        if let Some(with) = self.get(id).and_then(|definition| definition.get("with")) {
+            for (arg, value) in mapping(with, &format!("{}.with", id))? {
+                scope.entry(arg).or_insert(value);
+            }
+        }
+        let late: Vec<Call> = evaluation.late.drain(..).collect();
+        for call in late.iter().filter(|call| call.owner != id) {
+            let fields = self.call(call, evaluation.object_type.as_deref(), &scope, &mut vec![])?;
+            for (field, value) in fields {
+                if !evaluation.own.contains(&field) {
+                    evaluation.add(field, value);
+                }
+            }
+        }
+        let object_type = evaluation.object_type.as_deref().unwrap_or_default();
+        for (_, lower) in LOWERINGS.iter().filter(|(name, _)| *name == object_type) {
+            lower(&mut evaluation.fields, id)?;
+        }
+        for (field, value) in evaluation.fields.iter_mut() {
+            if let (Some(name), Value::String(text)) = (field.as_str(), &*value) {
+                let quantity =
+                    parse_quantity(name, text).with_context(|| format!("`{}.{}`", id, name))?;
+                if let Some(quantity) = quantity {
+                    *value = quantity.into();
+                }
+            }
+        }
+        for (site, test) in &evaluation.tests {
+            for (field, expected) in test {
+                let path = format!("{}.{}", site, field.as_str().unwrap_or_default());
+                match evaluation.fields.get(field) {
+                    None => bail!("`{}`: not set on `{}`", path, id),
+                    Some(value) if !expected.is_null() && value != expected => bail!(
+                        "`{}`: expected {} on `{}`, got {}",
+                        path,
+                        serde_json::to_string(expected)?,
+                        id,
+                        serde_json::to_string(value)?
+                    ),
+                    Some(_) => {}
+                }
+            }
+        }
+        Ok(Definition {
+            id: id.to_owned(),
+            object_type: evaluation.object_type,
+            bases: evaluation.bases,
+            fields: evaluation.fields,
+        })
+    }
+
+    /// Evaluates every object, the definitions with a type that are not `abstract`, and applies
+    /// the `patches` of the document to them.
+    pub fn objects(&self) -> Result<Vec<Definition>> {
+        let mut objects = vec![];
+        for id in self.ids() {
+            if self.is_abstract(id)? || self.is_mixin(id) {
+                continue;
+            }
+            let definition = self.evaluate(id)?;
+            if definition.object_type.is_some() {
+                objects.push(definition);
+            }
+        }
+        let patches = self.definitions.get("patches").cloned();
+        for (index, patch) in Patch::from_document(patches.unwrap_or_default())?
+            .iter()
+            .enumerate()
+        {
+            patch
+                .apply_to_definitions(&mut objects)
+                .with_context(|| format!("`patches[{}]`", index))?;
+        }
+        Ok(objects)
+    }
+
+    /// The raws of every object.
+    pub fn to_raw(&self) -> Result<DFRaw> {
+        let mut compact = Mapping::new();
+        for definition in self.objects()? {
+            let object_type = definition.object_type.clone().unwrap_or_default();
+            let objects = compact
+                .entry(object_type.into())
+                .or_insert_with(|| Mapping::new().into());
+            if let Value::Mapping(objects) = objects {
+                objects.insert(definition.object_id().into(), definition.fields.into());
+            }
+        }
+        from_compact(compact.into())
+    }
+
+    fn is_abstract(&self, id: &str) -> Result<bool> {
+        match self.get(id).and_then(|definition| definition.get("abstract")) {
+            None | Some(Value::Null) => Ok(false),
+            Some(Value::Bool(is_abstract)) => Ok(*is_abstract),
+            Some(_) => bail!("`{}.abstract`: expected `true` or `false`", id),
+        }
+    }
+
+    fn is_mixin(&self, id: &str) -> bool {
+        self.get(id)
+            .is_some_and(|definition| definition.contains_key("args"))
+    }
+
+    /// Evaluates a definition with arguments, without its late mixins. `site` is where it is
+    /// used.
+    fn resolve(
+        &self,
+        id: &str,
+        scope: &Mapping,
+        site: &str,
+        stack: &mut Vec<String>,
+    ) -> Result<Evaluation> {
+        let definition = match self.get(id) {
+            Some(definition) => definition,
+            None => bail!("`{}`: unknown definition `{}`", site, id),
+        };
+        if stack.iter().any(|used| used == id) {
+            bail!("`{}`: `{}` extends itself", site, id);
+        }
+        stack.push(id.to_owned());
+        let mut scope = scope.clone();
+        match definition.get("args") {
+            None | Some(Value::Null) => {}
+            Some(Value::Mapping(args)) => {
+                for (arg, default) in args {
+                    if scope.contains_key(arg) {
+                        continue;
+                    }
+                    if default.is_null() {
+                        let arg = arg.as_str().unwrap_or_default();
+                        bail!("`{}`: missing argument `{}` of `{}`", site, arg, id);
+                    }
+                    scope.insert(arg.clone(), default.clone());
+                }
+            }
+            Some(_) => bail!("`{}.args`: expected a map of arguments", id),
+        }
+
+        let mut evaluation = match definition.get("base") {
+            None | Some(Value::Null) => Evaluation::default(),
+            Some(Value::String(base)) => {
+                let mut base_scope = scope.clone();
+                if let Some(with) = definition.get("with") {
+                    let path = format!("{}.with", id);
+                    for (arg, value) in mapping(with, &path)? {
+                        if !base_scope.contains_key(&arg) {
+                            let value = self.substitute(value, &scope, &path)?;
+                            base_scope.insert(arg, value);
+                        }
+                    }
+                }
+                let mut evaluation =
+                    self.resolve(base, &base_scope, &format!("{}.base", id), stack)?;
+                evaluation.bases.insert(0, base.clone());
+                evaluation
+            }
+            Some(_) => bail!("`{}.base`: expected the key of a definition", id),
+        };
+        match definition.get("type") {
+            None | Some(Value::Null) => {}
+            Some(Value::String(object_type)) => evaluation.object_type = Some(object_type.clone()),
+            Some(_) => bail!("`{}.type`: expected an object type", id),
+        }
+
+        let fragments = match definition.get("add") {
+            None | Some(Value::Null) => vec![],
+            Some(Value::String(fragment)) => vec![fragment.clone()],
+            Some(Value::Sequence(fragments)) => fragments
+                .iter()
+                .map(|fragment| fragment.as_str().map(str::to_owned))
+                .collect::<Option<_>>()
+                .with_context(|| format!("`{}.add`: expected keys of definitions", id))?,
+            Some(_) => bail!("`{}.add`: expected a list of definitions", id),
+        };
+        for (index, fragment) in fragments.iter().enumerate() {
+            let site = format!("{}.add[{}]", id, index);
+            let added = self.resolve(fragment, &scope, &site, stack)?;
+            for (field, value) in added.fields {
+                evaluation.add(field, value);
+            }
+            evaluation.late.extend(added.late);
+            evaluation.tests.extend(added.tests);
+        }
+        for call in self.calls(id, "do", definition)? {
+            let object_type = evaluation.object_type.as_deref();
+            for (field, value) in self.call(&call, object_type, &scope, stack)? {
+                evaluation.add(field, value);
+            }
+        }
+        evaluation.own.clear();
+        for (key, value) in definition {
+            let name = match key.as_str() {
+                Some(name) => name,
+                None => bail!("`{}`: keys must be strings", id),
+            };
+            let path = format!("{}.{}", id, name);
+            match name {
+                "set" => {
+                    for (field, value) in mapping(value, &path)? {
+                        let field_path = format!("{}.{}", path, field.as_str().unwrap_or_default());
+                        let value = self.substitute(value, &scope, &field_path)?;
+                        evaluation.set(field, value);
+                    }
+                }
+                "tag" => {
+                    for (flag, value) in mapping(value, &path)? {
+                        match value {
+                            Value::Null | Value::Bool(true) => evaluation.set(flag, true.into()),
+                            Value::Bool(false) => evaluation.set(flag, Value::Null),
+                            _ => {
+                                let flag = flag.as_str().unwrap_or_default();
+                                bail!("`{}.{}`: expected `true` or `false`", path, flag);
+                            }
+                        }
+                    }
+                }
+                _ if KEYWORDS.contains(&name) => {}
+                _ if self.is_mixin(name) => {
+                    let value = self.substitute(value.clone(), &scope, &path)?;
+                    let args = self.call_args(name, value, &path)?;
+                    match evaluation.late.iter_mut().find(|call| call.mixin == name) {
+                        Some(call) => call.args.extend(args),
+                        None => {
+                            let call = Call {
+                                owner: id.to_owned(),
+                                site: path,
+                                mixin: name.to_owned(),
+                                args,
+                            };
+                            let object_type = evaluation.object_type.as_deref();
+                            for (field, value) in self.call(&call, object_type, &scope, stack)? {
+                                evaluation.set(field, value);
+                            }
+                        }
+                    }
+                }
+                _ => {
+                    let value = self.substitute(value.clone(), &scope, &path)?;
+                    evaluation.set(key.clone(), value);
+                }
+            }
+        }
+        evaluation.late.extend(self.calls(id, "late", definition)?);
+        if let Some(test) = definition.get("test") {
+            let path = format!("{}.test", id);
+            let test = mapping(test, &path)?;
+            evaluation.tests.push((path, test));
+        }
+        stack.pop();
+        Ok(evaluation)
+    }
+
+    /// The mixin calls listed under `key`.
+    fn calls(&self, id: &str, key: &str, definition: &Mapping) -> Result<Vec<Call>> {
+        let calls = match definition.get(key) {
+            None | Some(Value::Null) => return Ok(vec![]),
+            Some(Value::Sequence(calls)) => calls.clone(),
+            Some(Value::Mapping(calls)) => calls
+                .iter()
+                .map(|(mixin, args)| Mapping::from_iter([(mixin.clone(), args.clone())]).into())
+                .collect(),
+            Some(_) => bail!("`{}.{}`: expected a list of mixins", id, key),
+        };
+        let mut mixin_calls = vec![];
+        for (index, call) in calls.into_iter().enumerate() {
+            let site = format!("{}.{}[{}]", id, key, index);
+            let entries = match call {
+                Value::String(mixin) => vec![(mixin.into(), Value::Null)],
+                Value::Mapping(call) => call.into_iter().collect(),
+                _ => bail!("`{}`: expected a mixin", site),
+            };
+            for (mixin, args) in entries {
+                let mixin = match mixin {
+                    Value::String(mixin) if self.is_mixin(&mixin) => mixin,
+                    mixin => bail!("`{}`: `{:?}` is not a mixin", site, mixin),
+                };
+                let site = format!("{}.{}", site, mixin);
+                mixin_calls.push(Call {
+                    owner: id.to_owned(),
+                    args: self.call_args(&mixin, args, &site)?,
+                    site,
+                    mixin,
+                });
+            }
+        }
+        Ok(mixin_calls)
+    }
+
+    /// The arguments of a mixin call, from a map or the value of its only argument.
+    fn call_args(&self, mixin: &str, args: Value, site: &str) -> Result<Mapping> {
+        match args {
+            Value::Null => Ok(Mapping::new()),
+            Value::Mapping(args) => Ok(args),
+            value => {
+                let param = self
+                    .get(mixin)
+                    .and_then(|definition| definition.get("args"))
+                    .and_then(Value::as_mapping)
+                    .filter(|params| params.len() == 1)
+                    .and_then(|params| params.keys().next());
+                match param {
+                    Some(param) => Ok(Mapping::from_iter([(param.clone(), value)])),
+                    None => bail!(
+                        "`{}`: `{}` takes several arguments, give them as a map",
+                        site,
+                        mixin
+                    ),
+                }
+            }
+        }
+    }
+
+    /// Runs a mixin, returning the fields it sets.
+    fn call(
+        &self,
+        call: &Call,
+        object_type: Option<&str>,
+        scope: &Mapping,
+        stack: &mut Vec<String>,
+    ) -> Result<Mapping> {
+        let kinds = self
+            .get(&call.mixin)
+            .and_then(|mixin| mixin.get("on"))
+            .and_then(Value::as_sequence);
+        if let Some(kinds) = kinds {
+            let object_type = object_type.unwrap_or_default();
+            let allowed = kinds.iter().filter_map(Value::as_str).any(|kind| {
+                kind == object_type
+                    || KINDS
+                        .iter()
+                        .any(|(name, types)| *name == kind && types.contains(&object_type))
+            });
+            if !allowed {
+                bail!(
+                    "`{}`: `{}` can't be used on `{}`",
+                    call.site,
+                    call.mixin,
+                    object_type
+                );
+            }
+        }
+        // Arguments left empty, like `name: {}`, come from the definition calling the mixin.
+        let mut args = Mapping::new();
+        for (arg, value) in &call.args {
+            let empty = value.is_null() || value.as_mapping().is_some_and(Mapping::is_empty);
+            if !empty {
+                args.insert(
+                    arg.clone(),
+                    self.substitute(value.clone(), scope, &call.site)?,
+                );
+            }
+        }
+        for (arg, value) in scope {
+            args.entry(arg.clone()).or_insert_with(|| value.clone());
+        }
+        Ok(self.resolve(&call.mixin, &args, &call.site, stack)?.fields)
+    }
+
+    /// Puts the arguments and function results into a value.
+    fn substitute(&self, value: Value, scope: &Mapping, path: &str) -> Result<Value> {
+        match value {
+            Value::Mapping(map) if map.contains_key("call") => {
+                let name = map["call"].as_str().unwrap_or_default();
+                let function = match self.functions.iter().find(|(function, _)| function == name) {
+                    Some((_, function)) => function,
+                    None => bail!("`{}`: unknown function `{}`", path, name),
+                };
+                let mut args = Mapping::new();
+                for (arg, value) in map.iter().filter(|(arg, _)| *arg != "call") {
+                    let value = match value {
+                        Value::Null => match scope.get(arg) {
+                            Some(value) => value.clone(),
+                            None => {
+                                let arg = arg.as_str().unwrap_or_default();
+                                bail!("`{}`: no argument `{}` for `{}`", path, arg, name);
+                            }
+                        },
+                        value => self.substitute(value.clone(), scope, path)?,
+                    };
+                    args.insert(arg.clone(), value);
+                }
+                function(&args, &self.consts)
+                    .with_context(|| format!("`{}`: calling `{}`", path, name))
+            }
+            Value::Mapping(map) if map.len() == 1 && map.contains_key("template") => {
+                let path = format!("{}.template", path);
+                match &map["template"] {
+                    Value::String(template) => Ok(render(template, scope)
+                        .with_context(|| format!("`{}`", path))?
+                        .into()),
+                    _ => bail!("`{}`: expected a string", path),
+                }
+            }
+            Value::Mapping(map) if map.len() == 1 && map.contains_key("const") => {
+                let name = map["const"].as_str().unwrap_or_default();
+                match self.consts.get(name) {
+                    Some(value) => Ok(value.clone()),
+                    None => bail!("`{}`: unknown constant `{}`", path, name),
+                }
+            }
+            // `{ arg }`
+            Value::Mapping(map) if map.len() == 1 && map.values().all(Value::is_null) => {
+                match map.keys().next().and_then(|arg| scope.get(arg)) {
+                    Some(value) => Ok(value.clone()),
+                    None => Ok(Value::Mapping(map)),
+                }
+            }
+            Value::Mapping(map) => map
+                .into_iter()
+                .map(|(key, value)| {
+                    let path = format!("{}.{}", path, key.as_str().unwrap_or_default());
+                    Ok((key, self.substitute(value, scope, &path)?))
+                })
+                .collect::<Result<Mapping>>()
+                .map(Value::Mapping),
+            Value::Sequence(values) => values
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    self.substitute(value, scope, &format!("{}[{}]", path, index))
+                })
+                .collect::<Result<_>>()
+                .map(Value::Sequence),
+            Value::String(text) => match parse_kph(&text) {
+                Some(kph) => match self.consts.gait_speeds(kph) {
+                    Some(speeds) => Ok(speeds.to_vec().into()),
+                    None => bail!("`{}`: no gait speeds for {}kph", path, kph),
+                },
+                None => Ok(text.into()),
+            },
+            value => Ok(value),
+        }
+    }
+}
+
+/// Merges a value into another, maps key by key.
+fn merge(target: &mut Value, value: Value) {
+    match (target, value) {
+        (Value::Mapping(target), Value::Mapping(value)) => {
+            for (key, value) in value {
+                match (target.get_mut(&key), value) {
+                    (_, Value::Null) => {
+                        target.remove(&key);
+                    }
+                    (Some(target), value) => merge(target, value),
+                    (None, value) => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+        (target, value) => *target = value,
+    }
+}
+
+/// A map of a definition, where nothing is an empty map.
+fn mapping(value: &Value, path: &str) -> Result<Mapping> {
+    match value {
+        Value::Null => Ok(Mapping::new()),
+        Value::Mapping(map) => Ok(map.clone()),
+        _ => bail!("`{}`: expected a map", path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn definition_inheritance() -> Result<()> {
+        use crate::core::Choose;
+
+        let mut definitions = Definitions::from_yaml(&std::fs::read_to_string("steel.yml")?)?;
+        definitions.add_function("color2vec", |_, _| Ok(vec![0, 7, 1].into()));
+        let steel = definitions.evaluate("steel")?;
+        assert_eq!(steel.bases, ["economic_metal", "metal"]);
+        assert_eq!(steel.object_type.as_deref(), Some("inorganic"));
+        let field = |name: &str| steel.fields.get(name).cloned();
+        assert_eq!(field("material_value"), Some(30.into()));
+        assert_eq!(field("items_anvil"), Some(true.into()));
+        assert_eq!(field("ignite_point"), None);
+        assert_eq!(field("display_color"), Some(vec![0, 7, 1].into()));
+        let state_color = field("state_color").unwrap_or_default();
+        assert_eq!(state_color["all_solid"], "gray");
+        assert_eq!(field("state_name_liquid"), Some("molten steel".into()));
+        let error = definitions.evaluate("inebriation").unwrap_err();
+        assert!(error.to_string().starts_with("`inebriation.base`"), "{}", error);
+
+        let definitions = Definitions::from_yaml(
+            "
+metal:
+  type: inorganic
+  abstract: true
+  is_metal: true
+  melting_point: 12768
+  state_color: { gas: RED }
+  late: [glows]
+glows:
+  args: { color }
+  on: [material]
+  state_color: { all_solid: { color } }
+iron:
+  base: metal
+  glows: GRAY
+bronze:
+  base: metal
+",
+        )?;
+        let iron: InorganicToken = definitions.evaluate("iron")?.to_object()?;
+        assert_eq!(iron.reference.map(|reference| reference.0), Some("IRON".to_owned()));
+        assert_eq!(iron.melting_point, Some(Choose::Choice1(12768)));
+        assert!(iron.is_metal.is_some());
+        assert_eq!(iron.state_color.len(), 2);
+        let error = definitions.to_raw().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "`metal.late[0].glows`: missing argument `color` of `glows`"
+        );
+        let definitions =
+            Definitions::from_yaml("metal:\n  type: inorganic\niron:\n  base: metal\n")?;
+        let ids: Vec<_> = definitions.objects()?.into_iter().map(|object| object.id).collect();
+        assert_eq!(ids, ["metal", "iron"]);
+        Ok(())
+    }
+}
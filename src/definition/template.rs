@@ -0,0 +1,108 @@
+//! Templates like `molten {{name}}`, for `{template: "..."}` in definitions.
+//!
+//! `{{arg}}` is the value of an argument and `{{arg.key}}` looks into it. A name given as
+//! `{singular, plural, adjective}` reads as its singular, and these filters pick the other forms:
+//!
+//! - `{{name | plural}}`: the plural, made from the singular if it isn't given.
+//! - `{{name | adjective}}`: the adjective, the singular if it isn't given.
+
+use anyhow::{bail, Result};
+use serde_yaml::{Mapping, Value};
+
+/// Renders a template with the given arguments.
+pub fn render(template: &str, args: &Mapping) -> Result<String> {
+    let mut rendered = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let end = match rest[start..].find("}}") {
+            Some(end) => start + end,
+            None => bail!("unclosed `{{{{` in `{}`", template),
+        };
+        let mut filters = rest[start + 2..end].split('|').map(str::trim);
+        let path = filters.next().unwrap_or_default();
+        let mut value = match lookup(args, path) {
+            Some(value) => value,
+            None => bail!("no argument `{}` for `{}`", path, template),
+        };
+        let mut make_plural = false;
+        for filter in filters {
+            match filter {
+                "plural" => match value.get("plural") {
+                    Some(plural) => value = plural,
+                    None => make_plural = true,
+                },
+                "adjective" => value = value.get("adjective").unwrap_or(value),
+                filter => bail!("unknown filter `{}` in `{}`", filter, template),
+            }
+        }
+        let text = text(value, path, template)?;
+        rendered.push_str(&if make_plural { plural(&text) } else { text });
+        rest = &rest[end + 2..];
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+/// The plural of a name, for the last word of it.
+pub fn plural(singular: &str) -> String {
+    let consonant_y = singular.ends_with('y')
+        && !singular[..singular.len() - 1].ends_with(['a', 'e', 'i', 'o', 'u']);
+    if consonant_y {
+        format!("{}ies", &singular[..singular.len() - 1])
+    } else if singular.ends_with(['s', 'x', 'z'])
+        || singular.ends_with("ch")
+        || singular.ends_with("sh")
+    {
+        format!("{}es", singular)
+    } else {
+        format!("{}s", singular)
+    }
+}
+
+/// The value at a dotted path like `metal.name`.
+fn lookup<'a>(args: &'a Mapping, path: &str) -> Option<&'a Value> {
+    let mut keys = path.split('.');
+    let mut value = args.get(keys.next()?)?;
+    for key in keys {
+        value = value.get(key)?;
+    }
+    Some(value)
+}
+
+/// A value as text, names as their singular.
+fn text(value: &Value, path: &str, template: &str) -> Result<String> {
+    match value {
+        Value::String(text) => Ok(text.clone()),
+        Value::Number(number) => Ok(number.to_string()),
+        Value::Bool(flag) => Ok(flag.to_string()),
+        Value::Mapping(name) if name.contains_key("singular") => {
+            text(&name["singular"], path, template)
+        }
+        _ => bail!("`{}` is not text in `{}`", path, template),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn templates() -> Result<()> {
+        let args: serde_yaml::Mapping = serde_yaml::from_str(
+            "{name: {singular: tooth, plural: teeth}, metal: {name: upper body}, value: 3}",
+        )?;
+        let rendered = render("{{name}}, {{ name | plural }}, {{name|adjective}}", &args)?;
+        assert_eq!(rendered, "tooth, teeth, tooth");
+        assert_eq!(render("{{metal.name | plural}} x{{value}}", &args)?, "upper bodies x3");
+        assert!(render("molten {{nam}}", &args).is_err());
+
+        let mut definitions = Definitions::from_yaml(&std::fs::read_to_string("steel.yml")?)?;
+        definitions.add_function("color2vec", |_, _| Ok(vec![0, 7, 1].into()));
+        let steel: InorganicToken = definitions.evaluate("steel")?.to_object()?;
+        let names: Vec<&str> = steel.state_name.iter().map(|(_, name)| name.as_str()).collect();
+        assert_eq!(names, ["steel", "molten steel", "boiling steel"]);
+        assert_eq!(steel.state_adj.len(), 3);
+        Ok(())
+    }
+}
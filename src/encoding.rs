@@ -0,0 +1,213 @@
+//! Dwarf Fortress reads and writes its raws as code page 437, not UTF-8.
+
+use std::path::Path;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::core::DFChar;
+
+/// How the bytes of a raw file are turned into text.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// UTF-8 when the file is valid UTF-8, code page 437 otherwise.
+    #[default]
+    Auto,
+    Utf8,
+    Cp437,
+}
+
+impl Encoding {
+    pub fn decode(self, bytes: Vec<u8>) -> Result<String> {
+        match self {
+            Encoding::Auto => Ok(match String::from_utf8(bytes) {
+                Ok(text) => text,
+                Err(error) => decode_cp437(error.as_bytes()),
+            }),
+            Encoding::Utf8 => Ok(String::from_utf8(bytes)?),
+            Encoding::Cp437 => Ok(decode_cp437(&bytes)),
+        }
+    }
+
+    /// Encodes text for writing. `Auto` writes code page 437, like the game expects.
+    pub fn encode(self, text: &str) -> Result<Vec<u8>> {
+        match self {
+            Encoding::Utf8 => Ok(text.as_bytes().to_vec()),
+            Encoding::Auto | Encoding::Cp437 => encode_cp437(text),
+        }
+    }
+}
+
+/// Reads a raw file, decoding it with the given encoding.
+pub fn read_raw_file(path: impl AsRef<Path>, encoding: Encoding) -> Result<String> {
+    encoding.decode(std::fs::read(path)?)
+}
+
+/// The upper half of code page 437. The lower half is ASCII when used as text.
+const CP437_HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å', 'É', 'æ', 'Æ',
+    'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ', 'á', 'í', 'ó', 'ú', 'ñ', 'Ñ',
+    'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»', '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕',
+    '╣', '║', '╗', '╝', '╜', '╛', '┐', '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦',
+    '╠', '═', '╬', '╧', '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐',
+    '▀', 'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩', '≡', '±',
+    '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{a0}',
+];
+
+/// The glyphs shown for the control characters when they are used as tiles.
+const CP437_LOW_GLYPHS: [char; 32] = [
+    '\0', '☺', '☻', '♥', '♦', '♣', '♠', '•', '◘', '○', '◙', '♂', '♀', '♪', '♫', '☼', '►', '◄', '↕',
+    '‼', '¶', '§', '▬', '↨', '↑', '↓', '→', '←', '∟', '↔', '▲', '▼',
+];
+
+/// Decodes code page 437 text. Every byte maps to a character, so this can not fail.
+pub fn decode_cp437(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&byte| match byte {
+            0x00..=0x7F => char::from(byte),
+            _ => CP437_HIGH[usize::from(byte - 0x80)],
+        })
+        .collect()
+}
+
+/// Encodes text as code page 437, failing on the first character it does not contain.
+pub fn encode_cp437(text: &str) -> Result<Vec<u8>> {
+    text.chars()
+        .enumerate()
+        .map(|(index, c)| {
+            encode_cp437_char(c).ok_or_else(|| {
+                anyhow::anyhow!("{:?} (character {}) is not in code page 437", c, index)
+            })
+        })
+        .collect()
+}
+
+fn encode_cp437_char(c: char) -> Option<u8> {
+    if c.is_ascii() {
+        return Some(c as u8);
+    }
+    CP437_HIGH
+        .iter()
+        .position(|&high| high == c)
+        .map(|index| index as u8 + 0x80)
+}
+
+/// Where [`mark_quoted_chars`] moves quoted characters up to U+00FF: U+F700 to U+F7FF, in the
+/// private use area.
+const QUOTED: u32 = 0xF700;
+
+/// The tile index a character parsed by df_ls stands for, if it was written as a number.
+fn tile_code(c: char) -> Option<u8> {
+    u8::try_from(u32::from(c))
+        .ok()
+        .filter(|code| !(0x20..=0x7E).contains(code))
+}
+
+/// Moves every quoted character that df_ls would give back like a tile number, like the `é` in
+/// `[CREATURE_TILE:'é']`, to the private use area, so [`DFChar::from_parsed`] can tell the two
+/// apart. Each such character is still one character, so positions in diagnostics don't change.
+pub(crate) fn mark_quoted_chars(source: &str) -> String {
+    let chars: Vec<char> = source.chars().collect();
+    chars
+        .iter()
+        .enumerate()
+        .map(|(index, &c)| {
+            let quoted = index >= 2
+                && chars[index - 2] == ':'
+                && chars[index - 1] == '\''
+                && chars.get(index + 1) == Some(&'\'')
+                && matches!(chars.get(index + 2), Some(':' | ']'));
+            match tile_code(c) {
+                Some(code) if quoted => {
+                    char::from_u32(QUOTED + u32::from(code)).expect("U+F700 to U+F7FF are chars")
+                }
+                _ => c,
+            }
+        })
+        .collect()
+}
+
+impl DFChar {
+    /// The character for a tile index, e.g. `1` is `☺` and `130` is `é`.
+    pub fn from_tile(tile: u8) -> Self {
+        DFChar(match tile {
+            0x00..=0x1F => CP437_LOW_GLYPHS[usize::from(tile)],
+            0x7F => '⌂',
+            0x20..=0x7E => char::from(tile),
+            _ => CP437_HIGH[usize::from(tile - 0x80)],
+        })
+    }
+
+    /// The character for a tile as df_ls parses it. A tile written as a number, like
+    /// `[TILE:130]`, comes out as the character with that code (U+0082), so every character up
+    /// to U+00FF that isn't printable ASCII is taken for a tile index. Quoted characters in that
+    /// range, like `'é'`, are moved out of the way by [`mark_quoted_chars`] before parsing, and
+    /// moved back here.
+    pub(crate) fn from_parsed(c: char) -> Self {
+        let quoted = u32::from(c)
+            .checked_sub(QUOTED)
+            .and_then(|code| u8::try_from(code).ok());
+        if let Some(quoted) = quoted {
+            return DFChar(char::from(quoted));
+        }
+        match tile_code(c) {
+            Some(tile) => Self::from_tile(tile),
+            None => DFChar(c),
+        }
+    }
+
+    /// The tile index of this character, if it is on the code page 437 tile set.
+    pub fn tile(self) -> Option<u8> {
+        match self.0 {
+            '⌂' => Some(0x7F),
+            c => CP437_LOW_GLYPHS
+                .iter()
+                .position(|&glyph| glyph == c)
+                .map(|index| index as u8)
+                .or_else(|| encode_cp437_char(c)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn cp437() -> Result<()> {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let text = decode_cp437(&bytes);
+        assert_eq!(text.chars().nth(0x82), Some('é'));
+        assert_eq!(encode_cp437(&text)?, bytes);
+        assert!(encode_cp437("€").is_err());
+        for tile in 0..=255 {
+            assert_eq!(DFChar::from_tile(tile).tile(), Some(tile));
+        }
+        assert_eq!(DFChar::from_tile(1).0, '☺');
+        assert_eq!(DFChar('┼').tile(), Some(197));
+
+        let raw = parse("shape_test\n\n[OBJECT:DESCRIPTOR_SHAPE]\n\n[SHAPE:TEST][TILE:130]\n")?.raw;
+        let shape = &raw.object_tokens[0].shape_tokens[0];
+        assert_eq!(shape.tile, Some(DFChar('é')));
+        assert!(write_raw(&raw)?.contains("[TILE:130]"));
+        Ok(())
+    }
+    #[test]
+    fn quoted_tile() -> Result<()> {
+        let marked = encoding::mark_quoted_chars("[TILE:'é'][NAME:'é'x][TILE:130]");
+        assert_eq!(marked.chars().count(), 31);
+        assert_eq!(
+            DFChar::from_parsed(marked.chars().nth(7).unwrap()),
+            DFChar('é')
+        );
+        assert_eq!(marked.chars().nth(17), Some('é'));
+        assert_eq!(DFChar::from_parsed('\u{82}'), DFChar('é'));
+
+        let source = "creature_test\n\n[OBJECT:CREATURE]\n\n[CREATURE:TEST][CREATURE_TILE:'é']\n";
+        let raw = parse(source)?.raw;
+        let creature = &raw.object_tokens[0].creature_tokens[0];
+        assert_eq!(creature.creature_tile, Some(DFChar('é')));
+        Ok(())
+    }
+}
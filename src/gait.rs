@@ -0,0 +1,355 @@
+//! Gaits as real speeds. A gait speed is the delay between moves, so higher is slower: at 100 a
+//! creature moves a tile every tick, which is 87.75 kph, at 900 every 9 ticks, like most
+//! dwarves walk.
+//!
+//! The energy use of a gait is how much it tires the creature, and a gait with energy use 0 can
+//! be kept up forever. The game doesn't document how it turns into exhaustion, so it is counted
+//! here as exertion for every tile moved.
+
+use anyhow::{bail, Result};
+
+use crate::core::Choose;
+use crate::definition::Consts;
+use crate::{GaitFlagTokenArg, GaitTypeEnum, NoBuildUpEnum};
+
+/// A `GAIT` token: type, name, max speed, `NO_BUILD_UP` or build up time, max turning speed and
+/// start speed, energy use and flags.
+pub type GaitToken = (
+    GaitTypeEnum,
+    String,
+    u32,
+    Choose<NoBuildUpEnum, (u32, u32, u32)>,
+    u32,
+    Option<GaitFlagTokenArg>,
+);
+
+/// The speed in kph of a gait speed of 1, that is 100 tiles per tick.
+const KPH_AT_ONE: f64 = 8775.0;
+
+/// The average `STRENGTH` and `AGILITY`.
+const AVERAGE_ATTRIBUTE: f64 = 1000.0;
+
+/// The names of the gaits of the standard gait variations, from fastest to slowest, with the
+/// gait type of the variation.
+const STANDARD_GAIT_NAMES: &[(GaitTypeEnum, [&str; 6])] = &[
+    (
+        GaitTypeEnum::Walk,
+        [
+            "Fastest Walk",
+            "Faster Walk",
+            "Fast Walk",
+            "Walk",
+            "Slow Walk",
+            "Slowest Walk",
+        ],
+    ),
+    (
+        GaitTypeEnum::Crawl,
+        [
+            "Scramble",
+            "Faster Crawl",
+            "Fast Crawl",
+            "Crawl",
+            "Slow Crawl",
+            "Creep",
+        ],
+    ),
+    (
+        GaitTypeEnum::Swim,
+        [
+            "Maximum Swim Speed",
+            "Faster Swim",
+            "Fast Swim",
+            "Swim",
+            "Slow Swim",
+            "Creeping Swim",
+        ],
+    ),
+    (
+        GaitTypeEnum::Fly,
+        [
+            "Maximum Flight Speed",
+            "Faster Flight",
+            "Fast Flight",
+            "Fly",
+            "Slow Flight",
+            "Hover",
+        ],
+    ),
+    (
+        GaitTypeEnum::Climb,
+        [
+            "Scramble",
+            "Faster Climb",
+            "Fast Climb",
+            "Climb",
+            "Slow Climb",
+            "Creep",
+        ],
+    ),
+];
+
+/// A gait, read from a `GAIT` token.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Gait {
+    pub gait_type: GaitTypeEnum,
+    pub name: String,
+    /// The speed once built up.
+    pub max_speed: u32,
+    /// Build up time, max turning speed and start speed, `None` for `NO_BUILD_UP`.
+    pub build_up: Option<(u32, u32, u32)>,
+    /// How tiring the gait is, 0 for not at all.
+    pub energy_use: u32,
+    pub flags: GaitFlagTokenArg,
+}
+
+/// What a gait depends on besides its own values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mover {
+    /// For gaits with `STRENGTH`, 1000 on average.
+    pub strength: u32,
+    /// For gaits with `AGILITY`, 1000 on average.
+    pub agility: u32,
+    /// How much `THICKENS_ON_ENERGY_STORAGE` layers, fat, slow gaits with `LAYERS_SLOW`, in
+    /// percent.
+    pub fat_slowdown: f64,
+    /// How much `THICKENS_ON_STRENGTH` layers, muscle, slow gaits with `LAYERS_SLOW` but not
+    /// `STRENGTH`, in percent.
+    pub muscle_slowdown: f64,
+    /// Whether the creature is sneaking, for `STEALTH_SLOWS`.
+    pub sneaking: bool,
+}
+
+impl Default for Mover {
+    fn default() -> Self {
+        Self {
+            strength: AVERAGE_ATTRIBUTE as u32,
+            agility: AVERAGE_ATTRIBUTE as u32,
+            fat_slowdown: 0.0,
+            muscle_slowdown: 0.0,
+            sneaking: false,
+        }
+    }
+}
+
+impl Gait {
+    pub fn from_token(token: &GaitToken) -> Self {
+        let (gait_type, name, max_speed, build_up, energy_use, flags) = token.clone();
+        Self {
+            gait_type,
+            name,
+            max_speed,
+            build_up: match build_up {
+                Choose::Choice1(NoBuildUpEnum::NoBuildUp) => None,
+                Choose::Choice2(build_up) => Some(build_up),
+            },
+            energy_use,
+            flags: flags.unwrap_or_default(),
+        }
+    }
+
+    pub fn to_token(&self) -> GaitToken {
+        let flags = Some(self.flags.clone()).filter(|flags| *flags != GaitFlagTokenArg::default());
+        (
+            self.gait_type.clone(),
+            self.name.clone(),
+            self.max_speed,
+            match self.build_up {
+                None => Choose::Choice1(NoBuildUpEnum::NoBuildUp),
+                Some(build_up) => Choose::Choice2(build_up),
+            },
+            self.energy_use,
+            flags,
+        )
+    }
+
+    /// The speed when starting to move, the max speed without build up.
+    pub fn start_speed(&self) -> u32 {
+        self.build_up.map_or(self.max_speed, |(_, _, start)| start)
+    }
+
+    /// The speed after moving some tiles in a straight line, building up from the start speed to
+    /// the max speed over the build up time.
+    pub fn speed_after(&self, tiles: u32) -> u32 {
+        let (time, start) = match self.build_up {
+            Some((time, _, start)) if tiles < time => (time, start),
+            _ => return self.max_speed,
+        };
+        let progress = tiles as f64 / time as f64;
+        (start as f64 + (self.max_speed as f64 - start as f64) * progress).round() as u32
+    }
+
+    /// The speed right after turning from a speed, slowed down to the max turning speed.
+    pub fn speed_after_turning(&self, speed: u32) -> u32 {
+        match self.build_up {
+            Some((_, turning, _)) => speed.max(turning),
+            None => speed,
+        }
+    }
+
+    /// How much the flags of the gait change its speeds for a mover, as a factor of the speed.
+    ///
+    /// The game doesn't document how `STRENGTH` and `AGILITY` work, so they are estimated: each
+    /// makes the gait up to 40% faster at 5000 and 10% slower at 0, in a straight line through
+    /// the average.
+    pub fn factor(&self, mover: &Mover) -> f64 {
+        let attribute = |value: u32| 1.0 - (value as f64 - AVERAGE_ATTRIBUTE) / 10_000.0;
+        let mut factor = 1.0;
+        if self.flags.strength.is_some() {
+            factor *= attribute(mover.strength);
+        }
+        if self.flags.agility.is_some() {
+            factor *= attribute(mover.agility);
+        }
+        if self.flags.layers_slow.is_some() {
+            factor *= 1.0 + mover.fat_slowdown / 100.0;
+            if self.flags.strength.is_none() {
+                factor *= 1.0 + mover.muscle_slowdown / 100.0;
+            }
+        }
+        if let Some(slowdown) = self.flags.stealth_slows.filter(|_| mover.sneaking) {
+            factor *= 1.0 + slowdown as f64 / 100.0;
+        }
+        factor
+    }
+
+    /// The max speed for a mover.
+    pub fn max_speed_for(&self, mover: &Mover) -> u32 {
+        (self.max_speed as f64 * self.factor(mover)).round() as u32
+    }
+
+    /// The max speed in kph, for an average mover.
+    pub fn max_kph(&self) -> f64 {
+        speed_to_kph(self.max_speed)
+    }
+
+    /// The max speed in tiles per tick, for an average mover.
+    pub fn max_tiles_per_tick(&self) -> f64 {
+        tiles_per_tick(self.max_speed)
+    }
+
+    /// Whether moving with the gait tires the creature at all.
+    pub fn is_tiring(&self) -> bool {
+        self.energy_use > 0
+    }
+
+    /// The exertion from moving some tiles with the gait.
+    pub fn exertion(&self, tiles: u32) -> u32 {
+        self.energy_use.saturating_mul(tiles)
+    }
+
+    /// The exertion per tick at max speed for a mover, since faster gaits move more tiles in the
+    /// same time.
+    pub fn exertion_per_tick(&self, mover: &Mover) -> f64 {
+        self.energy_use as f64 * tiles_per_tick(self.max_speed_for(mover))
+    }
+
+    /// How many tiles the gait can be kept up before reaching some exertion, `None` when it
+    /// doesn't tire.
+    pub fn tiles_until(&self, exertion: u32) -> Option<u32> {
+        if !self.is_tiring() {
+            return None;
+        }
+        Some(exertion.div_ceil(self.energy_use))
+    }
+}
+
+/// A gait speed in kph.
+pub fn speed_to_kph(speed: u32) -> f64 {
+    KPH_AT_ONE / speed.max(1) as f64
+}
+
+/// The gait speed for a speed in kph.
+pub fn kph_to_speed(kph: f64) -> u32 {
+    (KPH_AT_ONE / kph).round().max(1.0) as u32
+}
+
+/// A gait speed in tiles per tick.
+pub fn tiles_per_tick(speed: u32) -> f64 {
+    100.0 / speed.max(1) as f64
+}
+
+/// The gaits of a standard gait variation like `STANDARD_WALKING_GAITS` for the speed of its
+/// fastest gait in kph, from the speed table of `consts.yml`.
+pub fn standard_gaits(gait_type: GaitTypeEnum, kph: u32) -> Result<Vec<Gait>> {
+    let speeds = match Consts::standard().gait_speeds(kph) {
+        Some(speeds) => speeds,
+        None => bail!("no gait speeds for {}kph", kph),
+    };
+    let names = match STANDARD_GAIT_NAMES
+        .iter()
+        .find(|(other, _)| *other == gait_type)
+    {
+        Some((_, names)) => names,
+        None => bail!("no standard gaits for {:?}", gait_type),
+    };
+    let [walk, jog, run, sprint, stroll, creep] = speeds;
+    let fast = |stealth_slows| GaitFlagTokenArg {
+        layers_slow: Some(()),
+        strength: Some(()),
+        agility: Some(()),
+        stealth_slows: Some(stealth_slows),
+    };
+    let gaits = [
+        (sprint, Some((10, 3, jog)), 50, fast(50)),
+        (run, Some((5, 3, jog)), 10, fast(20)),
+        (jog, None, 5, fast(10)),
+        (walk, None, 0, GaitFlagTokenArg::default()),
+        (stroll, None, 0, GaitFlagTokenArg::default()),
+        (creep, None, 0, GaitFlagTokenArg::default()),
+    ];
+    Ok(names
+        .iter()
+        .zip(gaits)
+        .map(|(name, (max_speed, build_up, energy_use, flags))| Gait {
+            gait_type: gait_type.clone(),
+            name: (*name).to_owned(),
+            max_speed,
+            build_up,
+            energy_use,
+            flags,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn gaits() -> Result<()> {
+        let gaits = standard_gaits(GaitTypeEnum::Walk, 45)?;
+        let (sprint, walk) = (&gaits[0], &gaits[3]);
+        assert_eq!((sprint.name.as_str(), sprint.max_speed), ("Fastest Walk", 195));
+        assert_eq!(sprint.max_kph().round(), 45.0);
+        assert_eq!(walk.max_tiles_per_tick(), 100.0 / 900.0);
+        assert_eq!(kph_to_speed(45.0), 195);
+        assert_eq!(Gait::from_token(&sprint.to_token()), *sprint);
+
+        assert_eq!(sprint.start_speed(), 585);
+        assert_eq!(sprint.speed_after(5), 390);
+        assert_eq!(sprint.speed_after(10), 195);
+        assert_eq!(sprint.speed_after_turning(195), 195);
+        assert_eq!(walk.speed_after(0), 900);
+        assert!(sprint.is_tiring() && !walk.is_tiring());
+        assert_eq!(sprint.exertion(10), 500);
+        assert_eq!(sprint.exertion_per_tick(&Mover::default()), 50.0 * (100.0 / 195.0));
+        assert_eq!(sprint.tiles_until(120), Some(3));
+        assert_eq!(walk.tiles_until(120), None);
+
+        let strong = Mover {
+            strength: 2000,
+            muscle_slowdown: 50.0,
+            ..Default::default()
+        };
+        assert_eq!(sprint.max_speed_for(&strong), 176);
+        assert_eq!(walk.max_speed_for(&strong), 900);
+        let sneaking = Mover {
+            sneaking: true,
+            ..Default::default()
+        };
+        assert_eq!(sprint.max_speed_for(&sneaking), 293);
+        assert!(standard_gaits(GaitTypeEnum::Swim, 0).is_err());
+        Ok(())
+    }
+}
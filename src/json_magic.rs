@@ -1,5 +1,3 @@
-use std::collections::HashMap;
-
 use serde_json::{Map, Value, Value::*};
 
 pub fn cleanup(value: serde_json::Value) -> Option<serde_json::Value> {
@@ -27,11 +25,7 @@ pub fn cleanup(value: serde_json::Value) -> Option<serde_json::Value> {
                 .into_iter()
                 .filter_map(|(k, v)| {
                     let v = cleanup(v);
-                    if let Some(v) = v {
-                        Some((k, v))
-                    } else {
-                        None
-                    }
+                    v.map(|v| (k, v))
                 })
                 .collect();
             if v.is_empty() {
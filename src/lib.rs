@@ -1,48 +1,87 @@
 #![forbid(unsafe_code)]
+#[cfg(test)]
+mod aliases;
+mod body;
+mod body_detail;
+mod caste;
+mod compact;
 mod core;
+mod definition;
+mod encoding;
+mod gait;
+#[cfg(test)]
 mod json_magic;
+mod material;
+mod node;
+mod palette;
+mod patch;
+mod raw_set;
+mod references;
+mod registry;
+mod report;
+mod selector;
 mod structure;
-
-use std::fs::{DirEntry, ReadDir};
+mod tags;
+mod units;
+mod variation;
+mod writer;
 
 use df_ls_structure::DFRaw as ParsedDFRaw;
 
+pub use crate::body::*;
+pub use crate::caste::*;
+pub use crate::compact::*;
+pub use crate::core::{DFChar, ReferenceTo, Referenceable};
+pub use crate::definition::*;
+pub use crate::encoding::*;
+pub use crate::gait::*;
+pub use crate::material::*;
+pub use crate::palette::*;
+pub use crate::patch::*;
+pub use crate::raw_set::*;
+pub use crate::references::*;
+pub use crate::registry::*;
+pub use crate::report::*;
+pub use crate::selector::*;
 pub use crate::structure::*;
+pub use crate::tags::*;
+pub use crate::units::*;
+pub use crate::variation::*;
+pub use crate::writer::{write_object_token, write_raw, write_raw_cp437, write_raw_object};
 
 use anyhow::Result;
 
-fn try_flatten<Output, Residual>(
-    mut iter: impl Iterator<Item = Result<Output, Residual>>,
-) -> Result<impl IntoIterator<Item = Output>, Residual> {
-    iter.try_fold(vec![], |mut acc, item| {
-        acc.push(item?);
-        Ok(acc)
-    })
+/// Parses a raw file, failing with the [`ParseReport`] as error if there are any diagnostics.
+pub fn parse(source: &str) -> Result<ParseReport> {
+    let report = parse_lossy(source)?;
+    if !report.diagnostics.is_empty() {
+        return Err(report.into());
+    }
+    Ok(report)
 }
 
-pub fn parse(source: &str) -> Result<DFRaw> {
-    let (tree, diagnostic_list_lexer) = df_ls_lexical_analysis::do_lexical_analysis(source);
-    anyhow::ensure!(
-        diagnostic_list_lexer.is_empty(),
-        "Legixal analysis failed: {:#?}",
-        diagnostic_list_lexer
-    );
+/// Parses a raw file, keeping whatever could be recovered alongside the diagnostics.
+pub fn parse_lossy(source: &str) -> Result<ParseReport> {
+    let marked = encoding::mark_quoted_chars(source);
+    let (tree, diagnostic_list_lexer) = df_ls_lexical_analysis::do_lexical_analysis(&marked);
     let (structure, diagnostic_list): (ParsedDFRaw, _) =
-        df_ls_syntax_analysis::do_syntax_analysis(&tree, source);
-    anyhow::ensure!(
-        diagnostic_list.is_empty(),
-        "Syntax analysis failed: {:#?}",
-        diagnostic_list
-    );
-    return Ok(serde_transmute(structure)?);
-}
-
-pub fn parse_lossy(source: &str) -> Result<DFRaw> {
-    let (tree, _) = df_ls_lexical_analysis::do_lexical_analysis(source);
-    let (structure, _): (ParsedDFRaw, _) = df_ls_syntax_analysis::do_syntax_analysis(&tree, source);
-    Ok(serde_transmute(structure)?)
+        df_ls_syntax_analysis::do_syntax_analysis(&tree, &marked);
+    let mut diagnostics =
+        report::convert_diagnostics(DiagnosticStage::Lexical, &diagnostic_list_lexer, source)?;
+    diagnostics.extend(report::convert_diagnostics(
+        DiagnosticStage::Syntax,
+        &diagnostic_list,
+        source,
+    )?);
+    Ok(ParseReport {
+        path: None,
+        raw: node::stream::transmute(&structure, |c| DFChar::from_parsed(c).0)?,
+        diagnostics,
+    })
 }
 
+/// Converts between two types with a compatible serde representation, through a
+/// `serde_json::Value`. Prefer [`transmute`], which gives the same result for less.
 pub fn serde_transmute<I, O>(from: I) -> Result<O>
 where
     I: serde::Serialize,
@@ -51,35 +90,24 @@ where
     Ok(serde_json::from_value(serde_json::to_value(&from)?)?)
 }
 
-pub fn convert_to_json(input: &str) -> Result<String> {
-    let raw = parse(input)?;
-    let json = serde_json::to_string(&raw)?;
-    return Ok(json);
+/// Converts between two types with a compatible serde representation, giving exactly what
+/// [`serde_transmute`] would.
+///
+/// The value is serialized on a second thread and deserialized as it arrives, so it is never
+/// held in between as a whole, only the maps in it are. `benches/transmute.rs` compares both on
+/// `raw/objects`.
+pub fn transmute<I, O>(from: I) -> Result<O>
+where
+    I: serde::Serialize + Sync,
+    O: serde::de::DeserializeOwned,
+{
+    Ok(node::stream::transmute(&from, |c| c)?)
 }
 
-pub fn load_all() -> Result<Vec<crate::ObjectToken>> {
-    let raw_files = std::fs::read_dir("./raw/objects")?;
-    //let raw_files = try_flatten(raw_files)?;
-
-    let mut tokens: Vec<ObjectToken> = vec![];
-
-    let content = raw_files
-        .flatten()
-        .filter_map(|x| std::fs::read_to_string(x.path()).ok());
-
-    for content in content {
-        // println!("{}", content);
-        let cool = parse_lossy(&content);
-        match cool {
-            Ok(raw) => {
-                for token in raw.object_tokens {
-                    tokens.push(token);
-                }
-            }
-            Err(e) => println!("{}", e),
-        }
-    }
-    Ok(tokens)
+pub fn convert_to_json(input: &str) -> Result<String> {
+    let raw = parse(input)?.raw;
+    let json = serde_json::to_string(&raw)?;
+    Ok(json)
 }
 
 #[cfg(test)]
@@ -88,12 +116,10 @@ mod tests {
 
     use crate::{json_magic::cleanup, *};
     #[test]
-    fn parse_real() -> Result<()> {
-        let data = load_all()?;
-        let data = cleanup(serde_json::to_value(data)?);
-        let writer = std::fs::File::create("data.json")?;
-        serde_json::to_writer_pretty(writer, &data)?;
-        Ok(())
+    fn parse_real() {
+        let raws = RawSet::load(["./raw/objects"]);
+        assert!(raws.errors.is_empty(), "{:#?}", raws.errors);
+        assert!(!raws.is_empty());
     }
     #[test]
     fn parse_trival() -> Result<()> {
@@ -123,11 +149,29 @@ mod tests {
 
     [BODYGLOSS:PAW:foot:paw:feet:paws]
     ";
-        let raw = parse(source)?;
+        let raw = parse(source)?.raw;
         println!("{}", serde_json::to_string_pretty(&raw)?);
 
         let data = cleanup(serde_json::to_value(raw)?).context("nothing?")?;
         println!("{}", serde_json::to_string_pretty(&data)?);
         Ok(())
     }
+    #[test]
+    fn transmute_matches_json() -> Result<()> {
+        for entry in std::fs::read_dir("./raw/objects")? {
+            let path = entry?.path();
+            if !path.is_file() {
+                continue;
+            }
+            let source = read_raw_file(&path, Encoding::Auto)
+                .with_context(|| format!("could not read {}", path.display()))?;
+            let (tree, _) = df_ls_lexical_analysis::do_lexical_analysis(&source);
+            let (structure, _): (ParsedDFRaw, _) =
+                df_ls_syntax_analysis::do_syntax_analysis(&tree, &source);
+            let json: DFRaw = serde_transmute(&structure)?;
+            let node: DFRaw = transmute(&structure)?;
+            assert!(json == node, "{} converts differently", path.display());
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,151 @@
+//! The tokens that edit a material, taken from the aliases of
+//! [`MaterialToken`](crate::MaterialToken) and the structures that select materials, which the
+//! tests check it against.
+
+/// Tokens that apply to the selected materials. Syndromes follow `SYNDROME` and are not listed.
+pub(super) const MATERIAL_TOKENS: &[&str] = &[
+    "WAFERS",
+    "METAL_ORE",
+    "THREAD_METAL",
+    "SYNDROME",
+    "STONE_NAME",
+    "IS_GEM",
+    "TEMP_DIET_INFO",
+    "POWDER_DYE",
+    "TILE",
+    "ITEM_SYMBOL",
+    "DISPLAY_COLOR",
+    "BUILD_COLOR",
+    "TILE_COLOR",
+    "BASIC_COLOR",
+    "STATE_COLOR",
+    "STATE_NAME",
+    "STATE_ADJ",
+    "STATE_NAME_ADJ",
+    "ABSORPTION",
+    "IMPACT_YIELD",
+    "IMPACT_FRACTURE",
+    "IMPACT_STRAIN_AT_YIELD",
+    "IMPACT_ELASTICITY",
+    "COMPRESSIVE_YIELD",
+    "COMPRESSIVE_FRACTURE",
+    "COMPRESSIVE_STRAIN_AT_YIELD",
+    "COMPRESSIVE_ELASTICITY",
+    "TENSILE_YIELD",
+    "TENSILE_FRACTURE",
+    "TENSILE_STRAIN_AT_YIELD",
+    "TENSILE_ELASTICITY",
+    "TORSION_YIELD",
+    "TORSION_FRACTURE",
+    "TORSION_STRAIN_AT_YIELD",
+    "TORSION_ELASTICITY",
+    "SHEAR_YIELD",
+    "SHEAR_FRACTURE",
+    "SHEAR_STRAIN_AT_YIELD",
+    "SHEAR_ELASTICITY",
+    "BENDING_YIELD",
+    "BENDING_FRACTURE",
+    "BENDING_STRAIN_AT_YIELD",
+    "BENDING_ELASTICITY",
+    "MAX_EDGE",
+    "MATERIAL_VALUE",
+    "SPEC_HEAT",
+    "HEATDAM_POINT",
+    "COLDDAM_POINT",
+    "IGNITE_POINT",
+    "MELTING_POINT",
+    "BOILING_POINT",
+    "MAT_FIXED_TEMP",
+    "SOLID_DENSITY",
+    "LIQUID_DENSITY",
+    "MOLAR_MASS",
+    "EXTRACT_STORAGE",
+    "BUTCHER_SPECIAL",
+    "MEAT_NAME",
+    "BLOCK_NAME",
+    "MATERIAL_REACTION_PRODUCT",
+    "ITEM_REACTION_PRODUCT",
+    "REACTION_CLASS",
+    "HARDENS_WITH_WATER",
+    "SOAP_LEVEL",
+    "IMPLIES_ANIMAL_KILL",
+    "ALCOHOL_PLANT",
+    "ALCOHOL_CREATURE",
+    "ALCOHOL",
+    "CHEESE_PLANT",
+    "CHEESE_CREATURE",
+    "CHEESE",
+    "POWDER_MISC_PLANT",
+    "POWDER_MISC_CREATURE",
+    "POWDER_MISC",
+    "STOCKPILE_GLOB",
+    "STOCKPILE_GLOB_SOLID",
+    "STOCKPILE_GLOB_PASTE",
+    "STOCKPILE_GLOB_PRESSED",
+    "STOCKPILE_PLANT_GROWTH",
+    "LIQUID_MISC_PLANT",
+    "LIQUID_MISC_CREATURE",
+    "LIQUID_MISC_OTHER",
+    "LIQUID_MISC",
+    "STRUCTURAL_PLANT_MAT",
+    "SEED_MAT",
+    "BONE",
+    "WOOD",
+    "THREAD_PLANT",
+    "TOOTH",
+    "HORN",
+    "PEARL",
+    "SHELL",
+    "LEATHER",
+    "SILK",
+    "SOAP",
+    "GENERATES_MIASMA",
+    "MEAT",
+    "ROTS",
+    "BLOOD_MAP_DESCRIPTOR",
+    "ICHOR_MAP_DESCRIPTOR",
+    "GOO_MAP_DESCRIPTOR",
+    "SLIME_MAP_DESCRIPTOR",
+    "PUS_MAP_DESCRIPTOR",
+    "SWEAT_MAP_DESCRIPTOR",
+    "TEARS_MAP_DESCRIPTOR",
+    "SPIT_MAP_DESCRIPTOR",
+    "EVAPORATES",
+    "ENTERS_BLOOD",
+    "EDIBLE_VERMIN",
+    "EDIBLE_RAW",
+    "EDIBLE_COOKED",
+    "DO_NOT_CLEAN_GLOB",
+    "NO_STONE_STOCKPILE",
+    "ITEMS_METAL",
+    "ITEMS_BARRED",
+    "ITEMS_SCALED",
+    "ITEMS_LEATHER",
+    "ITEMS_SOFT",
+    "ITEMS_HARD",
+    "IS_STONE",
+    "UNDIGGABLE",
+    "DISPLAY_UNGLAZED",
+    "YARN",
+    "STOCKPILE_THREAD_METAL",
+    "IS_METAL",
+    "IS_GLASS",
+    "CRYSTAL_GLASSABLE",
+    "ITEMS_WEAPON",
+    "ITEMS_WEAPON_RANGED",
+    "ITEMS_ANVIL",
+    "ITEMS_AMMO",
+    "ITEMS_DIGGER",
+    "ITEMS_ARMOR",
+    "ITEMS_DELICATE",
+    "ITEMS_SIEGE_ENGINE",
+    "ITEMS_QUERN",
+    "PREFIX",
+    "MULTIPLY_VALUE",
+    "IF_EXISTS_SET_HEATDAM_POINT",
+    "IF_EXISTS_SET_COLDDAM_POINT",
+    "IF_EXISTS_SET_IGNITE_POINT",
+    "IF_EXISTS_SET_MELTING_POINT",
+    "IF_EXISTS_SET_BOILING_POINT",
+    "IF_EXISTS_SET_MAT_FIXED_TEMP",
+];
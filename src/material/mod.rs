@@ -0,0 +1,596 @@
+//! Materials as the game builds them: a material template, the tokens written after it and any
+//! later `SELECT_MATERIAL` edits, on top of the defaults of every property.
+
+mod material_tokens;
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::raw_set::RawSet;
+use crate::structure::MaterialToken;
+use crate::tags::{parse_tags_as_raw, Tag};
+use crate::variation::CreatureExpander;
+
+use self::material_tokens::MATERIAL_TOKENS;
+
+/// The states `ALL` stands for in `STATE_*` tokens.
+const STATES: [&str; 6] = ["SOLID", "LIQUID", "GAS", "POWDER", "PASTE", "PRESSED"];
+/// The states `ALL_SOLID` stands for in `STATE_*` tokens.
+const SOLID_STATES: [&str; 4] = ["SOLID", "POWDER", "PASTE", "PRESSED"];
+
+/// What the game uses for properties nothing sets, as documented on [`MaterialToken`].
+const DEFAULTS: &[&str] = &[
+    "TILE:219",
+    "ITEM_SYMBOL:7",
+    "BUILD_COLOR:7:7:1",
+    "TILE_COLOR:7:7:1",
+    "BASIC_COLOR:7:1",
+    "ABSORPTION:0",
+    "IMPACT_YIELD:10000",
+    "IMPACT_FRACTURE:10000",
+    "IMPACT_STRAIN_AT_YIELD:0",
+    "COMPRESSIVE_YIELD:10000",
+    "COMPRESSIVE_FRACTURE:10000",
+    "COMPRESSIVE_STRAIN_AT_YIELD:0",
+    "TENSILE_YIELD:10000",
+    "TENSILE_FRACTURE:10000",
+    "TENSILE_STRAIN_AT_YIELD:0",
+    "TORSION_YIELD:10000",
+    "TORSION_FRACTURE:10000",
+    "TORSION_STRAIN_AT_YIELD:0",
+    "SHEAR_YIELD:10000",
+    "SHEAR_FRACTURE:10000",
+    "SHEAR_STRAIN_AT_YIELD:0",
+    "BENDING_YIELD:10000",
+    "BENDING_FRACTURE:10000",
+    "BENDING_STRAIN_AT_YIELD:0",
+    "MAX_EDGE:10000",
+    "MATERIAL_VALUE:1",
+    "SPEC_HEAT:NONE",
+    "HEATDAM_POINT:NONE",
+    "COLDDAM_POINT:NONE",
+    "IGNITE_POINT:NONE",
+    "MELTING_POINT:NONE",
+    "BOILING_POINT:NONE",
+    "MAT_FIXED_TEMP:NONE",
+    "SOLID_DENSITY:NONE",
+    "LIQUID_DENSITY:NONE",
+    "MOLAR_MASS:NONE",
+    "SOAP_LEVEL:0",
+];
+
+/// Whether a token edits the selected materials.
+fn is_material_token(name: &str) -> bool {
+    MATERIAL_TOKENS.contains(&name) || name.starts_with("SYN_") || name.starts_with("CE_")
+}
+
+/// The name a token is stored under, for tokens with more than one name.
+fn canonical_name(name: &str) -> String {
+    match name.strip_suffix("_ELASTICITY") {
+        Some(kind) => format!("{}_STRAIN_AT_YIELD", kind),
+        None if name == "STOCKPILE_GLOB_SOLID" => "STOCKPILE_GLOB".to_owned(),
+        None => name.to_owned(),
+    }
+}
+
+/// A material with every edit applied, as one token per property.
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ResolvedMaterial {
+    /// The local ID of the material, or the ID of the inorganic it belongs to.
+    pub id: String,
+    /// The `MATERIAL_TEMPLATE` the material started from.
+    pub template: Option<String>,
+    /// One token per property (per state for `STATE_*`), in the order they were first set.
+    /// `ALL` and `ALL_SOLID` are split into single states, `STATE_NAME_ADJ` and `DISPLAY_COLOR`
+    /// into the tokens they stand for.
+    pub properties: Vec<Tag>,
+    /// Every `SYNDROME` with the tokens that follow it.
+    pub syndromes: Vec<Vec<Tag>>,
+}
+
+impl ResolvedMaterial {
+    /// A material with nothing but the defaults.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            template: None,
+            properties: DEFAULTS.iter().map(|text| Tag::parse(text)).collect(),
+            syndromes: vec![],
+        }
+    }
+
+    /// A material starting from the tokens of a material template, header first.
+    pub fn from_template(id: impl Into<String>, template: &[Tag]) -> Self {
+        let mut material = Self::new(id);
+        material.template = template.first().and_then(|tag| tag.args.first()).cloned();
+        for tag in template.iter().skip(1) {
+            material.apply(tag);
+        }
+        material
+    }
+
+    /// Applies a token the way the game does: later values replace earlier ones, except for
+    /// tokens that can be given more than once.
+    pub fn apply(&mut self, tag: &Tag) {
+        let name = canonical_name(&tag.name);
+        let arg = |n: usize| tag.args.get(n).map(String::as_str).unwrap_or_default();
+        match name.as_str() {
+            "SYNDROME" => self.syndromes.push(vec![tag.clone()]),
+            _ if name.starts_with("SYN_") || name.starts_with("CE_") => {
+                if let Some(syndrome) = self.syndromes.last_mut() {
+                    syndrome.push(tag.clone());
+                }
+            }
+            "STATE_NAME" | "STATE_ADJ" | "STATE_COLOR" => self.set_state(&name, &tag.args),
+            "STATE_NAME_ADJ" => {
+                self.set_state("STATE_NAME", &tag.args);
+                self.set_state("STATE_ADJ", &tag.args);
+            }
+            "DISPLAY_COLOR" => {
+                let (fore, back, bright) = (arg(0), arg(1), arg(2));
+                let same = if fore == back { "1" } else { "0" };
+                self.set(Tag::new("TILE_COLOR", [fore, back, bright]), 0);
+                self.set(Tag::new("BUILD_COLOR", [back, fore, same]), 0);
+                self.set(Tag::new("BASIC_COLOR", [fore, bright]), 0);
+            }
+            "IS_GEM" => {
+                if arg(2) == "OVERWRITE_SOLID" {
+                    let solid = ["SOLID".to_owned(), arg(0).to_owned()];
+                    self.set_state("STATE_NAME", &solid);
+                    self.set_state("STATE_ADJ", &solid);
+                }
+                self.set(tag.clone(), 0);
+            }
+            "MULTIPLY_VALUE" => {
+                let value = self
+                    .property("MATERIAL_VALUE")
+                    .and_then(|tag| tag.args.first()?.parse::<i64>().ok())
+                    .unwrap_or(1);
+                let factor = arg(0).parse::<i64>().unwrap_or(1);
+                self.set(
+                    Tag::new("MATERIAL_VALUE", [(value * factor).to_string()]),
+                    0,
+                );
+            }
+            "REACTION_CLASS" => {
+                if !self.properties.contains(tag) {
+                    self.properties.push(tag.clone());
+                }
+            }
+            "MATERIAL_REACTION_PRODUCT" | "ITEM_REACTION_PRODUCT" => self.set(tag.clone(), 1),
+            _ => match name.strip_prefix("IF_EXISTS_SET_") {
+                Some(property) => {
+                    let exists = self
+                        .property(property)
+                        .is_some_and(|tag| tag.args.first().is_some_and(|arg| arg != "NONE"));
+                    if exists {
+                        self.set(Tag::new(property, tag.args.clone()), 0);
+                    }
+                }
+                None => self.set(Tag::new(name, tag.args.clone()), 0),
+            },
+        }
+    }
+
+    /// Replaces the property with the same name and first `keys` arguments, or adds it.
+    fn set(&mut self, tag: Tag, keys: usize) {
+        let existing = self.properties.iter_mut().find(|property| {
+            property.name == tag.name
+                && property
+                    .args
+                    .iter()
+                    .take(keys)
+                    .eq(tag.args.iter().take(keys))
+        });
+        match existing {
+            Some(existing) => *existing = tag,
+            None => self.properties.push(tag),
+        }
+    }
+
+    /// Sets a `STATE_*` property for each state its first argument stands for.
+    fn set_state(&mut self, name: &str, args: &[String]) {
+        let (state, rest) = match args.split_first() {
+            Some(split) => split,
+            None => return,
+        };
+        let states: Vec<&str> = match state.as_str() {
+            "ALL" => STATES.to_vec(),
+            "ALL_SOLID" => SOLID_STATES.to_vec(),
+            state => vec![state.strip_prefix("SOLID_").unwrap_or(state)],
+        };
+        for state in states {
+            let args = std::iter::once(state).chain(rest.iter().map(String::as_str));
+            self.set(Tag::new(name, args), 1);
+        }
+    }
+
+    /// The token setting a property, e.g. `MELTING_POINT`.
+    pub fn property(&self, name: &str) -> Option<&Tag> {
+        self.properties.iter().find(|property| property.is(name))
+    }
+
+    /// The token setting a `STATE_*` property for one state, e.g. `STATE_NAME` for `LIQUID`.
+    pub fn state_property(&self, name: &str, state: &str) -> Option<&Tag> {
+        self.properties
+            .iter()
+            .find(|property| property.is(name) && property.args.first().is_some_and(|s| s == state))
+    }
+
+    /// Every property followed by every syndrome.
+    pub fn tags(&self) -> Vec<Tag> {
+        self.properties
+            .iter()
+            .chain(self.syndromes.iter().flatten())
+            .cloned()
+            .collect()
+    }
+
+    /// Parses the properties as a material template with the ID of this material. `PREFIX` only
+    /// exists for local materials and inorganics, so it is left out. Fails with the
+    /// [`ParseReport`](crate::ParseReport) when df_ls does not accept any other property.
+    pub fn material(&self) -> Result<MaterialToken> {
+        let header = Tag::new("MATERIAL_TEMPLATE", [self.id.as_str()]);
+        let tags: Vec<Tag> = std::iter::once(header)
+            .chain(self.tags().into_iter().filter(|tag| !tag.is("PREFIX")))
+            .collect();
+        parse_tags_as_raw("MATERIAL_TEMPLATE", &tags)?
+            .object_tokens
+            .into_iter()
+            .flat_map(|object_token| object_token.material_tokens)
+            .next()
+            .context("No material in the given tokens")
+    }
+}
+
+/// Where material resolution looks up `MATERIAL_TEMPLATE`s, and the `BODY_DETAIL_PLAN`s that
+/// add materials from them.
+pub trait MaterialTemplateSource {
+    /// The tokens of a material template, header first.
+    fn material_template(&mut self, id: &str) -> Result<Option<Vec<Tag>>>;
+    /// The tokens of a body detail plan, header first.
+    fn body_detail_plan(&mut self, id: &str) -> Result<Option<Vec<Tag>>>;
+}
+
+/// Objects by ID, each found only under its own header.
+impl MaterialTemplateSource for HashMap<String, Vec<Tag>> {
+    fn material_template(&mut self, id: &str) -> Result<Option<Vec<Tag>>> {
+        Ok(find_object(self, "MATERIAL_TEMPLATE", id))
+    }
+
+    fn body_detail_plan(&mut self, id: &str) -> Result<Option<Vec<Tag>>> {
+        Ok(find_object(self, "BODY_DETAIL_PLAN", id))
+    }
+}
+
+fn find_object(objects: &HashMap<String, Vec<Tag>>, header: &str, id: &str) -> Option<Vec<Tag>> {
+    objects
+        .get(id)
+        .filter(|tags| tags.first().is_some_and(|tag| tag.is(header)))
+        .cloned()
+}
+
+impl MaterialTemplateSource for CreatureExpander<'_> {
+    fn material_template(&mut self, id: &str) -> Result<Option<Vec<Tag>>> {
+        self.find_tags("MATERIAL_TEMPLATE", id)
+    }
+
+    fn body_detail_plan(&mut self, id: &str) -> Result<Option<Vec<Tag>>> {
+        self.find_tags("BODY_DETAIL_PLAN", id)
+    }
+}
+
+fn template(source: &mut impl MaterialTemplateSource, id: &str) -> Result<Vec<Tag>> {
+    source
+        .material_template(id)?
+        .with_context(|| format!("Unknown material template `{}`", id))
+}
+
+fn body_detail_plan(source: &mut impl MaterialTemplateSource, id: &str) -> Result<Vec<Tag>> {
+    source
+        .body_detail_plan(id)?
+        .with_context(|| format!("Unknown body detail plan `{}`", id))
+}
+
+/// Resolves the local materials of a creature or plant from its tokens, header first:
+///
+/// - `USE_MATERIAL_TEMPLATE:ID:TEMPLATE` starts a material from a template, `MATERIAL:ID` from
+///   the defaults and `USE_MATERIAL:ID:OTHER` from a copy of another local material.
+/// - `SELECT_MATERIAL` selects a material, or every material with `ALL`, and `PLUS_MATERIAL`
+///   adds one to the selection.
+/// - `REMOVE_MATERIAL` removes a material.
+/// - `BODY_DETAIL_PLAN` adds each `ADD_MATERIAL:ID:TEMPLATE` of the plan, like
+///   `USE_MATERIAL_TEMPLATE`.
+///
+/// Material tokens apply to the selected materials until a token that isn't a material token.
+pub fn resolve_local_materials(
+    tags: &[Tag],
+    source: &mut impl MaterialTemplateSource,
+) -> Result<Vec<ResolvedMaterial>> {
+    let mut materials: Vec<ResolvedMaterial> = vec![];
+    let mut selected: Vec<usize> = vec![];
+    let find = |materials: &[ResolvedMaterial], id: &str| materials.iter().position(|m| m.id == id);
+    let insert = |materials: &mut Vec<ResolvedMaterial>, material: ResolvedMaterial| match find(
+        materials,
+        &material.id,
+    ) {
+        Some(index) => {
+            materials[index] = material;
+            index
+        }
+        None => {
+            materials.push(material);
+            materials.len() - 1
+        }
+    };
+    for tag in tags.iter().skip(1) {
+        let arg = |n: usize| tag.args.get(n).map(String::as_str).unwrap_or_default();
+        match tag.name.as_str() {
+            "USE_MATERIAL_TEMPLATE" => {
+                let material = ResolvedMaterial::from_template(arg(0), &template(source, arg(1))?);
+                selected = vec![insert(&mut materials, material)];
+            }
+            "MATERIAL" => selected = vec![insert(&mut materials, ResolvedMaterial::new(arg(0)))],
+            "BODY_DETAIL_PLAN" => {
+                for added in body_detail_plan(source, arg(0))? {
+                    if !added.is("ADD_MATERIAL") {
+                        continue;
+                    }
+                    let arg = |n: usize| added.args.get(n).map(String::as_str).unwrap_or_default();
+                    let material =
+                        ResolvedMaterial::from_template(arg(0), &template(source, arg(1))?);
+                    insert(&mut materials, material);
+                }
+                selected.clear();
+            }
+            "USE_MATERIAL" => {
+                let other = find(&materials, arg(1))
+                    .with_context(|| format!("Unknown local material `{}`", arg(1)))?;
+                let material = ResolvedMaterial {
+                    id: arg(0).to_owned(),
+                    ..materials[other].clone()
+                };
+                selected = vec![insert(&mut materials, material)];
+            }
+            "SELECT_MATERIAL" if arg(0) == "ALL" => selected = (0..materials.len()).collect(),
+            "SELECT_MATERIAL" => selected = find(&materials, arg(0)).into_iter().collect(),
+            "PLUS_MATERIAL" => {
+                if let Some(index) = find(&materials, arg(0)) {
+                    if !selected.contains(&index) {
+                        selected.push(index);
+                    }
+                }
+            }
+            "REMOVE_MATERIAL" => {
+                if let Some(index) = find(&materials, arg(0)) {
+                    materials.remove(index);
+                }
+                selected.clear();
+            }
+            name if is_material_token(name) => {
+                for &index in &selected {
+                    materials[index].apply(tag);
+                }
+            }
+            _ => selected.clear(),
+        }
+    }
+    Ok(materials)
+}
+
+/// Resolves the material of an inorganic from its tokens, header first. Its
+/// `USE_MATERIAL_TEMPLATE` takes only the template, and every material token edits the material.
+pub fn resolve_inorganic_material(
+    tags: &[Tag],
+    source: &mut impl MaterialTemplateSource,
+) -> Result<ResolvedMaterial> {
+    let id = tags
+        .first()
+        .and_then(|tag| tag.args.first())
+        .context("No inorganic in the given tokens")?;
+    let mut material = ResolvedMaterial::new(id.as_str());
+    for tag in tags.iter().skip(1) {
+        if tag.is("USE_MATERIAL_TEMPLATE") {
+            let template_id = tag.args.first().map(String::as_str).unwrap_or_default();
+            material =
+                ResolvedMaterial::from_template(id.as_str(), &template(source, template_id)?);
+        } else if is_material_token(&tag.name) {
+            material.apply(tag);
+        }
+    }
+    Ok(material)
+}
+
+impl RawSet {
+    /// The local materials of a creature, after expanding it.
+    pub fn creature_materials(&self, id: &str) -> Result<Vec<ResolvedMaterial>> {
+        let mut expander = CreatureExpander::new(self);
+        let tags = expander.creature_tags(id)?;
+        resolve_local_materials(&tags, &mut expander)
+    }
+
+    /// The local materials of a plant.
+    pub fn plant_materials(&self, id: &str) -> Result<Vec<ResolvedMaterial>> {
+        let mut expander = CreatureExpander::new(self);
+        let tags = expander
+            .find_tags("PLANT", id)?
+            .with_context(|| format!("Unknown plant `{}`", id))?;
+        resolve_local_materials(&tags, &mut expander)
+    }
+
+    /// The material of an inorganic.
+    pub fn inorganic_material(&self, id: &str) -> Result<ResolvedMaterial> {
+        let mut expander = CreatureExpander::new(self);
+        let tags = expander
+            .find_tags("INORGANIC", id)?
+            .with_context(|| format!("Unknown inorganic `{}`", id))?;
+        resolve_inorganic_material(&tags, &mut expander)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn material_templates() -> Result<()> {
+        let mut templates = std::collections::HashMap::new();
+        templates.insert(
+            "SKIN_TEMPLATE".to_owned(),
+            parse_tags(
+                "[MATERIAL_TEMPLATE:SKIN_TEMPLATE]
+                    [STATE_NAME_ADJ:ALL_SOLID:skin]
+                    [STATE_COLOR:ALL:PEACH]
+                    [DISPLAY_COLOR:6:0:0]
+                    [MATERIAL_VALUE:2]
+                    [SPEC_HEAT:4181]
+                    [HEATDAM_POINT:10250]
+                    [REACTION_CLASS:TANNING]",
+            ),
+        );
+        let creature = parse_tags(
+            "[CREATURE:TOAD]
+                [USE_MATERIAL_TEMPLATE:SKIN:SKIN_TEMPLATE]
+                    [STATE_COLOR:SOLID:GREEN]
+                    [REACTION_CLASS:TANNING]
+                [USE_MATERIAL:HIDE:SKIN]
+                    [IMPACT_ELASTICITY:50000]
+                [MATERIAL:SLIME]
+                [BODY_SIZE:0:0:10]
+                    [MATERIAL_VALUE:20]
+                [SELECT_MATERIAL:SKIN]
+                [PLUS_MATERIAL:HIDE]
+                    [MULTIPLY_VALUE:3]
+                    [IF_EXISTS_SET_HEATDAM_POINT:10100]
+                    [IF_EXISTS_SET_IGNITE_POINT:10100]
+                [REMOVE_MATERIAL:SLIME]",
+        );
+        let materials = resolve_local_materials(&creature, &mut templates)?;
+        assert_eq!(
+            materials.iter().map(|m| m.id.as_str()).collect::<Vec<_>>(),
+            ["SKIN", "HIDE"]
+        );
+        let skin = &materials[0];
+        assert_eq!(skin.template.as_deref(), Some("SKIN_TEMPLATE"));
+        let text = |tag: Option<&Tag>| tag.map(Tag::text);
+        assert_eq!(
+            text(skin.state_property("STATE_NAME", "POWDER")),
+            Some("STATE_NAME:POWDER:skin".to_owned())
+        );
+        assert_eq!(skin.state_property("STATE_ADJ", "LIQUID"), None);
+        assert_eq!(
+            text(skin.state_property("STATE_COLOR", "SOLID")),
+            Some("STATE_COLOR:SOLID:GREEN".to_owned())
+        );
+        assert_eq!(
+            text(skin.state_property("STATE_COLOR", "GAS")),
+            Some("STATE_COLOR:GAS:PEACH".to_owned())
+        );
+        assert_eq!(text(skin.property("BUILD_COLOR")), Some("BUILD_COLOR:0:6:0".to_owned()));
+        assert_eq!(text(skin.property("MATERIAL_VALUE")), Some("MATERIAL_VALUE:6".to_owned()));
+        assert_eq!(text(skin.property("HEATDAM_POINT")), Some("HEATDAM_POINT:10100".to_owned()));
+        assert_eq!(text(skin.property("IGNITE_POINT")), Some("IGNITE_POINT:NONE".to_owned()));
+        assert_eq!(text(skin.property("IMPACT_YIELD")), Some("IMPACT_YIELD:10000".to_owned()));
+        assert_eq!(
+            skin.properties.iter().filter(|tag| tag.is("REACTION_CLASS")).count(),
+            1
+        );
+        let hide = &materials[1];
+        assert_eq!(
+            text(hide.property("IMPACT_STRAIN_AT_YIELD")),
+            Some("IMPACT_STRAIN_AT_YIELD:50000".to_owned())
+        );
+        assert_eq!(text(hide.property("MATERIAL_VALUE")), Some("MATERIAL_VALUE:6".to_owned()));
+
+        let inorganic = parse_tags(
+            "[INORGANIC:GREENSTONE]
+                [USE_MATERIAL_TEMPLATE:SKIN_TEMPLATE]
+                [ENVIRONMENT:ALL_STONE:VEIN:100]
+                [MELTING_POINT:12000]",
+        );
+        let greenstone = resolve_inorganic_material(&inorganic, &mut templates)?;
+        assert_eq!(greenstone.id, "GREENSTONE");
+        assert_eq!(
+            text(greenstone.property("MELTING_POINT")),
+            Some("MELTING_POINT:12000".to_owned())
+        );
+        assert!(greenstone.property("ENVIRONMENT").is_none());
+
+        let template = greenstone.material()?;
+        assert_eq!(template.reference, Some(ReferenceTo::new("GREENSTONE".to_owned())));
+        let mut rejected = greenstone.clone();
+        rejected.properties.push(Tag::new("NOT_A_TOKEN", ["1"]));
+        let error = rejected.material().unwrap_err();
+        assert!(error.to_string().contains("[NOT_A_TOKEN:1]"), "{}", error);
+        Ok(())
+    }
+    #[test]
+    fn body_detail_plan_materials() -> Result<()> {
+        let mut objects = std::collections::HashMap::new();
+        for tags in [
+            parse_tags("[MATERIAL_TEMPLATE:SKIN_TEMPLATE][MATERIAL_VALUE:2]"),
+            parse_tags("[MATERIAL_TEMPLATE:BONE_TEMPLATE][MATERIAL_VALUE:3]"),
+            parse_tags(
+                "[BODY_DETAIL_PLAN:STANDARD_MATERIALS]
+                    [ADD_MATERIAL:SKIN:SKIN_TEMPLATE]
+                    [ADD_MATERIAL:BONE:BONE_TEMPLATE]
+                    [ADD_TISSUE:SKIN:SKIN_TISSUE_TEMPLATE]",
+            ),
+        ] {
+            objects.insert(tags[0].args[0].clone(), tags);
+        }
+        let creature = parse_tags(
+            "[CREATURE:HAMSTER]
+                [BODY_DETAIL_PLAN:STANDARD_MATERIALS]
+                [SELECT_MATERIAL:BONE]
+                    [MULTIPLY_VALUE:2]",
+        );
+        let materials = resolve_local_materials(&creature, &mut objects)?;
+        assert_eq!(
+            materials.iter().map(|m| m.id.as_str()).collect::<Vec<_>>(),
+            ["SKIN", "BONE"]
+        );
+        assert_eq!(materials[0].template.as_deref(), Some("SKIN_TEMPLATE"));
+        let value =
+            |material: &ResolvedMaterial| material.property("MATERIAL_VALUE").map(Tag::text);
+        assert_eq!(value(&materials[0]), Some("MATERIAL_VALUE:2".to_owned()));
+        assert_eq!(value(&materials[1]), Some("MATERIAL_VALUE:6".to_owned()));
+
+        let unknown = parse_tags("[CREATURE:HAMSTER][BODY_DETAIL_PLAN:UNKNOWN_PLAN]");
+        assert!(resolve_local_materials(&unknown, &mut objects).is_err());
+        Ok(())
+    }
+    #[test]
+    fn material_tokens_match_aliases() {
+        use std::collections::BTreeSet;
+
+        use super::material_tokens::MATERIAL_TOKENS;
+        use super::DEFAULTS;
+        use crate::aliases::{item_aliases, structure_aliases};
+
+        let aliases = structure_aliases();
+        let tokens: BTreeSet<&str> = MATERIAL_TOKENS.iter().copied().collect();
+        assert_eq!(tokens.len(), MATERIAL_TOKENS.len());
+        // Every structure that selects a material takes the same tokens after its header.
+        for (item, headers) in [
+            ("UseMaterialTemplate", vec!["USE_MATERIAL_TEMPLATE"]),
+            ("UseMaterial", vec!["USE_MATERIAL"]),
+            ("SelectMaterial", vec!["SELECT_MATERIAL", "PLUS_MATERIAL"]),
+        ] {
+            let headers: BTreeSet<&str> = headers.into_iter().collect();
+            assert_eq!(tokens, &item_aliases(&aliases, item) - &headers, "{}", item);
+        }
+        // Templates take the same, without the tokens only used when applying one.
+        let template = item_aliases(&aliases, "MaterialToken");
+        for token in &template - &BTreeSet::from(["MATERIAL_TEMPLATE"]) {
+            assert!(tokens.contains(token), "{}", token);
+        }
+        for default in DEFAULTS {
+            let name = default.split(':').next().unwrap_or_default();
+            assert!(template.contains(name), "{}", default);
+        }
+    }
+}
@@ -0,0 +1,527 @@
+//! Deserializes a `Node` exactly like `serde_json` deserializes the `Value` the same data would
+//! serialize to, so `stream` gives the same result for maps as the JSON round trip.
+//!
+//! In JSON terms: `None` and `()` are `null`, a `char` is a string, structs and maps are objects
+//! and enums are externally tagged.
+
+use std::sync::OnceLock;
+
+use serde::de::{
+    self, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, Unexpected,
+    VariantAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+
+use super::{Node, NodeError, Number};
+
+type Result<T> = std::result::Result<T, NodeError>;
+
+macro_rules! deserialize_number {
+    ( $fn:ident ) => {
+        fn $fn<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+            match self {
+                Node::Number(number) => visit_number(number, visitor),
+                node => Err(node.invalid_type(&visitor)),
+            }
+        }
+    };
+}
+
+impl Node {
+    fn invalid_type(&self, expected: &dyn de::Expected) -> NodeError {
+        de::Error::invalid_type(self.unexpected(), expected)
+    }
+
+    fn is_null(&self) -> bool {
+        matches!(self, Node::None | Node::Unit)
+    }
+
+    /// Everything that would become a JSON object.
+    fn is_object(&self) -> bool {
+        matches!(
+            self,
+            Node::Map(_) | Node::Struct(..) | Node::Variant { value: Some(_), .. }
+        )
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Node {
+    type Error = NodeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self {
+            Node::None | Node::Unit => visitor.visit_unit(),
+            Node::Bool(value) => visitor.visit_bool(value),
+            Node::Number(number) => visit_number(number, visitor),
+            Node::Char(value) => visitor.visit_string(value.to_string()),
+            Node::Str(value) | Node::Reference(_, value) => visitor.visit_string(value),
+            Node::Seq(values) | Node::Tuple(values) => visit_array(values, visitor),
+            Node::Variant {
+                value: None,
+                variant,
+                ..
+            } => visitor.visit_str(variant),
+            node => visit_object(node, visitor),
+        }
+    }
+
+    deserialize_number!(deserialize_i8);
+    deserialize_number!(deserialize_i16);
+    deserialize_number!(deserialize_i32);
+    deserialize_number!(deserialize_i64);
+    deserialize_number!(deserialize_u8);
+    deserialize_number!(deserialize_u16);
+    deserialize_number!(deserialize_u32);
+    deserialize_number!(deserialize_u64);
+    deserialize_number!(deserialize_f32);
+    deserialize_number!(deserialize_f64);
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        // `Some(())` is `null` as well, so like in JSON it comes back as `None`.
+        if self.is_null() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        match self {
+            Node::Str(variant) | Node::Reference(_, variant) => visitor.visit_enum(Enum {
+                variant: Key::Owned(variant),
+                value: None,
+            }),
+            Node::Char(variant) => visitor.visit_enum(Enum {
+                variant: Key::Owned(variant.to_string()),
+                value: None,
+            }),
+            Node::Variant {
+                variant,
+                value: None,
+                ..
+            } => visitor.visit_enum(Enum {
+                variant: Key::Static(variant),
+                value: None,
+            }),
+            node if node.is_object() => {
+                let mut entries = object_entries(node)?;
+                if entries.len() != 1 {
+                    return Err(de::Error::invalid_value(
+                        Unexpected::Map,
+                        &"map with a single key",
+                    ));
+                }
+                let (variant, value) = entries.remove(0);
+                visitor.visit_enum(Enum {
+                    variant,
+                    value: Some(value),
+                })
+            }
+            node => Err(de::Error::invalid_type(node.unexpected(), &"string or map")),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self {
+            Node::Bool(value) => visitor.visit_bool(value),
+            node => Err(node.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self {
+            Node::Str(value) | Node::Reference(_, value) => visitor.visit_string(value),
+            Node::Char(value) => visitor.visit_string(value.to_string()),
+            Node::Variant {
+                variant,
+                value: None,
+                ..
+            } => visitor.visit_str(variant),
+            node => Err(node.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self {
+            Node::Seq(values) | Node::Tuple(values) => visit_array(values, visitor),
+            node => node.deserialize_string(visitor),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.is_null() {
+            visitor.visit_unit()
+        } else {
+            Err(self.invalid_type(&visitor))
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self {
+            Node::Seq(values) | Node::Tuple(values) => visit_array(values, visitor),
+            node => Err(node.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.is_object() {
+            visit_object(self, visitor)
+        } else {
+            Err(self.invalid_type(&visitor))
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        match self {
+            Node::Seq(values) | Node::Tuple(values) => visit_array(values, visitor),
+            node if node.is_object() => visit_object(node, visitor),
+            node => Err(node.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128
+    }
+}
+
+pub(super) fn visit_number<'de, V: Visitor<'de>>(number: Number, visitor: V) -> Result<V::Value> {
+    match number {
+        Number::PosInt(value) => visitor.visit_u64(value),
+        Number::NegInt(value) => visitor.visit_i64(value),
+        Number::Float(value) => visitor.visit_f64(value),
+    }
+}
+
+fn visit_array<'de, V: Visitor<'de>>(values: Vec<Node>, visitor: V) -> Result<V::Value> {
+    let len = values.len();
+    let mut seq = Seq {
+        iter: values.into_iter(),
+    };
+    let value = visitor.visit_seq(&mut seq)?;
+    if seq.iter.len() == 0 {
+        Ok(value)
+    } else {
+        Err(de::Error::invalid_length(len, &"fewer elements in array"))
+    }
+}
+
+fn visit_object<'de, V: Visitor<'de>>(node: Node, visitor: V) -> Result<V::Value> {
+    let entries = object_entries(node)?;
+    let len = entries.len();
+    let mut map = Map {
+        iter: entries.into_iter(),
+        value: None,
+    };
+    let value = visitor.visit_map(&mut map)?;
+    if map.iter.len() == 0 {
+        Ok(value)
+    } else {
+        Err(de::Error::invalid_length(len, &"fewer elements in map"))
+    }
+}
+
+/// The entries of everything that would become a JSON object, keyed the way JSON would key them.
+fn object_entries(node: Node) -> Result<Vec<(Key, Node)>> {
+    match node {
+        Node::Struct(_, fields) => Ok(fields
+            .into_iter()
+            .map(|(field, value)| (Key::Static(field), value))
+            .collect()),
+        Node::Variant {
+            variant,
+            value: Some(value),
+            ..
+        } => Ok(vec![(Key::Static(variant), *value)]),
+        Node::Map(entries) => {
+            let mut object: Vec<(Key, Node)> = Vec::with_capacity(entries.len());
+            for (key, value) in entries {
+                let key = map_key(key)?;
+                // A repeated key replaces the earlier value but keeps its place.
+                match object.iter_mut().find(|(existing, _)| *existing == key) {
+                    Some(entry) => entry.1 = value,
+                    None => object.push((key, value)),
+                }
+            }
+            if json_sorts_keys() {
+                object.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+            }
+            Ok(object)
+        }
+        node => Err(node.invalid_type(&"map")),
+    }
+}
+
+/// Map keys are always strings in JSON.
+fn map_key(key: Node) -> Result<Key> {
+    match key {
+        Node::Str(key) | Node::Reference(_, key) => Ok(Key::Owned(key)),
+        Node::Char(key) => Ok(Key::Owned(key.to_string())),
+        Node::Bool(key) => Ok(Key::Owned(key.to_string())),
+        Node::Number(key) => Ok(Key::Owned(key.to_string())),
+        Node::Variant {
+            variant,
+            value: None,
+            ..
+        } => Ok(Key::Static(variant)),
+        key => Err(de::Error::custom(format!(
+            "key must be a string, got {}",
+            key.unexpected()
+        ))),
+    }
+}
+
+/// `serde_json::Map` is sorted unless some crate in the tree turns on its `preserve_order`
+/// feature, so check which one it is.
+fn json_sorts_keys() -> bool {
+    static SORTS_KEYS: OnceLock<bool> = OnceLock::new();
+    *SORTS_KEYS.get_or_init(|| {
+        let mut map = serde_json::Map::new();
+        map.insert("b".to_owned(), serde_json::Value::Null);
+        map.insert("a".to_owned(), serde_json::Value::Null);
+        map.keys().next().map(String::as_str) == Some("a")
+    })
+}
+
+#[derive(PartialEq)]
+pub(super) enum Key {
+    Static(&'static str),
+    Owned(String),
+}
+
+impl Key {
+    fn as_str(&self) -> &str {
+        match self {
+            Key::Static(key) => key,
+            Key::Owned(key) => key,
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Key {
+    type Error = NodeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self {
+            Key::Static(key) => visitor.visit_str(key),
+            Key::Owned(key) => visitor.visit_string(key),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        // Map keys cannot be null.
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let key: de::value::StringDeserializer<NodeError> = match self {
+            Key::Static(key) => key.to_owned(),
+            Key::Owned(key) => key,
+        }
+        .into_deserializer();
+        key.deserialize_enum(name, variants, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct Seq {
+    iter: std::vec::IntoIter<Node>,
+}
+
+impl<'de> SeqAccess<'de> for Seq {
+    type Error = NodeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        self.iter
+            .next()
+            .map(|value| seed.deserialize(value))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct Map {
+    iter: std::vec::IntoIter<(Key, Node)>,
+    value: Option<Node>,
+}
+
+impl<'de> MapAccess<'de> for Map {
+    type Error = NodeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(key).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<T::Value> {
+        match self.value.take() {
+            Some(value) => seed.deserialize(value),
+            None => Err(de::Error::custom("value is missing")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct Enum {
+    variant: Key,
+    value: Option<Node>,
+}
+
+impl<'de> EnumAccess<'de> for Enum {
+    type Error = NodeError;
+    type Variant = Variant;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Variant)> {
+        let variant = seed.deserialize(self.variant)?;
+        Ok((variant, Variant { value: self.value }))
+    }
+}
+
+struct Variant {
+    value: Option<Node>,
+}
+
+impl<'de> VariantAccess<'de> for Variant {
+    type Error = NodeError;
+
+    fn unit_variant(self) -> Result<()> {
+        match self.value {
+            Some(value) => de::Deserialize::deserialize(value),
+            None => Ok(()),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        match self.value {
+            Some(value) => seed.deserialize(value),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        match self.value {
+            Some(Node::Seq(values)) | Some(Node::Tuple(values)) => {
+                if values.is_empty() {
+                    visitor.visit_unit()
+                } else {
+                    visit_array(values, visitor)
+                }
+            }
+            Some(other) => Err(de::Error::invalid_type(
+                other.unexpected(),
+                &"tuple variant",
+            )),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"tuple variant",
+            )),
+        }
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        match self.value {
+            Some(value) if value.is_object() => visit_object(value, visitor),
+            Some(other) => Err(de::Error::invalid_type(
+                other.unexpected(),
+                &"struct variant",
+            )),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"struct variant",
+            )),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! An in-memory form of any serialized value.
+//!
+//! The raw writer uses it to see the names of structs and enums. `transmute` streams values
+//! through `stream` instead, and only builds a `Node` for each map.
+
+mod de;
+mod ser;
+pub(crate) mod stream;
+
+use std::fmt;
+
+use serde::{de::Unexpected, Serialize};
+
+/// Intermediate form of a serialized value that keeps everything the raw writer needs to know:
+/// struct and enum names, and the difference between a `Vec` (repeated tokens) and a tuple
+/// (the arguments of one token).
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Node {
+    None,
+    Unit,
+    Bool(bool),
+    Number(Number),
+    Char(char),
+    Str(String),
+    /// The ID in a `ReferenceTo`, with the DF type it points to.
+    Reference(&'static str, String),
+    /// A `Vec` or other sequence.
+    Seq(Vec<Node>),
+    /// A tuple or tuple struct.
+    Tuple(Vec<Node>),
+    Map(Vec<(Node, Node)>),
+    Struct(&'static str, Vec<(&'static str, Node)>),
+    Variant {
+        enum_name: &'static str,
+        variant: &'static str,
+        value: Option<Box<Node>>,
+    },
+}
+
+impl Node {
+    /// Replaces every character in the tree.
+    pub(crate) fn map_chars(&mut self, f: &impl Fn(char) -> char) {
+        match self {
+            Node::Char(value) => *value = f(*value),
+            Node::Seq(nodes) | Node::Tuple(nodes) => {
+                for node in nodes {
+                    node.map_chars(f);
+                }
+            }
+            Node::Map(entries) => {
+                for (key, value) in entries {
+                    key.map_chars(f);
+                    value.map_chars(f);
+                }
+            }
+            Node::Struct(_, fields) => {
+                for (_, value) in fields {
+                    value.map_chars(f);
+                }
+            }
+            Node::Variant {
+                value: Some(value), ..
+            } => value.map_chars(f),
+            _ => {}
+        }
+    }
+
+    fn unexpected(&self) -> Unexpected<'_> {
+        match self {
+            Node::None | Node::Unit => Unexpected::Unit,
+            Node::Bool(value) => Unexpected::Bool(*value),
+            Node::Number(Number::PosInt(value)) => Unexpected::Unsigned(*value),
+            Node::Number(Number::NegInt(value)) => Unexpected::Signed(*value),
+            Node::Number(Number::Float(value)) => Unexpected::Float(*value),
+            Node::Char(value) => Unexpected::Char(*value),
+            Node::Str(value) | Node::Reference(_, value) => Unexpected::Str(value),
+            Node::Seq(_) | Node::Tuple(_) => Unexpected::Seq,
+            Node::Map(_) | Node::Struct(..) => Unexpected::Map,
+            Node::Variant { value: None, .. } => Unexpected::UnitVariant,
+            Node::Variant { value: Some(_), .. } => Unexpected::Map,
+        }
+    }
+}
+
+/// Numbers are stored the way `serde_json` stores them, so both paths see the same values.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Number {
+    PosInt(u64),
+    NegInt(i64),
+    Float(f64),
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::PosInt(value) => value.fmt(f),
+            Number::NegInt(value) => value.fmt(f),
+            Number::Float(value) => value.fmt(f),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct NodeError(String);
+
+impl fmt::Display for NodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for NodeError {}
+
+impl serde::ser::Error for NodeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        NodeError(msg.to_string())
+    }
+}
+
+impl serde::de::Error for NodeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        NodeError(msg.to_string())
+    }
+}
+
+/// `ReferenceTo` serializes as a newtype struct named after the DF type it points to, like
+/// `CREATURE`. Rust type names are never all uppercase, so those names are easy to tell apart.
+fn is_reference_type(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'_')
+}
+
+pub(crate) fn to_node<T: Serialize + ?Sized>(value: &T) -> Result<Node, NodeError> {
+    value.serialize(ser::NodeSerializer)
+}
@@ -0,0 +1,321 @@
+use serde::ser::{self, Serialize};
+
+use super::{is_reference_type, to_node, Node, NodeError, Number};
+
+pub(crate) struct NodeSerializer;
+
+macro_rules! serialize_signed {
+    ( $fn:ident, $x:ty ) => {
+        fn $fn(self, v: $x) -> Result<Node, NodeError> {
+            let v = i64::from(v);
+            Ok(Node::Number(if v < 0 {
+                Number::NegInt(v)
+            } else {
+                Number::PosInt(v as u64)
+            }))
+        }
+    };
+}
+
+macro_rules! serialize_unsigned {
+    ( $fn:ident, $x:ty ) => {
+        fn $fn(self, v: $x) -> Result<Node, NodeError> {
+            Ok(Node::Number(Number::PosInt(u64::from(v))))
+        }
+    };
+}
+
+impl ser::Serializer for NodeSerializer {
+    type Ok = Node;
+    type Error = NodeError;
+    type SerializeSeq = SeqBuilder;
+    type SerializeTuple = SeqBuilder;
+    type SerializeTupleStruct = SeqBuilder;
+    type SerializeTupleVariant = VariantBuilder<SeqBuilder>;
+    type SerializeMap = MapBuilder;
+    type SerializeStruct = StructBuilder;
+    type SerializeStructVariant = VariantBuilder<StructBuilder>;
+
+    fn serialize_bool(self, v: bool) -> Result<Node, NodeError> {
+        Ok(Node::Bool(v))
+    }
+    serialize_signed!(serialize_i8, i8);
+    serialize_signed!(serialize_i16, i16);
+    serialize_signed!(serialize_i32, i32);
+    serialize_signed!(serialize_i64, i64);
+    serialize_unsigned!(serialize_u8, u8);
+    serialize_unsigned!(serialize_u16, u16);
+    serialize_unsigned!(serialize_u32, u32);
+    serialize_unsigned!(serialize_u64, u64);
+
+    fn serialize_f32(self, v: f32) -> Result<Node, NodeError> {
+        self.serialize_f64(f64::from(v))
+    }
+    fn serialize_f64(self, v: f64) -> Result<Node, NodeError> {
+        // Like `serde_json`, there is no representation for NaN and infinity.
+        if v.is_finite() {
+            Ok(Node::Number(Number::Float(v)))
+        } else {
+            Ok(Node::None)
+        }
+    }
+
+    fn serialize_char(self, v: char) -> Result<Node, NodeError> {
+        Ok(Node::Char(v))
+    }
+    fn serialize_str(self, v: &str) -> Result<Node, NodeError> {
+        Ok(Node::Str(v.to_owned()))
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<Node, NodeError> {
+        Ok(Node::Seq(
+            v.iter()
+                .map(|b| Node::Number(Number::PosInt(u64::from(*b))))
+                .collect(),
+        ))
+    }
+    fn serialize_none(self) -> Result<Node, NodeError> {
+        Ok(Node::None)
+    }
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Node, NodeError> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Node, NodeError> {
+        Ok(Node::Unit)
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Node, NodeError> {
+        Ok(Node::Unit)
+    }
+    fn serialize_unit_variant(
+        self,
+        enum_name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Node, NodeError> {
+        Ok(Node::Variant {
+            enum_name,
+            variant,
+            value: None,
+        })
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Node, NodeError> {
+        match value.serialize(self)? {
+            Node::Str(id) if is_reference_type(name) => Ok(Node::Reference(name, id)),
+            node => Ok(node),
+        }
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        enum_name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Node, NodeError> {
+        Ok(Node::Variant {
+            enum_name,
+            variant,
+            value: Some(Box::new(value.serialize(self)?)),
+        })
+    }
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqBuilder, NodeError> {
+        Ok(SeqBuilder {
+            items: Vec::with_capacity(len.unwrap_or_default()),
+            tuple: false,
+        })
+    }
+    fn serialize_tuple(self, len: usize) -> Result<SeqBuilder, NodeError> {
+        Ok(SeqBuilder {
+            items: Vec::with_capacity(len),
+            tuple: true,
+        })
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SeqBuilder, NodeError> {
+        self.serialize_tuple(len)
+    }
+    fn serialize_tuple_variant(
+        self,
+        enum_name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<VariantBuilder<SeqBuilder>, NodeError> {
+        Ok(VariantBuilder {
+            enum_name,
+            variant,
+            inner: self.serialize_tuple(len)?,
+        })
+    }
+    fn serialize_map(self, len: Option<usize>) -> Result<MapBuilder, NodeError> {
+        Ok(MapBuilder {
+            entries: Vec::with_capacity(len.unwrap_or_default()),
+            key: None,
+        })
+    }
+    fn serialize_struct(self, name: &'static str, len: usize) -> Result<StructBuilder, NodeError> {
+        Ok(StructBuilder {
+            name,
+            fields: Vec::with_capacity(len),
+        })
+    }
+    fn serialize_struct_variant(
+        self,
+        enum_name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<VariantBuilder<StructBuilder>, NodeError> {
+        Ok(VariantBuilder {
+            enum_name,
+            variant,
+            inner: self.serialize_struct(variant, len)?,
+        })
+    }
+}
+
+pub(crate) struct SeqBuilder {
+    items: Vec<Node>,
+    tuple: bool,
+}
+
+impl SeqBuilder {
+    fn finish(self) -> Node {
+        if self.tuple {
+            Node::Tuple(self.items)
+        } else {
+            Node::Seq(self.items)
+        }
+    }
+}
+
+impl ser::SerializeSeq for SeqBuilder {
+    type Ok = Node;
+    type Error = NodeError;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NodeError> {
+        self.items.push(to_node(value)?);
+        Ok(())
+    }
+    fn end(self) -> Result<Node, NodeError> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTuple for SeqBuilder {
+    type Ok = Node;
+    type Error = NodeError;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NodeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<Node, NodeError> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqBuilder {
+    type Ok = Node;
+    type Error = NodeError;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NodeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<Node, NodeError> {
+        Ok(self.finish())
+    }
+}
+
+pub(crate) struct MapBuilder {
+    entries: Vec<(Node, Node)>,
+    key: Option<Node>,
+}
+
+impl ser::SerializeMap for MapBuilder {
+    type Ok = Node;
+    type Error = NodeError;
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), NodeError> {
+        self.key = Some(to_node(key)?);
+        Ok(())
+    }
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NodeError> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| NodeError("map value without a key".to_owned()))?;
+        self.entries.push((key, to_node(value)?));
+        Ok(())
+    }
+    fn end(self) -> Result<Node, NodeError> {
+        Ok(Node::Map(self.entries))
+    }
+}
+
+pub(crate) struct StructBuilder {
+    name: &'static str,
+    fields: Vec<(&'static str, Node)>,
+}
+
+impl ser::SerializeStruct for StructBuilder {
+    type Ok = Node;
+    type Error = NodeError;
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), NodeError> {
+        self.fields.push((key, to_node(value)?));
+        Ok(())
+    }
+    fn end(self) -> Result<Node, NodeError> {
+        Ok(Node::Struct(self.name, self.fields))
+    }
+}
+
+pub(crate) struct VariantBuilder<B> {
+    enum_name: &'static str,
+    variant: &'static str,
+    inner: B,
+}
+
+impl VariantBuilder<SeqBuilder> {
+    fn finish(self) -> Node {
+        Node::Variant {
+            enum_name: self.enum_name,
+            variant: self.variant,
+            value: Some(Box::new(self.inner.finish())),
+        }
+    }
+}
+
+impl ser::SerializeTupleVariant for VariantBuilder<SeqBuilder> {
+    type Ok = Node;
+    type Error = NodeError;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NodeError> {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+    fn end(self) -> Result<Node, NodeError> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeStructVariant for VariantBuilder<StructBuilder> {
+    type Ok = Node;
+    type Error = NodeError;
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), NodeError> {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+    fn end(self) -> Result<Node, NodeError> {
+        Ok(Node::Variant {
+            enum_name: self.enum_name,
+            variant: self.variant,
+            value: Some(Box::new(Node::Struct(self.inner.name, self.inner.fields))),
+        })
+    }
+}
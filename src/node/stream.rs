@@ -0,0 +1,986 @@
+//! Converts a value by serializing it on a second thread and deserializing what that thread
+//! sends as it arrives, so the value is never held in between as a whole.
+//!
+//! `Serialize` pushes a value into a serializer while `Deserialize` pulls one out of a
+//! deserializer, so one can only drive the other from a thread of its own. The value travels as
+//! [`Event`]s, sent in batches so the threads don't wait on each other for every event, through
+//! a channel that holds only a few batches.
+//!
+//! Events are read the way `de` reads a `Node`, so the result is the same as going through a
+//! `serde_json::Value`. Maps are the exception to streaming: JSON keys them by string, sorts
+//! them and keeps the last of repeated keys, so each map is collected into a `Node` and read
+//! from there.
+
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::thread;
+
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, Unexpected,
+    VariantAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+use serde::ser::{self, Serialize};
+
+use super::de::{visit_number, Key};
+use super::{to_node, Node, NodeError, Number};
+
+type Result<T> = std::result::Result<T, NodeError>;
+
+/// Events sent at once.
+const BATCH_LEN: usize = 1024;
+/// Batches the channel holds before the serializing thread waits.
+const BATCHES: usize = 4;
+
+/// One step of a serialized value.
+enum Event {
+    /// `None`, `()` or a unit struct, which are all `null` in JSON.
+    Null,
+    Bool(bool),
+    Number(Number),
+    Char(char),
+    Str(String),
+    UnitVariant(&'static str),
+    /// A variant with a value, which follows.
+    Variant(&'static str),
+    SeqStart,
+    SeqEnd,
+    StructStart,
+    /// A struct field, whose value follows.
+    Field(&'static str),
+    StructEnd,
+    /// A whole map.
+    Map(Node),
+    /// `Serialize` failed.
+    Error(String),
+}
+
+impl Event {
+    fn unexpected(&self) -> Unexpected<'_> {
+        match self {
+            Event::Null => Unexpected::Unit,
+            Event::Bool(value) => Unexpected::Bool(*value),
+            Event::Number(Number::PosInt(value)) => Unexpected::Unsigned(*value),
+            Event::Number(Number::NegInt(value)) => Unexpected::Signed(*value),
+            Event::Number(Number::Float(value)) => Unexpected::Float(*value),
+            Event::Char(value) => Unexpected::Char(*value),
+            Event::Str(value) => Unexpected::Str(value),
+            Event::UnitVariant(_) => Unexpected::UnitVariant,
+            Event::SeqStart => Unexpected::Seq,
+            Event::StructStart | Event::Variant(_) => Unexpected::Map,
+            Event::Map(node) => node.unexpected(),
+            Event::SeqEnd | Event::Field(_) | Event::StructEnd | Event::Error(_) => {
+                Unexpected::Other("the end of a value")
+            }
+        }
+    }
+
+    fn invalid_type(&self, expected: &dyn de::Expected) -> NodeError {
+        de::Error::invalid_type(self.unexpected(), expected)
+    }
+}
+
+/// Converts `from` into an `O`, passing every `char` through `map_char` on the way.
+pub(crate) fn transmute<I, O>(from: &I, map_char: fn(char) -> char) -> Result<O>
+where
+    I: Serialize + Sync + ?Sized,
+    O: DeserializeOwned,
+{
+    let (sender, receiver) = mpsc::sync_channel(BATCHES);
+    thread::scope(|scope| {
+        let serializing = scope.spawn(move || {
+            let mut events = EventSerializer {
+                sender,
+                batch: Vec::with_capacity(BATCH_LEN),
+                map_char,
+            };
+            if let Err(error) = from.serialize(&mut events) {
+                events.batch.push(Event::Error(error.0));
+            }
+            // Fails only when the deserializing side is done already.
+            let _ = events.flush();
+        });
+        let mut events = EventDeserializer {
+            receiver,
+            batch: Vec::new().into_iter(),
+            peeked: None,
+        };
+        let value = O::deserialize(&mut events).and_then(|value| events.end().map(|()| value));
+        // Lets the serializing thread stop at its next batch if the value wasn't read to the end.
+        drop(events);
+        if let Err(panic) = serializing.join() {
+            std::panic::resume_unwind(panic);
+        }
+        value
+    })
+}
+
+struct EventSerializer {
+    sender: SyncSender<Vec<Event>>,
+    batch: Vec<Event>,
+    map_char: fn(char) -> char,
+}
+
+impl EventSerializer {
+    fn push(&mut self, event: Event) -> Result<()> {
+        self.batch.push(event);
+        if self.batch.len() == BATCH_LEN {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let batch = std::mem::replace(&mut self.batch, Vec::with_capacity(BATCH_LEN));
+        self.sender
+            .send(batch)
+            .map_err(|_| NodeError("the value is no longer being read".to_owned()))
+    }
+}
+
+macro_rules! serialize_signed {
+    ( $fn:ident, $x:ty ) => {
+        fn $fn(self, v: $x) -> Result<()> {
+            let v = i64::from(v);
+            self.push(Event::Number(if v < 0 {
+                Number::NegInt(v)
+            } else {
+                Number::PosInt(v as u64)
+            }))
+        }
+    };
+}
+
+macro_rules! serialize_unsigned {
+    ( $fn:ident, $x:ty ) => {
+        fn $fn(self, v: $x) -> Result<()> {
+            self.push(Event::Number(Number::PosInt(u64::from(v))))
+        }
+    };
+}
+
+impl<'a> ser::Serializer for &'a mut EventSerializer {
+    type Ok = ();
+    type Error = NodeError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = MapCollector<'a>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.push(Event::Bool(v))
+    }
+    serialize_signed!(serialize_i8, i8);
+    serialize_signed!(serialize_i16, i16);
+    serialize_signed!(serialize_i32, i32);
+    serialize_signed!(serialize_i64, i64);
+    serialize_unsigned!(serialize_u8, u8);
+    serialize_unsigned!(serialize_u16, u16);
+    serialize_unsigned!(serialize_u32, u32);
+    serialize_unsigned!(serialize_u64, u64);
+
+    fn serialize_f32(self, v: f32) -> Result<()> {
+        self.serialize_f64(f64::from(v))
+    }
+    fn serialize_f64(self, v: f64) -> Result<()> {
+        // Like `serde_json`, there is no representation for NaN and infinity.
+        if v.is_finite() {
+            self.push(Event::Number(Number::Float(v)))
+        } else {
+            self.push(Event::Null)
+        }
+    }
+
+    fn serialize_char(self, v: char) -> Result<()> {
+        let v = (self.map_char)(v);
+        self.push(Event::Char(v))
+    }
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.push(Event::Str(v.to_owned()))
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        self.push(Event::SeqStart)?;
+        for b in v {
+            self.push(Event::Number(Number::PosInt(u64::from(*b))))?;
+        }
+        self.push(Event::SeqEnd)
+    }
+    fn serialize_none(self) -> Result<()> {
+        self.push(Event::Null)
+    }
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<()> {
+        self.push(Event::Null)
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        self.push(Event::Null)
+    }
+    fn serialize_unit_variant(
+        self,
+        _enum_name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<()> {
+        self.push(Event::UnitVariant(variant))
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _enum_name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.push(Event::Variant(variant))?;
+        value.serialize(self)
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self> {
+        self.push(Event::SeqStart)?;
+        Ok(self)
+    }
+    fn serialize_tuple(self, len: usize) -> Result<Self> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Self> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _enum_name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self> {
+        self.push(Event::Variant(variant))?;
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_map(self, len: Option<usize>) -> Result<MapCollector<'a>> {
+        Ok(MapCollector {
+            events: self,
+            entries: Vec::with_capacity(len.unwrap_or_default()),
+            key: None,
+        })
+    }
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        self.push(Event::StructStart)?;
+        Ok(self)
+    }
+    fn serialize_struct_variant(
+        self,
+        _enum_name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self> {
+        self.push(Event::Variant(variant))?;
+        self.serialize_struct(variant, len)
+    }
+}
+
+impl ser::SerializeSeq for &mut EventSerializer {
+    type Ok = ();
+    type Error = NodeError;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+    fn end(self) -> Result<()> {
+        self.push(Event::SeqEnd)
+    }
+}
+
+impl ser::SerializeTuple for &mut EventSerializer {
+    type Ok = ();
+    type Error = NodeError;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+    fn end(self) -> Result<()> {
+        self.push(Event::SeqEnd)
+    }
+}
+
+impl ser::SerializeTupleStruct for &mut EventSerializer {
+    type Ok = ();
+    type Error = NodeError;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+    fn end(self) -> Result<()> {
+        self.push(Event::SeqEnd)
+    }
+}
+
+impl ser::SerializeTupleVariant for &mut EventSerializer {
+    type Ok = ();
+    type Error = NodeError;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+    fn end(self) -> Result<()> {
+        self.push(Event::SeqEnd)
+    }
+}
+
+impl ser::SerializeStruct for &mut EventSerializer {
+    type Ok = ();
+    type Error = NodeError;
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.push(Event::Field(key))?;
+        value.serialize(&mut **self)
+    }
+    fn end(self) -> Result<()> {
+        self.push(Event::StructEnd)
+    }
+}
+
+impl ser::SerializeStructVariant for &mut EventSerializer {
+    type Ok = ();
+    type Error = NodeError;
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        ser::SerializeStruct::serialize_field(self, key, value)
+    }
+    fn end(self) -> Result<()> {
+        self.push(Event::StructEnd)
+    }
+}
+
+pub(crate) struct MapCollector<'a> {
+    events: &'a mut EventSerializer,
+    entries: Vec<(Node, Node)>,
+    key: Option<Node>,
+}
+
+impl ser::SerializeMap for MapCollector<'_> {
+    type Ok = ();
+    type Error = NodeError;
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.key = Some(to_node(key)?);
+        Ok(())
+    }
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| NodeError("map value without a key".to_owned()))?;
+        self.entries.push((key, to_node(value)?));
+        Ok(())
+    }
+    fn end(self) -> Result<()> {
+        let mut map = Node::Map(self.entries);
+        map.map_chars(&self.events.map_char);
+        self.events.push(Event::Map(map))
+    }
+}
+
+struct EventDeserializer {
+    receiver: Receiver<Vec<Event>>,
+    batch: std::vec::IntoIter<Event>,
+    peeked: Option<Event>,
+}
+
+impl EventDeserializer {
+    fn next(&mut self) -> Result<Event> {
+        let event = match self.peeked.take() {
+            Some(event) => event,
+            None => loop {
+                if let Some(event) = self.batch.next() {
+                    break event;
+                }
+                self.batch = self
+                    .receiver
+                    .recv()
+                    .map_err(|_| NodeError("the value ended early".to_owned()))?
+                    .into_iter();
+            },
+        };
+        match event {
+            Event::Error(message) => Err(NodeError(message)),
+            event => Ok(event),
+        }
+    }
+
+    fn peek(&mut self) -> Result<&Event> {
+        let event = match self.peeked.take() {
+            Some(event) => event,
+            None => self.next()?,
+        };
+        Ok(self.peeked.insert(event))
+    }
+
+    /// Checks that nothing follows the value.
+    fn end(&mut self) -> Result<()> {
+        if self.peeked.is_some() || self.batch.len() > 0 || self.receiver.iter().next().is_some() {
+            return Err(de::Error::custom("trailing events after the value"));
+        }
+        Ok(())
+    }
+
+    /// Reads past one value.
+    fn skip(&mut self) -> Result<()> {
+        match self.next()? {
+            Event::SeqStart => self.skip_elements().map(|_| ()),
+            Event::StructStart => self.skip_fields().map(|_| ()),
+            Event::Variant(_) => self.skip(),
+            event @ (Event::SeqEnd | Event::Field(_) | Event::StructEnd) => {
+                Err(event.invalid_type(&"a value"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Reads past the rest of a sequence and its end, counting the elements.
+    fn skip_elements(&mut self) -> Result<usize> {
+        let mut len = 0;
+        while !matches!(self.peek()?, Event::SeqEnd) {
+            self.skip()?;
+            len += 1;
+        }
+        self.next()?;
+        Ok(len)
+    }
+
+    /// Reads past the rest of a struct and its end, counting the fields.
+    fn skip_fields(&mut self) -> Result<usize> {
+        let mut len = 0;
+        loop {
+            match self.next()? {
+                Event::Field(_) => self.skip()?,
+                Event::StructEnd => return Ok(len),
+                event => return Err(event.invalid_type(&"a struct field")),
+            }
+            len += 1;
+        }
+    }
+
+    /// Reads a sequence whose start has been read.
+    fn visit_seq<'de, V: Visitor<'de>>(&mut self, visitor: V) -> Result<V::Value> {
+        let mut seq = Seq {
+            de: self,
+            len: 0,
+            ended: false,
+        };
+        let value = visitor.visit_seq(&mut seq)?;
+        let (read, ended) = (seq.len, seq.ended);
+        if ended {
+            return Ok(value);
+        }
+        let len = read + self.skip_elements()?;
+        if len == read {
+            Ok(value)
+        } else {
+            Err(de::Error::invalid_length(len, &"fewer elements in array"))
+        }
+    }
+
+    /// Reads everything that would become a JSON object, given the event it starts with.
+    fn visit_object<'de, V: Visitor<'de>>(&mut self, start: Event, visitor: V) -> Result<V::Value> {
+        let mut object = Object {
+            de: self,
+            variant: match start {
+                Event::Variant(variant) => Some(variant),
+                _ => None,
+            },
+            len: 0,
+            pending_value: false,
+            ended: false,
+        };
+        let value = visitor.visit_map(&mut object)?;
+        if object.ended {
+            return Ok(value);
+        }
+        let (mut len, is_variant) = (object.len, object.variant.is_some());
+        if object.pending_value {
+            self.skip()?;
+        }
+        if is_variant {
+            // The variant itself was never asked for, so its value is still to come.
+            if len == 0 {
+                self.skip()?;
+                return Err(de::Error::invalid_length(1, &"fewer elements in map"));
+            }
+            return Ok(value);
+        }
+        let read = len;
+        len += self.skip_fields()?;
+        if len == read {
+            Ok(value)
+        } else {
+            Err(de::Error::invalid_length(len, &"fewer elements in map"))
+        }
+    }
+}
+
+macro_rules! deserialize_number {
+    ( $fn:ident ) => {
+        fn $fn<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+            match self.next()? {
+                Event::Number(number) => visit_number(number, visitor),
+                event => Err(event.invalid_type(&visitor)),
+            }
+        }
+    };
+}
+
+impl<'de> de::Deserializer<'de> for &mut EventDeserializer {
+    type Error = NodeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.next()? {
+            Event::Null => visitor.visit_unit(),
+            Event::Bool(value) => visitor.visit_bool(value),
+            Event::Number(number) => visit_number(number, visitor),
+            Event::Char(value) => visitor.visit_string(value.to_string()),
+            Event::Str(value) => visitor.visit_string(value),
+            Event::UnitVariant(variant) => visitor.visit_str(variant),
+            Event::SeqStart => self.visit_seq(visitor),
+            event @ (Event::StructStart | Event::Variant(_)) => self.visit_object(event, visitor),
+            Event::Map(node) => node.deserialize_any(visitor),
+            event => Err(event.invalid_type(&visitor)),
+        }
+    }
+
+    deserialize_number!(deserialize_i8);
+    deserialize_number!(deserialize_i16);
+    deserialize_number!(deserialize_i32);
+    deserialize_number!(deserialize_i64);
+    deserialize_number!(deserialize_u8);
+    deserialize_number!(deserialize_u16);
+    deserialize_number!(deserialize_u32);
+    deserialize_number!(deserialize_u64);
+    deserialize_number!(deserialize_f32);
+    deserialize_number!(deserialize_f64);
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        // `Some(())` is `null` as well, so like in JSON it comes back as `None`.
+        if matches!(self.peek()?, Event::Null) {
+            self.next()?;
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let (variant, has_value) = match self.next()? {
+            Event::Str(variant) => (Key::Owned(variant), false),
+            Event::Char(variant) => (Key::Owned(variant.to_string()), false),
+            Event::UnitVariant(variant) => (Key::Static(variant), false),
+            Event::Variant(variant) => (Key::Static(variant), true),
+            Event::StructStart => {
+                let variant = match self.next()? {
+                    Event::Field(field) => field,
+                    _ => return Err(single_key()),
+                };
+                let value = visitor.visit_enum(Enum {
+                    de: &mut *self,
+                    variant: Key::Static(variant),
+                    has_value: true,
+                })?;
+                return match self.next()? {
+                    Event::StructEnd => Ok(value),
+                    _ => Err(single_key()),
+                };
+            }
+            Event::Map(node) => return node.deserialize_enum(name, variants, visitor),
+            event => return Err(event.invalid_type(&"string or map")),
+        };
+        visitor.visit_enum(Enum {
+            de: self,
+            variant,
+            has_value,
+        })
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.next()? {
+            Event::Bool(value) => visitor.visit_bool(value),
+            event => Err(event.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.next()? {
+            Event::Str(value) => visitor.visit_string(value),
+            Event::Char(value) => visitor.visit_string(value.to_string()),
+            Event::UnitVariant(variant) => visitor.visit_str(variant),
+            Event::Map(node) => node.deserialize_string(visitor),
+            event => Err(event.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if matches!(self.peek()?, Event::SeqStart) {
+            self.next()?;
+            self.visit_seq(visitor)
+        } else {
+            self.deserialize_string(visitor)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.next()? {
+            Event::Null => visitor.visit_unit(),
+            event => Err(event.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.next()? {
+            Event::SeqStart => self.visit_seq(visitor),
+            event => Err(event.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.next()? {
+            event @ (Event::StructStart | Event::Variant(_)) => self.visit_object(event, visitor),
+            Event::Map(node) => node.deserialize_map(visitor),
+            event => Err(event.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        match self.next()? {
+            Event::SeqStart => self.visit_seq(visitor),
+            event @ (Event::StructStart | Event::Variant(_)) => self.visit_object(event, visitor),
+            Event::Map(node) => node.deserialize_struct(name, fields, visitor),
+            event => Err(event.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.skip()?;
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128
+    }
+}
+
+fn single_key() -> NodeError {
+    de::Error::invalid_value(Unexpected::Map, &"map with a single key")
+}
+
+struct Seq<'a> {
+    de: &'a mut EventDeserializer,
+    len: usize,
+    ended: bool,
+}
+
+impl<'de> SeqAccess<'de> for Seq<'_> {
+    type Error = NodeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if self.ended {
+            return Ok(None);
+        }
+        if matches!(self.de.peek()?, Event::SeqEnd) {
+            self.de.next()?;
+            self.ended = true;
+            return Ok(None);
+        }
+        self.len += 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+}
+
+/// A struct, or a variant with a value as an object with a single key.
+struct Object<'a> {
+    de: &'a mut EventDeserializer,
+    variant: Option<&'static str>,
+    len: usize,
+    pending_value: bool,
+    ended: bool,
+}
+
+impl<'de> MapAccess<'de> for Object<'_> {
+    type Error = NodeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if self.ended {
+            return Ok(None);
+        }
+        if self.pending_value {
+            self.de.skip()?;
+            self.pending_value = false;
+        }
+        let key = match self.variant {
+            Some(_) if self.len > 0 => None,
+            Some(variant) => Some(variant),
+            None => match self.de.next()? {
+                Event::Field(field) => Some(field),
+                Event::StructEnd => None,
+                event => return Err(event.invalid_type(&"a struct field")),
+            },
+        };
+        match key {
+            Some(key) => {
+                self.len += 1;
+                self.pending_value = true;
+                seed.deserialize(Key::Static(key)).map(Some)
+            }
+            None => {
+                self.ended = true;
+                Ok(None)
+            }
+        }
+    }
+
+    fn next_value_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<T::Value> {
+        if !self.pending_value {
+            return Err(de::Error::custom("value is missing"));
+        }
+        self.pending_value = false;
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+struct Enum<'a> {
+    de: &'a mut EventDeserializer,
+    variant: Key,
+    has_value: bool,
+}
+
+impl<'de, 'a> EnumAccess<'de> for Enum<'a> {
+    type Error = NodeError;
+    type Variant = Variant<'a>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Variant<'a>)> {
+        let variant = seed.deserialize(self.variant)?;
+        Ok((
+            variant,
+            Variant {
+                de: self.de,
+                has_value: self.has_value,
+            },
+        ))
+    }
+}
+
+struct Variant<'a> {
+    de: &'a mut EventDeserializer,
+    has_value: bool,
+}
+
+impl<'de> VariantAccess<'de> for Variant<'_> {
+    type Error = NodeError;
+
+    fn unit_variant(self) -> Result<()> {
+        if self.has_value {
+            de::Deserialize::deserialize(self.de)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        if self.has_value {
+            seed.deserialize(self.de)
+        } else {
+            Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"newtype variant",
+            ))
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        if !self.has_value {
+            return Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"tuple variant",
+            ));
+        }
+        match self.de.next()? {
+            Event::SeqStart if matches!(self.de.peek()?, Event::SeqEnd) => {
+                self.de.next()?;
+                visitor.visit_unit()
+            }
+            Event::SeqStart => self.de.visit_seq(visitor),
+            event => Err(event.invalid_type(&"tuple variant")),
+        }
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        if !self.has_value {
+            return Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"struct variant",
+            ));
+        }
+        match self.de.next()? {
+            event @ (Event::StructStart | Event::Variant(_)) => {
+                self.de.visit_object(event, visitor)
+            }
+            Event::Map(node) => de::Deserializer::deserialize_map(node, visitor),
+            event => Err(event.invalid_type(&"struct variant")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Shape {
+        Empty,
+        Tile(char),
+        Pair(u8, i32),
+        Area { width: f64, height: Option<()> },
+        Nothing(),
+    }
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(untagged)]
+    enum Either {
+        Number(i64),
+        Shape(Shape),
+        Text(String),
+    }
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Everything {
+        shapes: Vec<Shape>,
+        either: Vec<Either>,
+        names: HashMap<u8, String>,
+        tuple: (bool, Option<u16>, ()),
+        #[serde(skip_serializing)]
+        skipped: Option<String>,
+        nan: f32,
+    }
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Partial {
+        tuple: (bool, Option<u16>, ()),
+    }
+    fn everything() -> Everything {
+        Everything {
+            shapes: vec![
+                Shape::Empty,
+                Shape::Tile('é'),
+                Shape::Pair(1, -2),
+                Shape::Area {
+                    width: 0.5,
+                    height: Some(()),
+                },
+            ],
+            either: vec![
+                Either::Number(-3),
+                Either::Shape(Shape::Pair(4, 5)),
+                Either::Shape(Shape::Empty),
+                Either::Text("text".to_owned()),
+            ],
+            names: HashMap::from([(1, "one".to_owned()), (20, "twenty".to_owned())]),
+            tuple: (true, None, ()),
+            skipped: None,
+            nan: f32::NAN,
+        }
+    }
+    #[test]
+    fn matches_json() {
+        let json: serde_json::Value = serde_transmute(everything()).unwrap();
+        let streamed: serde_json::Value = transmute(everything()).unwrap();
+        assert_eq!(streamed, json);
+        let partial: Partial = transmute(everything()).unwrap();
+        assert_eq!(
+            partial,
+            serde_transmute::<_, Partial>(everything()).unwrap()
+        );
+        let shapes: Vec<Shape> = transmute(everything().shapes).unwrap();
+        assert_eq!(
+            shapes,
+            serde_transmute::<_, Vec<Shape>>(everything().shapes).unwrap()
+        );
+        assert!(transmute::<_, Vec<Shape>>(everything().either).is_err());
+        // Like `serde_json`, an empty tuple variant comes back as a unit.
+        assert!(serde_transmute::<_, Shape>(Shape::Nothing()).is_err());
+        assert!(transmute::<_, Shape>(Shape::Nothing()).is_err());
+        assert!(transmute::<_, (bool, Option<u16>)>(everything().tuple).is_err());
+    }
+    #[test]
+    fn map_chars() {
+        let shapes = vec![Shape::Tile('a'), Shape::Tile('b')];
+        let mapped: Vec<Shape> = super::transmute(&shapes, |c| c.to_ascii_uppercase()).unwrap();
+        assert_eq!(mapped, vec![Shape::Tile('A'), Shape::Tile('B')]);
+    }
+}
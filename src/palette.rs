@@ -0,0 +1,217 @@
+//! The 16 colors DF draws with, as in `data/Colors/default.txt`, and the conversions between
+//! `(fg, bg, bright)` as `DISPLAY_COLOR` takes it, color names like `lred` and RGB.
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use crate::ColorToken;
+
+/// A color as red, green and blue.
+pub type Rgb = (u8, u8, u8);
+
+/// The colors in the order of the palette, so `lred` is color 4 bright.
+pub const COLOR_NAMES: [&str; 16] = [
+    "black", "blue", "green", "cyan", "red", "magenta", "brown", "lgray", "dgray", "lblue",
+    "lgreen", "lcyan", "lred", "lmagenta", "yellow", "white",
+];
+
+/// The RGB values of the 16 colors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Palette {
+    colors: [Rgb; 16],
+}
+
+impl Default for Palette {
+    /// The palette of `data/Colors/default.txt`.
+    fn default() -> Self {
+        Self::parse(include_str!("../data/Colors/default.txt")).expect("`default.txt` is valid")
+    }
+}
+
+impl Palette {
+    /// A palette from its colors, in the order of [`COLOR_NAMES`].
+    pub fn new(colors: [Rgb; 16]) -> Self {
+        Self { colors }
+    }
+
+    /// Reads a palette written like `black_r: 0` or `[BLACK_R:0]`, for every channel of every
+    /// color.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut channels = [[None; 3]; 16];
+        for line in text.trim_start_matches('\u{feff}').lines() {
+            let line = line.trim();
+            let line = match line
+                .strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+            {
+                Some(token) => token,
+                None => line,
+            };
+            let (key, value) = match line.split_once(':') {
+                Some((key, value)) => (key.trim().to_ascii_lowercase(), value.trim()),
+                None => continue,
+            };
+            let (name, channel) = match key.rsplit_once('_') {
+                Some((name, channel)) => (name, channel),
+                None => continue,
+            };
+            let index = COLOR_NAMES.iter().position(|color| *color == name);
+            let channel = ["r", "g", "b"].iter().position(|other| *other == channel);
+            if let (Some(index), Some(channel)) = (index, channel) {
+                let value = value
+                    .parse()
+                    .with_context(|| format!("`{}`: expected a value from 0 to 255", key))?;
+                channels[index][channel] = Some(value);
+            }
+        }
+        let mut colors = [(0, 0, 0); 16];
+        for (index, (color, channels)) in colors.iter_mut().zip(channels).enumerate() {
+            *color = match channels {
+                [Some(r), Some(g), Some(b)] => (r, g, b),
+                _ => bail!("no RGB value for `{}`", COLOR_NAMES[index]),
+            };
+        }
+        Ok(Self::new(colors))
+    }
+
+    /// Reads a palette file, like `data/Colors/alternate.txt`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("in {}", path.display()))
+    }
+
+    /// The colors, in the order of [`COLOR_NAMES`].
+    pub fn colors(&self) -> &[Rgb; 16] {
+        &self.colors
+    }
+
+    /// The RGB value of a named color.
+    pub fn rgb(&self, name: &str) -> Option<Rgb> {
+        let index = COLOR_NAMES.iter().position(|color| *color == name)?;
+        Some(self.colors[index])
+    }
+
+    /// The RGB value of the foreground of `(fg, bg, bright)`.
+    pub fn foreground(&self, (fg, _, bright): (u8, u8, u8)) -> Option<Rgb> {
+        if fg > 7 || bright > 1 {
+            return None;
+        }
+        Some(self.colors[(fg + bright * 8) as usize])
+    }
+
+    /// The RGB value of the background of `(fg, bg, bright)`, which is never bright.
+    pub fn background(&self, (_, bg, _): (u8, u8, u8)) -> Option<Rgb> {
+        if bg > 7 {
+            return None;
+        }
+        Some(self.colors[bg as usize])
+    }
+
+    /// The color of the palette nearest to an RGB value, as `(fg, 0, bright)`.
+    pub fn nearest(&self, rgb: Rgb) -> (u8, u8, u8) {
+        let index = (0..self.colors.len())
+            .min_by_key(|&index| distance(self.colors[index], rgb))
+            .unwrap_or_default();
+        ((index % 8) as u8, 0, (index / 8) as u8)
+    }
+
+    /// The name of the color of the palette nearest to an RGB value.
+    pub fn nearest_name(&self, rgb: Rgb) -> &'static str {
+        let (fg, _, bright) = self.nearest(rgb);
+        COLOR_NAMES[(fg + bright * 8) as usize]
+    }
+}
+
+/// A named color as `(fg, 0, bright)`.
+pub fn named_color(name: &str) -> Option<(u8, u8, u8)> {
+    let index = COLOR_NAMES.iter().position(|color| *color == name)?;
+    Some(((index % 8) as u8, 0, (index / 8) as u8))
+}
+
+/// The name of the foreground of `(fg, bg, bright)`.
+pub fn color_name((fg, _, bright): (u8, u8, u8)) -> Option<&'static str> {
+    if fg > 7 || bright > 1 {
+        return None;
+    }
+    Some(COLOR_NAMES[(fg + bright * 8) as usize])
+}
+
+/// An RGB value written as hex, like `#ff8000` or `ff8000`.
+pub fn parse_hex(text: &str) -> Option<Rgb> {
+    let hex = text.strip_prefix('#').unwrap_or(text);
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// An RGB value as hex, like `#ff8000`.
+pub fn to_hex((r, g, b): Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// The descriptor color nearest to an RGB value, like `STATE_COLOR` takes it.
+pub fn nearest_color_token<'a>(
+    colors: impl IntoIterator<Item = &'a ColorToken>,
+    rgb: Rgb,
+) -> Option<&'a ColorToken> {
+    colors
+        .into_iter()
+        .filter_map(|color| Some((color, distance(color.rgb?, rgb))))
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(color, _)| color)
+}
+
+/// How far apart two colors are, as the square of their distance in RGB.
+pub(crate) fn distance(a: Rgb, b: Rgb) -> u32 {
+    let channel = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    channel(a.0, b.0) + channel(a.1, b.1) + channel(a.2, b.2)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn palette() -> Result<()> {
+        let default = Palette::default();
+        let alternate = Palette::load("data/Colors/alternate.txt")?;
+        assert_eq!(Consts::standard().palette(), Some(default));
+        assert_eq!(alternate.rgb("blue"), Some((48, 60, 120)));
+        assert_eq!(default.foreground((4, 0, 1)), Some((255, 0, 0)));
+        assert_eq!(default.background((7, 1, 0)), Some((0, 0, 128)));
+        assert_eq!(named_color("yellow"), Some((6, 0, 1)));
+        assert_eq!(color_name((6, 0, 1)), Some("yellow"));
+        assert_eq!(parse_hex("#ff8000"), Some((255, 128, 0)));
+        assert_eq!(to_hex((255, 128, 0)), "#ff8000");
+        assert_eq!(default.nearest((240, 16, 16)), (4, 0, 1));
+        assert_eq!(alternate.nearest_name((50, 60, 110)), "blue");
+
+        let color = |id: &str, rgb| ColorToken {
+            reference: Some(ReferenceTo::new(id.to_owned())),
+            rgb: Some(rgb),
+            ..Default::default()
+        };
+        let colors = [color("RED", (128, 0, 0)), color("GRAY", (128, 128, 128))];
+        assert_eq!(nearest_color_token(&colors, (120, 5, 5)), Some(&colors[0]));
+
+        let mut definitions = Definitions::from_yaml(
+            "
+ember:
+  type: inorganic
+  display_color: { call: color2vec, color: '#3a3c70' }
+  state_color: { all_solid: { call: color2ref, color: '#7a0505' } }
+",
+        )?;
+        definitions.set_palette(&alternate);
+        definitions.set_descriptor_colors(&colors);
+        let ember = definitions.evaluate("ember")?;
+        assert_eq!(ember.fields["display_color"], serde_yaml::to_value([1, 0, 0])?);
+        assert_eq!(ember.fields["state_color"]["all_solid"], "RED");
+        Ok(())
+    }
+}
@@ -0,0 +1,332 @@
+//! Patches editing every object a selector matches, written like the `patches` of `steel.yml`:
+//!
+//! ```yaml
+//! patches:
+//!   - foreach: { tag: economic_metal, where: { solid_density: { min: 5000 } } }
+//!     scale: { solid_density: 1.1 }
+//!     append: { reaction_class: [DENSE] }
+//! ```
+//!
+//! They work on the compact form of objects, so the same patches apply to evaluated
+//! [`Definition`]s and to loaded raws.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+
+use crate::compact::{from_compact, to_compact};
+use crate::definition::Definition;
+use crate::structure::DFRaw;
+
+/// Which objects a patch applies to. An object matches when it matches everything given.
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Selector {
+    /// A base the object extends, or a flag it has, like `is_metal`.
+    pub tag: Option<String>,
+    /// The object type, like `inorganic`.
+    #[serde(rename = "type")]
+    pub object_type: Option<String>,
+    /// The ID, where `*` matches any text and `?` any character, in any case.
+    pub id: Option<String>,
+    /// Fields with the value they must have, nothing for any value, or `{min, max}` for numbers.
+    #[serde(rename = "where")]
+    pub fields: Mapping,
+}
+
+/// Edits for the objects a selector matches, applied in the order of the fields here.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Patch {
+    pub foreach: Selector,
+    /// Fields to set, or to unset when set to nothing.
+    pub set: Mapping,
+    /// Fields to unset.
+    pub remove: Vec<String>,
+    /// Values to add to the end of list fields.
+    pub append: Mapping,
+    /// Factors to multiply number fields by, rounding fields that hold whole numbers.
+    pub scale: Mapping,
+}
+
+/// An object as patches see it.
+struct Object<'a> {
+    object_type: Option<&'a str>,
+    id: &'a str,
+    bases: &'a [String],
+    fields: &'a mut Mapping,
+}
+
+impl Selector {
+    fn matches(&self, object: &Object) -> Result<bool> {
+        if let Some(tag) = &self.tag {
+            let flag = object.fields.get(tag.as_str()) == Some(&Value::Bool(true));
+            if !flag && !object.bases.contains(tag) && object.id != tag {
+                return Ok(false);
+            }
+        }
+        if self.object_type.is_some() && self.object_type.as_deref() != object.object_type {
+            return Ok(false);
+        }
+        if let Some(id) = &self.id {
+            if !glob(&id.to_ascii_uppercase(), &object.id.to_ascii_uppercase()) {
+                return Ok(false);
+            }
+        }
+        for (field, expected) in &self.fields {
+            let value = object.fields.get(field);
+            let matches = match (expected, value) {
+                (_, None) => false,
+                (Value::Null, Some(_)) => true,
+                (Value::Mapping(range), Some(value)) => {
+                    let number = value.as_f64();
+                    let bound = |bound: &str| range.get(bound).and_then(Value::as_f64);
+                    let field = field.as_str().unwrap_or_default();
+                    if range.keys().any(|key| key != "min" && key != "max") {
+                        bail!("`where.{}`: expected `min` and `max`", field);
+                    }
+                    number.is_some_and(|number| {
+                        bound("min").is_none_or(|min| number >= min)
+                            && bound("max").is_none_or(|max| number <= max)
+                    })
+                }
+                (Value::Number(expected), Some(Value::Number(value))) => {
+                    expected.as_f64() == value.as_f64()
+                }
+                (expected, Some(value)) => expected == value,
+            };
+            if !matches {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl Patch {
+    /// Reads the patches under `patches` in a YAML document.
+    pub fn from_yaml(yaml: &str) -> Result<Vec<Self>> {
+        let mut document: Mapping = serde_yaml::from_str(yaml)?;
+        Self::from_document(document.remove("patches").unwrap_or_default())
+    }
+
+    /// Reads a list of patches.
+    pub(crate) fn from_document(patches: Value) -> Result<Vec<Self>> {
+        let patches = match patches {
+            Value::Null => return Ok(vec![]),
+            Value::Sequence(patches) => patches,
+            _ => bail!("`patches`: expected a list of patches"),
+        };
+        patches
+            .into_iter()
+            .enumerate()
+            .map(|(index, patch)| {
+                serde_yaml::from_value(patch).with_context(|| format!("`patches[{}]`", index))
+            })
+            .collect()
+    }
+
+    /// Patches the definitions the selector matches, returning how many that is.
+    pub fn apply_to_definitions(&self, definitions: &mut [Definition]) -> Result<usize> {
+        let mut patched = 0;
+        for definition in definitions {
+            let mut object = Object {
+                object_type: definition.object_type.as_deref(),
+                id: &definition.id,
+                bases: &definition.bases,
+                fields: &mut definition.fields,
+            };
+            patched += self.apply(&mut object)? as usize;
+        }
+        Ok(patched)
+    }
+
+    /// Patches the objects of raws the selector matches, returning how many that is.
+    pub fn apply_to_raw(&self, raw: &mut DFRaw) -> Result<usize> {
+        let mut compact = match to_compact(raw)? {
+            Value::Mapping(compact) => compact,
+            _ => return Ok(0),
+        };
+        let mut patched = 0;
+        for (object_type, objects) in compact.iter_mut() {
+            let objects = match objects {
+                Value::Mapping(objects) => objects,
+                _ => continue,
+            };
+            for (id, fields) in objects.iter_mut() {
+                let fields = match fields {
+                    Value::Mapping(fields) if is_kind(fields) => match fields.values_mut().next() {
+                        Some(Value::Mapping(kind)) => kind,
+                        _ => continue,
+                    },
+                    Value::Mapping(fields) => fields,
+                    _ => continue,
+                };
+                let mut object = Object {
+                    object_type: object_type.as_str(),
+                    id: id.as_str().unwrap_or_default(),
+                    bases: &[],
+                    fields,
+                };
+                patched += self.apply(&mut object)? as usize;
+            }
+        }
+        if patched > 0 {
+            *raw = from_compact(compact.into())?;
+        }
+        Ok(patched)
+    }
+
+    fn apply(&self, object: &mut Object) -> Result<bool> {
+        let matches = self.foreach.matches(object).context("in `foreach`")?;
+        if !matches {
+            return Ok(false);
+        }
+        let fields = &mut *object.fields;
+        for (field, value) in &self.set {
+            match value {
+                Value::Null => fields.remove(field),
+                value => fields.insert(field.clone(), value.clone()),
+            };
+        }
+        for field in &self.remove {
+            fields.remove(field.as_str());
+        }
+        for (field, values) in &self.append {
+            let name = field.as_str().unwrap_or_default();
+            match (fields.get_mut(field), values) {
+                (None, values @ (Value::Sequence(_) | Value::Mapping(_))) => {
+                    fields.insert(field.clone(), values.clone());
+                }
+                (None, value) => {
+                    fields.insert(field.clone(), vec![value.clone()].into());
+                }
+                (Some(Value::Sequence(list)), Value::Sequence(values)) => {
+                    list.extend(values.iter().cloned())
+                }
+                (Some(Value::Sequence(list)), value) => list.push(value.clone()),
+                // Lists keyed by their first argument.
+                (Some(Value::Mapping(list)), Value::Mapping(values)) => list.extend(values.clone()),
+                (Some(_), _) => {
+                    bail!(
+                        "`append.{}`: `{}` is not a list on `{}`",
+                        name,
+                        name,
+                        object.id
+                    )
+                }
+            }
+        }
+        for (field, factor) in &self.scale {
+            let name = field.as_str().unwrap_or_default();
+            let factor = match factor.as_f64() {
+                Some(factor) => factor,
+                None => bail!("`scale.{}`: expected a number", name),
+            };
+            let value = match fields.get_mut(field) {
+                Some(value) => value,
+                None => continue,
+            };
+            *value = match &*value {
+                Value::Number(number) if number.is_f64() => number
+                    .as_f64()
+                    .map(|number| (number * factor).into())
+                    .unwrap_or_default(),
+                Value::Number(number) => match number.as_f64() {
+                    Some(number) => ((number * factor).round() as i64).into(),
+                    None => continue,
+                },
+                _ => bail!(
+                    "`scale.{}`: `{}` is not a number on `{}`",
+                    name,
+                    name,
+                    object.id
+                ),
+            };
+        }
+        Ok(true)
+    }
+}
+
+/// Whether the fields of an object in the compact form are its kind, like `{Workshop: {...}}`.
+fn is_kind(fields: &Mapping) -> bool {
+    fields.len() == 1
+        && fields.iter().all(|(kind, value)| {
+            let kind = kind.as_str().unwrap_or_default();
+            kind.starts_with(char::is_uppercase) && value.is_mapping()
+        })
+}
+
+/// Whether a text matches a pattern where `*` matches any text and `?` any character.
+fn glob(pattern: &str, text: &str) -> bool {
+    match pattern.chars().next() {
+        None => text.is_empty(),
+        Some('*') => (0..=text.len())
+            .filter(|&index| text.is_char_boundary(index))
+            .any(|index| glob(&pattern[1..], &text[index..])),
+        Some(first) => {
+            let mut chars = text.chars();
+            match chars.next() {
+                Some(char) if first == '?' || first == char => {
+                    glob(&pattern[first.len_utf8()..], chars.as_str())
+                }
+                _ => false,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn patches() -> Result<()> {
+        use crate::core::{Choose, Reference};
+
+        let definitions = Definitions::from_yaml(
+            "
+metal:
+  type: inorganic
+  abstract: true
+  is_metal: true
+iron:
+  base: metal
+  solid_density: 7850
+copper:
+  base: metal
+  solid_density: 8930
+clay:
+  type: inorganic
+  solid_density: 2000
+patches:
+  - foreach: { tag: metal, where: { solid_density: { max: 8000 } } }
+    scale: { solid_density: 1.1 }
+  - foreach: { type: inorganic, id: C* }
+    append: { reaction_class: [SOFT] }
+    remove: [is_metal]
+",
+        )?;
+        let objects = definitions.objects()?;
+        let field = |index: usize, name: &str| objects[index].fields.get(name).cloned();
+        assert_eq!(field(0, "solid_density"), Some(8635.into()));
+        assert_eq!(field(1, "solid_density"), Some(8930.into()));
+        assert_eq!(field(1, "is_metal"), None);
+        assert_eq!(field(2, "reaction_class"), Some(vec!["SOFT"].into()));
+
+        let mut raw = from_yaml(
+            "inorganic:\n  IRON:\n    is_metal: true\n    solid_density: 7850\n  CLAY: {}\n",
+        )?;
+        let patches = Patch::from_yaml(
+            "patches:\n- foreach: { tag: is_metal }\n  scale: { solid_density: 1.1 }\n  \
+             append: { reaction_class: METAL }\n",
+        )?;
+        assert_eq!(patches[0].apply_to_raw(&mut raw)?, 1);
+        let iron = &raw.object_tokens[0].inorganic_tokens[0];
+        assert_eq!(iron.solid_density, Some(Choose::Choice1(8635)));
+        assert_eq!(iron.reaction_class, [Reference("METAL".to_owned())]);
+        assert!(Patch::from_yaml("patches:\n- foreach: { tags: metal }\n").is_err());
+        Ok(())
+    }
+}
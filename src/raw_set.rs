@@ -0,0 +1,565 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::structure::*;
+use crate::tags::{object_headers, parse_tags_with_lines};
+use crate::{parse_lossy, read_raw_file, Diagnostic, Encoding};
+
+/// Loads raw files from any number of directories, keeping track of where every object came
+/// from.
+///
+/// ```no_run
+/// let raws = domni::RawLoader::new()
+///     .root("./raw/objects")
+///     .root("./raw/graphics")
+///     .load();
+/// for error in &raws.errors {
+///     eprintln!("{}", error);
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RawLoader {
+    /// Directories (or single files) to load, in order.
+    pub roots: Vec<PathBuf>,
+    /// Skip every file that has diagnostics, instead of keeping what could be recovered.
+    pub strict: bool,
+    pub encoding: Encoding,
+}
+
+impl RawLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.roots.push(root.into());
+        self
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    pub fn load(&self) -> RawSet {
+        let mut raw_set = RawSet::default();
+        for root in &self.roots {
+            let mut files = vec![];
+            if let Err(error) = collect_files(root, &mut files) {
+                raw_set.errors.push(LoadError::Read {
+                    path: root.clone(),
+                    message: error.to_string(),
+                });
+                continue;
+            }
+            for path in files {
+                self.load_file(&path, &mut raw_set);
+            }
+        }
+        raw_set
+    }
+
+    fn load_file(&self, path: &Path, raw_set: &mut RawSet) {
+        let source = match read_raw_file(path, self.encoding) {
+            Ok(source) => source,
+            Err(error) => {
+                raw_set.errors.push(LoadError::Read {
+                    path: path.to_owned(),
+                    message: error.to_string(),
+                });
+                return;
+            }
+        };
+        // Text files next to the raws (like `readme.txt`) are not raw files.
+        if !source.contains("[OBJECT:") {
+            return;
+        }
+        let report = match parse_lossy(&source) {
+            Ok(report) => report,
+            Err(error) => {
+                raw_set.errors.push(LoadError::Parse {
+                    path: path.to_owned(),
+                    message: format!("{:#}", error),
+                });
+                return;
+            }
+        };
+        let has_diagnostics = !report.diagnostics.is_empty();
+        if has_diagnostics {
+            raw_set.errors.push(LoadError::Diagnostics {
+                path: path.to_owned(),
+                diagnostics: report.diagnostics,
+            });
+            if self.strict {
+                return;
+            }
+        }
+        let raw = report.raw;
+        let mut lines = HeaderLines::new(&source);
+        for object_token in raw.object_tokens {
+            for object in RawObject::from_object_token(object_token) {
+                let line = object
+                    .id()
+                    .and_then(|id| lines.take(object.header_token(), id));
+                raw_set.objects.push(SourcedObject {
+                    source: Source {
+                        path: path.to_owned(),
+                        header: raw.header.clone(),
+                        line,
+                        encoding: self.encoding,
+                    },
+                    object,
+                });
+            }
+        }
+    }
+}
+
+/// Collects every `.txt` file below `path`, sorted so loading is deterministic.
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if path.is_file() {
+        files.push(path.to_owned());
+        return Ok(());
+    }
+    let mut entries = std::fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+    for entry in entries {
+        if entry.is_dir() {
+            collect_files(&entry, files)?;
+        } else if is_txt(&entry) {
+            files.push(entry);
+        }
+    }
+    Ok(())
+}
+
+fn is_txt(path: &Path) -> bool {
+    matches!(path.extension(), Some(extension) if extension.eq_ignore_ascii_case("txt"))
+}
+
+/// All objects loaded from a set of raw directories.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RawSet {
+    pub objects: Vec<SourcedObject>,
+    /// Files that could not be (fully) loaded.
+    pub errors: Vec<LoadError>,
+}
+
+impl RawSet {
+    /// Loads every raw file below the given directories.
+    pub fn load<P: Into<PathBuf>>(roots: impl IntoIterator<Item = P>) -> Self {
+        RawLoader {
+            roots: roots.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }
+        .load()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &SourcedObject> {
+        self.objects.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// Finds an object by its `[OBJECT:...]` type and ID. When the ID is defined more than once,
+    /// the one loaded last wins.
+    pub fn find(&self, object_type: &str, id: &str) -> Option<&SourcedObject> {
+        self.objects.iter().rev().find(|sourced| {
+            sourced.object.object_type() == object_type && sourced.object.id() == Some(id)
+        })
+    }
+
+    pub fn creatures(&self) -> impl Iterator<Item = &CreatureToken> {
+        self.objects
+            .iter()
+            .filter_map(|sourced| match &sourced.object {
+                RawObject::Creature(creature) => Some(creature),
+                _ => None,
+            })
+    }
+}
+
+impl Extend<SourcedObject> for RawSet {
+    fn extend<T: IntoIterator<Item = SourcedObject>>(&mut self, iter: T) {
+        self.objects.extend(iter)
+    }
+}
+
+/// Where an object was defined.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Source {
+    pub path: PathBuf,
+    /// The header line of the file, normally the file name without extension.
+    pub header: String,
+    /// 0-based line of the object's header token, if it could be found.
+    pub line: Option<usize>,
+    /// What the file was read as, to read it again the same way.
+    pub encoding: Encoding,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path.display())?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line + 1)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SourcedObject {
+    pub source: Source,
+    pub object: RawObject,
+}
+
+/// A single top level object of any type.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum RawObject {
+    Body(BodyObjectToken),
+    BodyDetailPlan(BodyDetailPlanToken),
+    Building(BuildingToken),
+    Creature(CreatureToken),
+    CreatureVariation(CreatureVariationToken),
+    Color(ColorToken),
+    Pattern(PatternToken),
+    Shape(ShapeToken),
+    Entity(EntityToken),
+    Graphics(GraphicsToken),
+    Interaction(InteractionToken),
+    Inorganic(InorganicToken),
+    Item(ItemToken),
+    Language(LanguageToken),
+    MaterialTemplate(MaterialToken),
+    Plant(PlantToken),
+    Reaction(ReactionToken),
+    TissueTemplate(TissueToken),
+}
+
+impl RawObject {
+    /// Splits an `ObjectToken` into its objects, in the order they were stored.
+    pub fn from_object_token(object_token: ObjectToken) -> Vec<RawObject> {
+        let ObjectToken {
+            body_tokens,
+            body_detail_plan_tokens,
+            building_tokens,
+            creature_tokens,
+            creature_variation_tokens,
+            color_tokens,
+            pattern_tokens,
+            shape_tokens,
+            entity_tokens,
+            graphics_tokens,
+            interaction_tokens,
+            inorganic_tokens,
+            item_tokens,
+            language_tokens,
+            material_tokens,
+            plant_tokens,
+            reaction_tokens,
+            tissue_template_tokens,
+        } = object_token;
+        let mut objects = vec![];
+        objects.extend(body_tokens.into_iter().map(RawObject::Body));
+        objects.extend(
+            body_detail_plan_tokens
+                .into_iter()
+                .map(RawObject::BodyDetailPlan),
+        );
+        objects.extend(building_tokens.into_iter().map(RawObject::Building));
+        objects.extend(creature_tokens.into_iter().map(RawObject::Creature));
+        objects.extend(
+            creature_variation_tokens
+                .into_iter()
+                .map(RawObject::CreatureVariation),
+        );
+        objects.extend(color_tokens.into_iter().map(RawObject::Color));
+        objects.extend(pattern_tokens.into_iter().map(RawObject::Pattern));
+        objects.extend(shape_tokens.into_iter().map(RawObject::Shape));
+        objects.extend(entity_tokens.into_iter().map(RawObject::Entity));
+        objects.extend(graphics_tokens.into_iter().map(RawObject::Graphics));
+        objects.extend(interaction_tokens.into_iter().map(RawObject::Interaction));
+        objects.extend(inorganic_tokens.into_iter().map(RawObject::Inorganic));
+        objects.extend(item_tokens.into_iter().map(RawObject::Item));
+        objects.extend(language_tokens.into_iter().map(RawObject::Language));
+        objects.extend(material_tokens.into_iter().map(RawObject::MaterialTemplate));
+        objects.extend(plant_tokens.into_iter().map(RawObject::Plant));
+        objects.extend(reaction_tokens.into_iter().map(RawObject::Reaction));
+        objects.extend(
+            tissue_template_tokens
+                .into_iter()
+                .map(RawObject::TissueTemplate),
+        );
+        objects
+    }
+
+    /// The type used in `[OBJECT:...]` for this object.
+    pub fn object_type(&self) -> &'static str {
+        match self {
+            RawObject::Body(_) => "BODY",
+            RawObject::BodyDetailPlan(_) => "BODY_DETAIL_PLAN",
+            RawObject::Building(_) => "BUILDING",
+            RawObject::Creature(_) => "CREATURE",
+            RawObject::CreatureVariation(_) => "CREATURE_VARIATION",
+            RawObject::Color(_) => "DESCRIPTOR_COLOR",
+            RawObject::Pattern(_) => "DESCRIPTOR_PATTERN",
+            RawObject::Shape(_) => "DESCRIPTOR_SHAPE",
+            RawObject::Entity(_) => "ENTITY",
+            RawObject::Graphics(_) => "GRAPHICS",
+            RawObject::Interaction(_) => "INTERACTION",
+            RawObject::Inorganic(_) => "INORGANIC",
+            RawObject::Item(_) => "ITEM",
+            RawObject::Language(_) => "LANGUAGE",
+            RawObject::MaterialTemplate(_) => "MATERIAL_TEMPLATE",
+            RawObject::Plant(_) => "PLANT",
+            RawObject::Reaction(_) => "REACTION",
+            RawObject::TissueTemplate(_) => "TISSUE_TEMPLATE",
+        }
+    }
+
+    /// The token that starts this object, e.g. `CREATURE` or `ITEM_WEAPON`.
+    pub fn header_token(&self) -> &'static str {
+        match self {
+            RawObject::Body(BodyObjectToken::BodyToken(_)) => "BODY",
+            RawObject::Body(BodyObjectToken::BodyGlossToken(_)) => "BODYGLOSS",
+            RawObject::Building(BuildingToken::Workshop(_)) => "BUILDING_WORKSHOP",
+            RawObject::Building(BuildingToken::Furnace(_)) => "BUILDING_FURNACE",
+            RawObject::Color(_) => "COLOR",
+            RawObject::Pattern(_) => "COLOR_PATTERN",
+            RawObject::Shape(_) => "SHAPE",
+            RawObject::Graphics(GraphicsToken::TilePage(_)) => "TILE_PAGE",
+            RawObject::Graphics(GraphicsToken::CreatureGraphics(_)) => "CREATURE_GRAPHICS",
+            RawObject::Item(item) => match item {
+                ItemToken::AmmoToken(_) => "ITEM_AMMO",
+                ItemToken::ArmorToken(_) => "ITEM_ARMOR",
+                ItemToken::FoodToken(_) => "ITEM_FOOD",
+                ItemToken::GlovesToken(_) => "ITEM_GLOVES",
+                ItemToken::HelmToken(_) => "ITEM_HELM",
+                ItemToken::InstrumentToken(_) => "ITEM_INSTRUMENT",
+                ItemToken::PantsToken(_) => "ITEM_PANTS",
+                ItemToken::ShieldToken(_) => "ITEM_SHIELD",
+                ItemToken::ShoesToken(_) => "ITEM_SHOES",
+                ItemToken::SiegeAmmoToken(_) => "ITEM_SIEGEAMMO",
+                ItemToken::ToolToken(_) => "ITEM_TOOL",
+                ItemToken::ToyToken(_) => "ITEM_TOY",
+                ItemToken::TrapCompToken(_) => "ITEM_TRAPCOMP",
+                ItemToken::WeaponToken(_) => "ITEM_WEAPON",
+            },
+            RawObject::Language(LanguageToken::WordToken(_)) => "WORD",
+            RawObject::Language(LanguageToken::SymbolToken(_)) => "SYMBOL",
+            RawObject::Language(LanguageToken::TranslationToken(_)) => "TRANSLATION",
+            object => object.object_type(),
+        }
+    }
+
+    /// The type other objects use to refer to this one, e.g. `BUILDING` for
+    /// `[BUILDING_WORKSHOP:...]`. Creature graphics can't be referred to.
+    pub fn ref_type(&self) -> Option<&'static str> {
+        match self {
+            RawObject::Building(_) => Some("BUILDING"),
+            RawObject::Graphics(GraphicsToken::CreatureGraphics(_)) => None,
+            object => Some(object.header_token()),
+        }
+    }
+
+    /// The ID given in the header token, e.g. `DWARF` for `[CREATURE:DWARF]`.
+    pub fn id(&self) -> Option<&str> {
+        let reference = match self {
+            RawObject::Body(BodyObjectToken::BodyToken(token)) => &token.reference,
+            RawObject::Body(BodyObjectToken::BodyGlossToken(token)) => {
+                return token
+                    .bodygloss
+                    .as_ref()
+                    .map(|bodygloss| bodygloss.0 .0.as_str())
+            }
+            RawObject::BodyDetailPlan(token) => return ref_str(&token.reference),
+            RawObject::Building(BuildingToken::Workshop(token))
+            | RawObject::Building(BuildingToken::Furnace(token)) => {
+                return ref_str(&token.reference)
+            }
+            RawObject::Creature(token) => return ref_str(&token.reference),
+            RawObject::CreatureVariation(token) => return ref_str(&token.reference),
+            RawObject::Color(token) => return ref_str(&token.reference),
+            RawObject::Pattern(token) => return ref_str(&token.reference),
+            RawObject::Shape(token) => return ref_str(&token.reference),
+            RawObject::Entity(token) => return ref_str(&token.reference),
+            RawObject::Graphics(GraphicsToken::TilePage(token)) => {
+                return ref_str(&token.reference)
+            }
+            RawObject::Graphics(GraphicsToken::CreatureGraphics(token)) => {
+                return ref_str(&token.reference)
+            }
+            RawObject::Interaction(token) => return ref_str(&token.reference),
+            RawObject::Inorganic(token) => return ref_str(&token.reference),
+            RawObject::Item(item) => {
+                return match item {
+                    ItemToken::AmmoToken(token) => ref_str(&token.reference),
+                    ItemToken::ArmorToken(token) => ref_str(&token.reference),
+                    ItemToken::FoodToken(token) => ref_str(&token.reference),
+                    ItemToken::GlovesToken(token) => ref_str(&token.reference),
+                    ItemToken::HelmToken(token) => ref_str(&token.reference),
+                    ItemToken::InstrumentToken(token) => ref_str(&token.reference),
+                    ItemToken::PantsToken(token) => ref_str(&token.reference),
+                    ItemToken::ShieldToken(token) => ref_str(&token.reference),
+                    ItemToken::ShoesToken(token) => ref_str(&token.reference),
+                    ItemToken::SiegeAmmoToken(token) => ref_str(&token.reference),
+                    ItemToken::ToolToken(token) => ref_str(&token.reference),
+                    ItemToken::ToyToken(token) => ref_str(&token.reference),
+                    ItemToken::TrapCompToken(token) => ref_str(&token.reference),
+                    ItemToken::WeaponToken(token) => ref_str(&token.reference),
+                }
+            }
+            RawObject::Language(LanguageToken::WordToken(token)) => {
+                return ref_str(&token.reference)
+            }
+            RawObject::Language(LanguageToken::SymbolToken(token)) => {
+                return ref_str(&token.reference)
+            }
+            RawObject::Language(LanguageToken::TranslationToken(token)) => {
+                return ref_str(&token.reference)
+            }
+            RawObject::MaterialTemplate(token) => return ref_str(&token.reference),
+            RawObject::Plant(token) => return ref_str(&token.reference),
+            RawObject::Reaction(token) => return ref_str(&token.reference),
+            RawObject::TissueTemplate(token) => return ref_str(&token.reference),
+        };
+        ref_str(reference)
+    }
+}
+
+fn ref_str<T>(reference: &Option<crate::core::ReferenceTo<T>>) -> Option<&str> {
+    reference.as_ref().map(|reference| reference.0.as_str())
+}
+
+/// A file that could not be loaded, or only partially.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum LoadError {
+    /// The file or directory could not be read.
+    Read { path: PathBuf, message: String },
+    /// The file was read, but could not be turned into our structures.
+    Parse { path: PathBuf, message: String },
+    /// The file was loaded, but had lexer or syntax diagnostics.
+    Diagnostics {
+        path: PathBuf,
+        diagnostics: Vec<Diagnostic>,
+    },
+    /// An object was loaded, but `COPY_TAGS_FROM` or a creature variation could not be applied.
+    Expand {
+        path: PathBuf,
+        id: String,
+        message: String,
+        /// What df_ls reported for the expanded tokens. Their spans are in the expanded text,
+        /// not in the file at `path`.
+        diagnostics: Vec<Diagnostic>,
+    },
+}
+
+impl LoadError {
+    pub fn path(&self) -> &Path {
+        match self {
+            LoadError::Read { path, .. }
+            | LoadError::Parse { path, .. }
+            | LoadError::Diagnostics { path, .. }
+            | LoadError::Expand { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Read { path, message } => {
+                write!(f, "{}: could not read: {}", path.display(), message)
+            }
+            LoadError::Parse { path, message } => {
+                write!(f, "{}: could not parse: {}", path.display(), message)
+            }
+            LoadError::Diagnostics { path, diagnostics } => {
+                write!(
+                    f,
+                    "{} has {} diagnostic(s)",
+                    path.display(),
+                    diagnostics.len()
+                )?;
+                for diagnostic in diagnostics {
+                    write!(f, "\n  {}:{}", path.display(), diagnostic)?;
+                }
+                Ok(())
+            }
+            LoadError::Expand {
+                path,
+                id,
+                message,
+                diagnostics,
+            } => {
+                write!(
+                    f,
+                    "{}: could not expand {}: {}",
+                    path.display(),
+                    id,
+                    message
+                )?;
+                for diagnostic in diagnostics {
+                    write!(f, "\n  {}", diagnostic)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// Finds the lines of object header tokens like `[CREATURE:DWARF]` in a source file. Only the
+/// header tokens of the `[OBJECT:...]` type before them count, so `[CREATURE:DWARF]` in an entity
+/// or in the notes above `[OBJECT:...]` is not taken for the header of the dwarf.
+struct HeaderLines {
+    lines: HashMap<(String, String), VecDeque<usize>>,
+}
+
+impl HeaderLines {
+    fn new(source: &str) -> Self {
+        let mut lines: HashMap<_, VecDeque<_>> = HashMap::new();
+        let mut headers: &[&str] = &[];
+        for (number, tag) in parse_tags_with_lines(source) {
+            if tag.is("OBJECT") {
+                headers = object_headers(tag.args.first().map_or("", |arg| arg.trim()));
+                continue;
+            }
+            if !headers.contains(&tag.name.trim()) {
+                continue;
+            }
+            if let Some(id) = tag.args.first() {
+                lines
+                    .entry((tag.name.trim().to_owned(), id.trim().to_owned()))
+                    .or_default()
+                    .push_back(number);
+            }
+        }
+        Self { lines }
+    }
+
+    /// Takes the first unused line of `[token:id]`, so duplicate IDs map to their own line.
+    fn take(&mut self, token: &str, id: &str) -> Option<usize> {
+        self.lines
+            .get_mut(&(token.to_owned(), id.to_owned()))
+            .and_then(VecDeque::pop_front)
+    }
+}
@@ -0,0 +1,594 @@
+//! Finding the references objects make to each other, and the ones that point nowhere.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::core::{ReferenceTo, Referenceable};
+use crate::node::{to_node, Node};
+use crate::raw_set::{RawObject, RawSet, Source, SourcedObject};
+use crate::registry::Registry;
+use crate::writer::{token_name, variant_name};
+
+/// Item types that have subtypes defined in the raws, e.g. `WEAPON` for `ITEM_WEAPON`.
+const ITEM_TYPES_WITH_SUBTYPES: &[(&str, &str)] = &[
+    ("AMMO", "ITEM_AMMO"),
+    ("ARMOR", "ITEM_ARMOR"),
+    ("FOOD", "ITEM_FOOD"),
+    ("GLOVES", "ITEM_GLOVES"),
+    ("HELM", "ITEM_HELM"),
+    ("INSTRUMENT", "ITEM_INSTRUMENT"),
+    ("PANTS", "ITEM_PANTS"),
+    ("SHIELD", "ITEM_SHIELD"),
+    ("SHOES", "ITEM_SHOES"),
+    ("SIEGEAMMO", "ITEM_SIEGEAMMO"),
+    ("TOOL", "ITEM_TOOL"),
+    ("TOY", "ITEM_TOY"),
+    ("TRAPCOMP", "ITEM_TRAPCOMP"),
+    ("WEAPON", "ITEM_WEAPON"),
+];
+
+/// Reference types that name something inside the object itself, not another object.
+const LOCAL_TYPES: &[&str] = &["REAGENT", "CATEGORY"];
+
+/// The game's own workshops and furnaces, which reactions can use without them being defined in
+/// the raws.
+const BUILTIN_BUILDINGS: &[&str] = &[
+    "ASHERY",
+    "BOWYER",
+    "BUTCHER",
+    "CARPENTER",
+    "CLOTHIER",
+    "CRAFTSMAN",
+    "DYER",
+    "FARMER",
+    "FISHERY",
+    "GLASS_FURNACE",
+    "JEWELER",
+    "KENNEL",
+    "KILN",
+    "KITCHEN",
+    "LEATHER",
+    "LOOM",
+    "MAGMA_FORGE",
+    "MAGMA_GLASS_FURNACE",
+    "MAGMA_KILN",
+    "MAGMA_SMELTER",
+    "MASON",
+    "MECHANIC",
+    "METALSMITH",
+    "MILLSTONE",
+    "QUERN",
+    "SIEGE",
+    "SMELTER",
+    "STILL",
+    "TANNER",
+    "TOOL",
+    "WOOD_FURNACE",
+];
+
+/// Fields that are plain strings in our structures, but really name something shared between
+/// objects. These names are not objects themselves, so they are never dangling.
+const NAME_FIELDS: &[(&str, &str)] = &[
+    ("creature_class", "CREATURE_CLASS"),
+    ("gobble_vermin_class", "CREATURE_CLASS"),
+    ("sense_creature_class", "CREATURE_CLASS"),
+    ("animal_class", "CREATURE_CLASS"),
+    ("animal_forbidden_class", "CREATURE_CLASS"),
+    ("allowed_class", "CREATURE_CLASS"),
+    ("rejected_class", "CREATURE_CLASS"),
+    ("syn_affected_class", "CREATURE_CLASS"),
+    ("syn_immune_class", "CREATURE_CLASS"),
+    ("it_affected_class", "CREATURE_CLASS"),
+    ("it_immune_class", "CREATURE_CLASS"),
+    ("syn_class", "SYNDROME_CLASS"),
+    ("it_cannot_have_syndrome_class", "SYNDROME_CLASS"),
+    ("reaction_class", "REACTION_CLASS"),
+];
+
+/// Fields that name a material or tissue of the creature they are in.
+const CREATURE_NAME_FIELDS: &[(&str, &str)] = &[
+    ("remove_material", "CREATURE_MAT"),
+    ("remove_tissue", "CREATURE_TISSUE"),
+];
+
+/// The kinds of names found in [`NAME_FIELDS`] and [`CREATURE_NAME_FIELDS`].
+const NAME_KINDS: &[&str] = &[
+    "CREATURE_CLASS",
+    "SYNDROME_CLASS",
+    "REACTION_CLASS",
+    "CREATURE_MAT",
+    "CREATURE_TISSUE",
+];
+
+/// Something that can be referred to: an object like `INORGANIC:IRON`, or a name shared between
+/// objects like `CREATURE_CLASS:EDIBLE_GROUND_BUG`.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ObjectId {
+    /// The reference type, e.g. `INORGANIC` or `CREATURE_CLASS`.
+    pub ref_type: String,
+    pub id: String,
+}
+
+impl ObjectId {
+    pub fn new(ref_type: impl Into<String>, id: impl Into<String>) -> Self {
+        Self {
+            ref_type: ref_type.into(),
+            id: id.into(),
+        }
+    }
+}
+
+impl fmt::Display for ObjectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.ref_type, self.id)
+    }
+}
+
+impl<T: Referenceable> From<&ReferenceTo<T>> for ObjectId {
+    fn from(reference: &ReferenceTo<T>) -> Self {
+        Self::new(T::get_ref_type(), reference.0.clone())
+    }
+}
+
+/// One reference from an object to another object.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FoundReference {
+    /// Tokens leading to the reference, e.g. `CASTE/BODY`.
+    pub path: String,
+    /// The type of object the reference points to, e.g. `CREATURE` or `ITEM_WEAPON`.
+    pub target_type: &'static str,
+    pub id: String,
+}
+
+impl FoundReference {
+    pub fn target(&self) -> ObjectId {
+        ObjectId::new(self.target_type, self.id.clone())
+    }
+
+    /// Whether this refers to an object, instead of a name like a creature class.
+    pub fn is_object(&self) -> bool {
+        !NAME_KINDS.contains(&self.target_type)
+    }
+}
+
+impl RawObject {
+    /// The type and ID other objects use to refer to this one.
+    pub fn object_id(&self) -> Option<ObjectId> {
+        Some(ObjectId::new(self.ref_type()?, self.id()?))
+    }
+
+    /// Every reference this object makes to an object that should be defined in the raws:
+    /// each `ReferenceTo`, the objects named in material tokens, and item subtypes. Names that are
+    /// shared between objects, like creature classes, are included too.
+    ///
+    /// The object's own header is included, so an object always refers to itself. Material
+    /// names inside a creature or plant (`CREATURE_MAT:DWARF:SKIN`) are not included, as they
+    /// are only known once templates and body detail plans are applied. The materials and
+    /// tissues a creature removes are, as `CREATURE_MAT:DWARF:SKIN` and
+    /// `CREATURE_TISSUE:DWARF:SKIN`.
+    pub fn references(&self) -> Vec<FoundReference> {
+        let mut walker = ReferenceWalker {
+            creature: match self {
+                RawObject::Creature(_) => self.id().map(str::to_owned),
+                _ => None,
+            },
+            ..Default::default()
+        };
+        // Our structures always serialize.
+        if let Ok(node) = to_node(self) {
+            walker.walk(&node);
+        }
+        walker.found
+    }
+}
+
+#[derive(Default)]
+struct ReferenceWalker {
+    /// The ID of the creature being walked, which its local names are scoped to.
+    creature: Option<String>,
+    path: Vec<String>,
+    found: Vec<FoundReference>,
+}
+
+impl ReferenceWalker {
+    fn walk(&mut self, node: &Node) {
+        match node {
+            Node::Reference(target_type, id) => self.push(target_type, id),
+            Node::Seq(items) | Node::Tuple(items) => items.iter().for_each(|item| self.walk(item)),
+            Node::Map(entries) => {
+                for (key, value) in entries {
+                    self.walk(key);
+                    self.walk(value);
+                }
+            }
+            Node::Struct("ItemReferenceArg", fields) => self.item_reference(fields),
+            Node::Struct(name, fields) => {
+                for (field, value) in fields {
+                    if is_local_field(name, field) {
+                        continue;
+                    }
+                    self.path.push(token_name(name, field));
+                    if let Some((_, kind)) = NAME_FIELDS.iter().find(|(name, _)| name == field) {
+                        self.names(kind, value);
+                    } else if let Some((_, kind)) =
+                        CREATURE_NAME_FIELDS.iter().find(|(name, _)| name == field)
+                    {
+                        self.creature_names(kind, value);
+                    } else if *field == "material_reaction_product" {
+                        self.material_reaction_products(value);
+                    } else {
+                        self.walk(value);
+                    }
+                    self.path.pop();
+                }
+            }
+            // The reagent and product named here belong to the reaction being defined.
+            Node::Variant {
+                enum_name: "MaterialTypeEnum",
+                variant: "GetMaterialFromReagent",
+                ..
+            } => {}
+            Node::Variant {
+                enum_name,
+                variant,
+                value: Some(value),
+            } => {
+                self.path.push(variant_name(enum_name, variant));
+                self.walk(value);
+                self.path.pop();
+            }
+            _ => {}
+        }
+    }
+
+    /// Every string below a field listed in [`NAME_FIELDS`].
+    fn names(&mut self, kind: &'static str, node: &Node) {
+        match node {
+            Node::Str(id) => self.push(kind, id),
+            Node::Seq(items) | Node::Tuple(items) => {
+                items.iter().for_each(|item| self.names(kind, item))
+            }
+            _ => {}
+        }
+    }
+
+    /// Every string below a field listed in [`CREATURE_NAME_FIELDS`].
+    fn creature_names(&mut self, kind: &'static str, node: &Node) {
+        // Each creature has its own `SKIN`, so two creatures removing theirs have nothing in
+        // common. The creature is part of the key, the way `CREATURE_MAT:DWARF:SKIN` names it.
+        let creature = match &self.creature {
+            Some(creature) => creature.clone(),
+            None => return,
+        };
+        match node {
+            Node::Str(id) => self.push(kind, &format!("{}:{}", creature, id)),
+            Node::Seq(items) | Node::Tuple(items) => items
+                .iter()
+                .for_each(|item| self.creature_names(kind, item)),
+            _ => {}
+        }
+    }
+
+    /// `[MATERIAL_REACTION_PRODUCT:ID:material]`: the ID is the name of the product, only the
+    /// material refers to other objects.
+    fn material_reaction_products(&mut self, node: &Node) {
+        match node {
+            Node::Seq(items) => items
+                .iter()
+                .for_each(|item| self.material_reaction_products(item)),
+            Node::Tuple(items) => items.iter().skip(1).for_each(|item| self.walk(item)),
+            node => self.walk(node),
+        }
+    }
+
+    /// `item_type` is a plain item type like `WEAPON`, and `item_subtype` is an object of the
+    /// matching `ITEM_WEAPON` type, if the type has subtypes.
+    fn item_reference(&mut self, fields: &[(&'static str, Node)]) {
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(field, _)| *field == name)
+                .map(|(_, value)| value)
+        };
+        let item_type = match field("item_type") {
+            Some(Node::Str(item_type)) => item_type,
+            _ => return,
+        };
+        let subtype = match field("item_subtype") {
+            Some(Node::Str(subtype)) => subtype,
+            _ => return,
+        };
+        if let Some((_, target_type)) = ITEM_TYPES_WITH_SUBTYPES
+            .iter()
+            .find(|(name, _)| name == item_type)
+        {
+            self.push(target_type, subtype);
+        }
+    }
+
+    fn push(&mut self, target_type: &'static str, id: &str) {
+        if LOCAL_TYPES.contains(&target_type) || is_placeholder(id) {
+            return;
+        }
+        self.found.push(FoundReference {
+            path: self.path.join("/"),
+            target_type,
+            id: id.to_owned(),
+        });
+    }
+}
+
+/// Fields typed as references that name something inside the same creature.
+fn is_local_field(struct_name: &str, field: &str) -> bool {
+    matches!(
+        (struct_name, field),
+        (_, "subordinate_to_tissue") | ("TissueStyle", "reference")
+    )
+}
+
+/// IDs the game fills in itself, and creature variation arguments.
+fn is_placeholder(id: &str) -> bool {
+    id == "USE_LAVA_STONE" || id.contains("!ARG")
+}
+
+/// A reference to an object that isn't defined anywhere in the raw set.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct DanglingReference {
+    /// Where the object holding the reference was defined.
+    pub source: Source,
+    /// The header token of the object holding the reference, e.g. `CREATURE`.
+    pub object_type: &'static str,
+    pub object_id: Option<String>,
+    #[serde(flatten)]
+    pub reference: FoundReference,
+}
+
+impl fmt::Display for DanglingReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}:{}",
+            self.source,
+            self.object_type,
+            self.object_id.as_deref().unwrap_or("?")
+        )?;
+        if !self.reference.path.is_empty() {
+            write!(f, " {}", self.reference.path)?;
+        }
+        write!(
+            f,
+            " refers to missing {}:{}",
+            self.reference.target_type, self.reference.id
+        )
+    }
+}
+
+impl RawSet {
+    /// Finds every reference that doesn't resolve to an object in this raw set.
+    ///
+    /// ```no_run
+    /// let raws = domni::RawSet::load(["./raw/objects"]);
+    /// for dangling in raws.dangling_references() {
+    ///     eprintln!("{}", dangling);
+    /// }
+    /// ```
+    pub fn dangling_references(&self) -> Vec<DanglingReference> {
+        let registry = Registry::new(self);
+        self.iter()
+            .flat_map(|sourced| {
+                let registry = &registry;
+                sourced
+                    .object
+                    .references()
+                    .into_iter()
+                    .filter(move |reference| {
+                        reference.is_object() && !resolves(registry, reference)
+                    })
+                    .map(move |reference| DanglingReference {
+                        source: sourced.source.clone(),
+                        object_type: sourced.object.header_token(),
+                        object_id: sourced.object.id().map(str::to_owned),
+                        reference,
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Whether a reference points to an object in the registry, or to something the game defines
+/// itself.
+fn resolves(registry: &Registry, reference: &FoundReference) -> bool {
+    let id = reference.id.as_str();
+    registry.contains_id(reference.target_type, id)
+        || match reference.target_type {
+            // `STATE_COLOR` and `TL_COLOR_MODIFIER` take color patterns as well as colors.
+            "COLOR" => registry.contains_id("COLOR_PATTERN", id),
+            "BUILDING" => BUILTIN_BUILDINGS.contains(&id),
+            _ => false,
+        }
+}
+
+/// Where an object is used: the object holding the reference, and the tokens leading to it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Usage<'a> {
+    pub user: &'a SourcedObject,
+    pub path: &'a str,
+}
+
+/// Answers "what uses this?" for every object and shared name in a raw set.
+///
+/// ```no_run
+/// let raws = domni::RawSet::load(["./raw/objects"]);
+/// let index = raws.reverse_index();
+/// for usage in index.users_of(&domni::ObjectId::new("INORGANIC", "IRON")) {
+///     println!("{} {}", usage.user.source, usage.path);
+/// }
+/// ```
+#[derive(Default)]
+pub struct ReverseIndex<'a> {
+    references: Vec<(&'a SourcedObject, FoundReference)>,
+    users: HashMap<ObjectId, Vec<usize>>,
+}
+
+impl<'a> ReverseIndex<'a> {
+    pub fn new(raw_set: &'a RawSet) -> Self {
+        let mut index = Self::default();
+        for sourced in raw_set.iter() {
+            let own_id = sourced.object.object_id();
+            for reference in sourced.object.references() {
+                let target = reference.target();
+                // The header refers to the object itself.
+                if own_id.as_ref() == Some(&target) {
+                    continue;
+                }
+                index
+                    .users
+                    .entry(target)
+                    .or_default()
+                    .push(index.references.len());
+                index.references.push((sourced, reference));
+            }
+        }
+        index
+    }
+
+    /// Every place that refers to `id`, in load order. An object that refers to `id` more than
+    /// once is listed once per reference.
+    pub fn users_of(&self, id: &ObjectId) -> impl Iterator<Item = Usage<'_>> + '_ {
+        self.users.get(id).into_iter().flatten().map(|&index| {
+            let (user, reference) = &self.references[index];
+            Usage {
+                user,
+                path: &reference.path,
+            }
+        })
+    }
+
+    /// Every object and name that is referred to at least once.
+    pub fn targets(&self) -> impl Iterator<Item = &ObjectId> + '_ {
+        self.users.keys()
+    }
+}
+
+impl RawSet {
+    pub fn reverse_index(&self) -> ReverseIndex<'_> {
+        ReverseIndex::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn dangling_references() {
+        let material = |id: &str| MaterialTokenArg {
+            material: MaterialTypeEnum::Inorganic(ReferenceTo::new(id.to_owned())),
+        };
+        let creature = CreatureToken {
+            reference: Some(ReferenceTo::new("DWARF".to_owned())),
+            copy_tags_from: Some(ReferenceTo::new("ELF".to_owned())),
+            itemcorpse: Some((
+                ItemReferenceArg {
+                    item_type: crate::core::Reference("WEAPON".to_owned()),
+                    item_subtype: crate::core::Choose::Choice2(crate::core::Reference(
+                        "ITEM_WEAPON_AXE_BATTLE".to_owned(),
+                    )),
+                },
+                material("IRON"),
+            )),
+            webber: Some(material("USE_LAVA_STONE")),
+            ..Default::default()
+        };
+        let mut raws = RawSet::default();
+        raws.extend([SourcedObject {
+            source: Source::default(),
+            object: RawObject::Creature(creature),
+        }]);
+
+        let dangling: Vec<_> = raws
+            .dangling_references()
+            .into_iter()
+            .map(|dangling| (dangling.reference.target_type, dangling.reference.id))
+            .collect();
+        assert_eq!(
+            dangling,
+            [
+                ("ITEM_WEAPON", "ITEM_WEAPON_AXE_BATTLE".to_owned()),
+                ("INORGANIC", "IRON".to_owned()),
+                ("CREATURE", "ELF".to_owned()),
+            ]
+        );
+    }
+    #[test]
+    fn reverse_index() {
+        let iron = InorganicToken {
+            reference: Some(ReferenceTo::new("IRON".to_owned())),
+            ..Default::default()
+        };
+        let dwarf = CreatureToken {
+            reference: Some(ReferenceTo::new("DWARF".to_owned())),
+            webber: Some(MaterialTokenArg {
+                material: MaterialTypeEnum::Inorganic(ReferenceTo::new("IRON".to_owned())),
+            }),
+            gobble_vermin_class: vec![crate::core::Reference("EDIBLE_GROUND_BUG".to_owned())],
+            remove_material: vec![crate::core::Reference("SKIN".to_owned())],
+            remove_tissue: vec![crate::core::Reference("SKIN".to_owned())],
+            ..Default::default()
+        };
+        let beetle = CreatureToken {
+            reference: Some(ReferenceTo::new("BEETLE".to_owned())),
+            creature_class: vec![crate::core::Reference("EDIBLE_GROUND_BUG".to_owned())],
+            select_castes: vec![SelectCaste {
+                remove_material: vec![crate::core::Reference("SKIN".to_owned())],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let mut raws = RawSet::default();
+        raws.extend(
+            [
+                RawObject::Inorganic(iron),
+                RawObject::Creature(dwarf),
+                RawObject::Creature(beetle),
+            ]
+            .into_iter()
+            .map(|object| SourcedObject {
+                source: Source::default(),
+                object,
+            }),
+        );
+        assert!(raws.dangling_references().is_empty());
+
+        let index = raws.reverse_index();
+        let users = |ref_type: &str, id: &str| {
+            index
+                .users_of(&ObjectId::new(ref_type, id))
+                .map(|usage| usage.user.object.id().unwrap_or_default().to_owned())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(users("INORGANIC", "IRON"), ["DWARF"]);
+        assert_eq!(
+            users("CREATURE_CLASS", "EDIBLE_GROUND_BUG"),
+            ["DWARF", "BEETLE"]
+        );
+        assert!(users("CREATURE", "DWARF").is_empty());
+        assert_eq!(users("CREATURE_MAT", "DWARF:SKIN"), ["DWARF"]);
+        assert_eq!(users("CREATURE_MAT", "BEETLE:SKIN"), ["BEETLE"]);
+        assert_eq!(users("CREATURE_TISSUE", "DWARF:SKIN"), ["DWARF"]);
+        assert!(users("CREATURE_MAT", "SKIN").is_empty());
+    }
+    #[test]
+    fn vanilla_references_resolve() {
+        let raws = RawSet::load(["./raw/objects"]);
+        assert!(!raws.is_empty());
+        let dangling: Vec<_> = raws
+            .dangling_references()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert!(dangling.is_empty(), "{:#?}", dangling);
+    }
+}
@@ -0,0 +1,182 @@
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, HashSet};
+
+use crate::core::{ReferenceTo, Referenceable};
+use crate::raw_set::{RawObject, RawSet};
+use crate::structure::*;
+
+/// Looks up objects by type and ID, so references can be resolved.
+///
+/// Objects are registered under their own type, and under their enum for items (`ItemToken`)
+/// and buildings (`BuildingToken`). When an ID is defined twice, the one loaded last wins, the
+/// same way later mods override earlier ones.
+///
+/// ```no_run
+/// let raws = domni::RawSet::load(["./raw/objects"]);
+/// let registry = domni::Registry::new(&raws);
+/// let dwarf = registry.get::<domni::CreatureToken>("DWARF");
+/// ```
+#[derive(Default)]
+pub struct Registry<'a> {
+    objects: HashMap<TypeId, HashMap<&'a str, &'a dyn Any>>,
+    /// IDs by reference type like `CREATURE`, for looking up references without knowing the
+    /// Rust type.
+    ids: HashMap<&'static str, HashSet<&'a str>>,
+}
+
+impl<'a> Registry<'a> {
+    pub fn new(raw_set: &'a RawSet) -> Self {
+        let mut registry = Self::default();
+        for sourced in raw_set.iter() {
+            registry.insert_object(&sourced.object);
+        }
+        registry
+    }
+
+    fn insert_object(&mut self, object: &'a RawObject) {
+        let id = match object.id() {
+            Some(id) => id,
+            None => return,
+        };
+        match object {
+            RawObject::Body(BodyObjectToken::BodyToken(token)) => self.insert(id, token),
+            RawObject::Body(BodyObjectToken::BodyGlossToken(token)) => self.insert(id, token),
+            RawObject::BodyDetailPlan(token) => self.insert(id, token),
+            RawObject::Building(building) => {
+                self.insert(id, building);
+                let (BuildingToken::Workshop(token) | BuildingToken::Furnace(token)) = building;
+                self.insert(id, token);
+            }
+            RawObject::Creature(token) => self.insert(id, token),
+            RawObject::CreatureVariation(token) => self.insert(id, token),
+            RawObject::Color(token) => self.insert(id, token),
+            RawObject::Pattern(token) => self.insert(id, token),
+            RawObject::Shape(token) => self.insert(id, token),
+            RawObject::Entity(token) => self.insert(id, token),
+            RawObject::Graphics(GraphicsToken::TilePage(token)) => self.insert(id, token),
+            // Creature graphics use the ID of the creature they belong to.
+            RawObject::Graphics(GraphicsToken::CreatureGraphics(_)) => {}
+            RawObject::Interaction(token) => self.insert(id, token),
+            RawObject::Inorganic(token) => self.insert(id, token),
+            RawObject::Item(item) => {
+                self.insert(id, item);
+                match item {
+                    ItemToken::AmmoToken(token) => self.insert(id, token),
+                    ItemToken::ArmorToken(token) => self.insert(id, token),
+                    ItemToken::FoodToken(token) => self.insert(id, token),
+                    ItemToken::GlovesToken(token) => self.insert(id, token),
+                    ItemToken::HelmToken(token) => self.insert(id, token),
+                    ItemToken::InstrumentToken(token) => self.insert(id, token),
+                    ItemToken::PantsToken(token) => self.insert(id, token),
+                    ItemToken::ShieldToken(token) => self.insert(id, token),
+                    ItemToken::ShoesToken(token) => self.insert(id, token),
+                    ItemToken::SiegeAmmoToken(token) => self.insert(id, token),
+                    ItemToken::ToolToken(token) => self.insert(id, token),
+                    ItemToken::ToyToken(token) => self.insert(id, token),
+                    ItemToken::TrapCompToken(token) => self.insert(id, token),
+                    ItemToken::WeaponToken(token) => self.insert(id, token),
+                }
+            }
+            RawObject::Language(LanguageToken::WordToken(token)) => self.insert(id, token),
+            RawObject::Language(LanguageToken::SymbolToken(token)) => self.insert(id, token),
+            RawObject::Language(LanguageToken::TranslationToken(token)) => self.insert(id, token),
+            RawObject::MaterialTemplate(token) => self.insert(id, token),
+            RawObject::Plant(token) => self.insert(id, token),
+            RawObject::Reaction(token) => self.insert(id, token),
+            RawObject::TissueTemplate(token) => self.insert(id, token),
+        }
+    }
+
+    /// Registers an object, replacing any earlier object of the same type and ID.
+    pub fn insert<T: Referenceable + 'static>(&mut self, id: &'a str, object: &'a T) {
+        self.objects
+            .entry(TypeId::of::<T>())
+            .or_default()
+            .insert(id, object);
+        self.ids.entry(T::get_ref_type()).or_default().insert(id);
+    }
+
+    pub fn get<T: Referenceable + 'static>(&self, id: &str) -> Option<&'a T> {
+        self.objects
+            .get(&TypeId::of::<T>())?
+            .get(id)?
+            .downcast_ref::<T>()
+    }
+
+    /// All registered objects of one type, in no particular order.
+    pub fn all<T: Referenceable + 'static>(&self) -> impl Iterator<Item = (&'a str, &'a T)> + '_ {
+        self.objects
+            .get(&TypeId::of::<T>())
+            .into_iter()
+            .flatten()
+            .filter_map(|(id, object)| Some((*id, object.downcast_ref::<T>()?)))
+    }
+
+    pub fn contains<T: Referenceable + 'static>(&self, id: &str) -> bool {
+        self.get::<T>(id).is_some()
+    }
+
+    /// Checks for an ID by reference type, e.g. `contains_id("ITEM_WEAPON", "ITEM_WEAPON_PICK")`.
+    pub fn contains_id(&self, ref_type: &str, id: &str) -> bool {
+        self.ids.get(ref_type).is_some_and(|ids| ids.contains(id))
+    }
+}
+
+impl<T: Referenceable + 'static> ReferenceTo<T> {
+    /// Finds the object this reference points to.
+    pub fn resolve<'a>(&self, registry: &Registry<'a>) -> Option<&'a T> {
+        registry.get::<T>(&self.0)
+    }
+}
+
+impl RawSet {
+    pub fn registry(&self) -> Registry<'_> {
+        Registry::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn registry_resolve() {
+        let creature = CreatureToken {
+            reference: Some(ReferenceTo::new("DWARF".to_owned())),
+            ..Default::default()
+        };
+        let weapon = WeaponToken {
+            reference: Some(ReferenceTo::new("ITEM_WEAPON_AXE_BATTLE".to_owned())),
+            ..Default::default()
+        };
+        let mut raws = RawSet::default();
+        raws.extend(
+            [
+                RawObject::Creature(creature),
+                RawObject::Item(ItemToken::WeaponToken(weapon)),
+            ]
+            .into_iter()
+            .map(|object| SourcedObject {
+                source: Source::default(),
+                object,
+            }),
+        );
+        let registry = raws.registry();
+
+        let dwarf = ReferenceTo::<CreatureToken>::new("DWARF".to_owned());
+        assert!(dwarf.resolve(&registry).is_some());
+        assert!(ReferenceTo::<CreatureToken>::new("ELF".to_owned())
+            .resolve(&registry)
+            .is_none());
+        let axe = "ITEM_WEAPON_AXE_BATTLE".to_owned();
+        assert!(ReferenceTo::<WeaponToken>::new(axe.clone())
+            .resolve(&registry)
+            .is_some());
+        assert!(ReferenceTo::<ItemToken>::new(axe.clone())
+            .resolve(&registry)
+            .is_some());
+        assert!(ReferenceTo::<ArmorToken>::new(axe).resolve(&registry).is_none());
+        assert_eq!(ReferenceTo::<CreatureToken>::get_type(), "CREATURE");
+        assert_eq!(ReferenceTo::<WeaponToken>::get_type(), "ITEM_WEAPON");
+    }
+}
@@ -0,0 +1,261 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::structure::DFRaw;
+
+/// The outcome of parsing a single raw file: the (possibly partial) `DFRaw` and every
+/// diagnostic the lexer and syntax analysis produced for it.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ParseReport {
+    /// The file the source was read from, if known.
+    pub path: Option<PathBuf>,
+    /// The parsed raw. When there are errors this only holds what could be recovered.
+    pub raw: DFRaw,
+    /// All diagnostics, lexical ones first, each group in the order they were reported.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ParseReport {
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+
+    pub fn into_raw(self) -> DFRaw {
+        self.raw
+    }
+}
+
+impl fmt::Display for ParseReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let file = self
+            .path
+            .as_ref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| "<source>".to_owned());
+        write!(f, "{} has {} diagnostic(s)", file, self.diagnostics.len())?;
+        for diagnostic in &self.diagnostics {
+            write!(f, "\n  {}:{}", file, diagnostic)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseReport {}
+
+/// A single problem found in a raw file.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Which analysis pass reported the problem.
+    pub stage: DiagnosticStage,
+    pub severity: Severity,
+    /// The df_ls diagnostic code, e.g. `unknown_token`.
+    pub code: Option<String>,
+    pub message: String,
+    pub span: Span,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {:?}",
+            self.span.start.line + 1,
+            self.span.start.column + 1,
+            self.severity
+        )?;
+        if let Some(code) = &self.code {
+            write!(f, "[{}]", code)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DiagnosticStage {
+    Lexical,
+    Syntax,
+}
+
+/// Same levels as the Language Server Protocol uses.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+    Information,
+    Hint,
+}
+
+/// A range in the source, `start` inclusive and `end` exclusive.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub start: Location,
+    pub end: Location,
+}
+
+impl Span {
+    pub fn byte_range(&self) -> std::ops::Range<usize> {
+        self.start.byte..self.end.byte
+    }
+}
+
+/// A position in the source. `line` and `column` are 0-based, `column` counts characters.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Location {
+    pub byte: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+// region: Conversion from df_ls (LSP) diagnostics ===============================================
+// df_ls reports plain `lsp_types::Diagnostic`s. Only the shape matters to us, so they are read
+// through their serde representation instead of depending on `lsp_types` directly.
+
+#[derive(Deserialize)]
+struct LspDiagnostic {
+    range: LspRange,
+    severity: Option<u8>,
+    code: Option<LspCode>,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct LspRange {
+    start: LspPosition,
+    end: LspPosition,
+}
+
+#[derive(Deserialize)]
+struct LspPosition {
+    line: usize,
+    /// UTF-16 code units, as the LSP specifies.
+    character: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LspCode {
+    Number(i64),
+    String(String),
+}
+
+pub(crate) fn convert_diagnostics<D: Serialize + Sync>(
+    stage: DiagnosticStage,
+    diagnostics: &D,
+    source: &str,
+) -> anyhow::Result<Vec<Diagnostic>> {
+    let diagnostics: Vec<LspDiagnostic> = crate::transmute(diagnostics)?;
+    let lines = LineIndex::new(source);
+    Ok(diagnostics
+        .into_iter()
+        .map(|diagnostic| Diagnostic {
+            stage,
+            severity: match diagnostic.severity {
+                Some(2) => Severity::Warning,
+                Some(3) => Severity::Information,
+                Some(4) => Severity::Hint,
+                _ => Severity::Error,
+            },
+            code: diagnostic.code.map(|code| match code {
+                LspCode::Number(number) => number.to_string(),
+                LspCode::String(string) => string,
+            }),
+            message: diagnostic.message,
+            span: Span {
+                start: lines.locate(&diagnostic.range.start),
+                end: lines.locate(&diagnostic.range.end),
+            },
+        })
+        .collect())
+}
+
+/// Maps LSP positions back to byte offsets.
+struct LineIndex<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    fn new(source: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    fn locate(&self, position: &LspPosition) -> Location {
+        let line_start = match self.line_starts.get(position.line) {
+            Some(start) => *start,
+            None => {
+                return Location {
+                    byte: self.source.len(),
+                    line: position.line,
+                    column: position.character,
+                }
+            }
+        };
+        let line = self.source[line_start..]
+            .split('\n')
+            .next()
+            .unwrap_or_default();
+        let mut utf16 = 0;
+        let mut column = 0;
+        let mut byte = line_start + line.len();
+        for (offset, c) in line.char_indices() {
+            if utf16 >= position.character {
+                byte = line_start + offset;
+                break;
+            }
+            utf16 += c.len_utf16();
+            column += 1;
+        }
+        Location {
+            byte,
+            line: position.line,
+            column,
+        }
+    }
+}
+// endregion ======================================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn diagnostic_spans() -> Result<()> {
+        let source = "creature_a\n\n[OBJECT:CREATURE]\n[CREATURE:ÄTHER][BAD_TOKEN]\n";
+        let lsp = serde_json::json!([{
+            "range": {
+                "start": { "line": 3, "character": 16 },
+                "end": { "line": 3, "character": 27 }
+            },
+            "severity": 2,
+            "code": "unknown_token",
+            "message": "Unknown token"
+        }]);
+        let diagnostics = super::convert_diagnostics(DiagnosticStage::Syntax, &lsp, source)?;
+        assert_eq!(diagnostics.len(), 1);
+        let diagnostic = &diagnostics[0];
+        assert_eq!(diagnostic.severity, Severity::Warning);
+        assert_eq!(diagnostic.code.as_deref(), Some("unknown_token"));
+        assert_eq!(diagnostic.span.start.column, 16);
+        assert_eq!(&source[diagnostic.span.byte_range()], "[BAD_TOKEN]");
+        Ok(())
+    }
+}
@@ -0,0 +1,227 @@
+//! Body part and tissue layer selections evaluated against an assembled body: `SET_BP_GROUP`,
+//! `SET_TL_GROUP`, `SELECT_TISSUE_LAYER`, `TISSUE_LAYER` and syndrome `BP` targets.
+
+use serde::{Deserialize, Serialize};
+
+use crate::body::{BodyGraph, BodyLayer};
+use crate::core::{Choose, Reference};
+use crate::structure::{
+    BpCriteriaTokenArg, CeXTokenArg, PositionEnum, SelectTissueLayer, SetBpGroup, SetTlGroup,
+    TissueLayer,
+};
+
+/// A tissue layer of an assembled body.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayerIndex {
+    /// The index of the part in [`BodyGraph::parts`].
+    pub part: usize,
+    /// The index of the layer in [`BodyPart::layers`](crate::BodyPart::layers).
+    pub layer: usize,
+}
+
+impl BodyGraph {
+    /// Every part matching any of the criteria, in body order.
+    pub fn select_any<'a>(
+        &self,
+        criteria: impl IntoIterator<Item = &'a BpCriteriaTokenArg>,
+    ) -> Vec<usize> {
+        let mut parts: Vec<usize> = criteria
+            .into_iter()
+            .flat_map(|criteria| self.select(criteria))
+            .collect();
+        parts.sort_unstable();
+        parts.dedup();
+        parts
+    }
+
+    /// The layers of the parts matching the criteria made of the given local tissue, or every
+    /// layer of them for `ALL`. With a position, only layers with that position match.
+    pub fn select_layers(
+        &self,
+        criteria: &BpCriteriaTokenArg,
+        tissue: &str,
+        position: Option<&PositionEnum>,
+    ) -> Vec<LayerIndex> {
+        self.select(criteria)
+            .into_iter()
+            .flat_map(|part| {
+                self.parts[part]
+                    .layers
+                    .iter()
+                    .enumerate()
+                    .filter(move |(_, layer)| tissue == "ALL" || layer.tissue == tissue)
+                    .filter(move |(_, layer)| {
+                        position.is_none() || layer.position.as_ref() == position
+                    })
+                    .map(move |(layer, _)| LayerIndex { part, layer })
+            })
+            .collect()
+    }
+
+    /// The parts selected by `SET_BP_GROUP` and its `PLUS_BP_GROUP` tokens.
+    pub fn select_bp_group(&self, group: &SetBpGroup) -> Vec<usize> {
+        self.select_any(group.set_bp_group.iter().chain(&group.plus_bp_group))
+    }
+
+    /// The layers selected by `SET_TL_GROUP` and its `PLUS_TL_GROUP` tokens.
+    pub fn select_tl_group(&self, group: &SetTlGroup) -> Vec<LayerIndex> {
+        let selectors = group.set_tl_group.iter().chain(&group.plus_tl_group);
+        let layers = selectors
+            .flat_map(|(criteria, Reference(tissue))| self.select_layers(criteria, tissue, None));
+        sorted(layers.collect())
+    }
+
+    /// The layers selected by `SELECT_TISSUE_LAYER` and its `PLUS_TISSUE_LAYER` tokens.
+    /// `SELECT_TISSUE_LAYER:ALL` without criteria selects every layer of the body.
+    pub fn select_tissue_layer(&self, select: &SelectTissueLayer) -> Vec<LayerIndex> {
+        let mut layers = match &select.select_tissue_layer {
+            None => vec![],
+            Some(Choose::Choice1((_, None))) => self.all_layers().collect(),
+            Some(Choose::Choice1((_, Some((criteria, position))))) => {
+                self.select_layers(criteria, "ALL", position.as_ref())
+            }
+            Some(Choose::Choice2((Reference(tissue), criteria, position))) => {
+                self.select_layers(criteria, tissue, position.as_ref())
+            }
+        };
+        for (Reference(tissue), criteria) in &select.plus_tissue_layer {
+            layers.extend(self.select_layers(criteria, tissue, None));
+        }
+        sorted(layers)
+    }
+
+    /// Adds the layer of a `TISSUE_LAYER` token to the outside of every part its criteria match,
+    /// returning the new layers along with those its `PLUS_TISSUE_LAYER` tokens select.
+    pub fn add_tissue_layer(&mut self, tissue_layer: &TissueLayer) -> Vec<LayerIndex> {
+        let mut layers = vec![];
+        if let Some((criteria, Reference(tissue), placement)) = &tissue_layer.tissue_layer {
+            for part in self.select(criteria) {
+                let (position, relation) = match placement {
+                    None | Some(Choose::Choice1(_)) => (None, None),
+                    Some(Choose::Choice2(Choose::Choice1(position))) => {
+                        (Some(position.clone()), None)
+                    }
+                    Some(Choose::Choice2(Choose::Choice2((relation, target, extent)))) => (
+                        None,
+                        Some(self.layer_relation(part, relation, target, *extent)),
+                    ),
+                };
+                let part_layers = &mut self.parts[part].layers;
+                part_layers.push(BodyLayer {
+                    tissue: tissue.clone(),
+                    thickness: 1,
+                    position,
+                    relation,
+                });
+                layers.push(LayerIndex {
+                    part,
+                    layer: part_layers.len() - 1,
+                });
+            }
+        }
+        for (Reference(tissue), criteria) in &tissue_layer.plus_tissue_layer {
+            layers.extend(self.select_layers(criteria, tissue, None));
+        }
+        sorted(layers)
+    }
+
+    /// The layers a syndrome effect acts on through its `BP` targets. Effects without targets
+    /// act on the whole creature and select nothing here.
+    pub fn syndrome_targets(&self, effect: &CeXTokenArg) -> Vec<LayerIndex> {
+        let layers = effect
+            .bp
+            .iter()
+            .flat_map(|(criteria, Reference(tissue))| self.select_layers(criteria, tissue, None));
+        sorted(layers.collect())
+    }
+
+    fn all_layers(&self) -> impl Iterator<Item = LayerIndex> + '_ {
+        self.parts.iter().enumerate().flat_map(|(part, body_part)| {
+            (0..body_part.layers.len()).map(move |layer| LayerIndex { part, layer })
+        })
+    }
+}
+
+/// Puts selected layers in body order, without duplicates.
+fn sorted(mut layers: Vec<LayerIndex>) -> Vec<LayerIndex> {
+    layers.sort_unstable();
+    layers.dedup();
+    layers
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::body::fixtures::{by_category, part};
+    use crate::*;
+
+    #[test]
+    fn tissue_layer_selection() {
+        use crate::core::{Choose, Reference};
+        let body = BodyToken {
+            reference: Some(ReferenceTo::new("HEAD_EYES".to_owned())),
+            bp: vec![
+                part("HEAD", "HEAD", None),
+                BodyPartToken {
+                    sight: Some(()),
+                    ..part("REYE", "EYE", Some("HEAD"))
+                },
+                BodyPartToken {
+                    sight: Some(()),
+                    ..part("LEYE", "EYE", Some("HEAD"))
+                },
+            ],
+        };
+        let at = |part, layer| LayerIndex { part, layer };
+        let mut graph = BodyGraph::new(&[&body], &[]);
+        let skin = TissueLayer {
+            tissue_layer: Some((by_category("ALL"), Reference("SKIN".to_owned()), None)),
+            ..Default::default()
+        };
+        assert_eq!(graph.add_tissue_layer(&skin), [at(0, 0), at(1, 0), at(2, 0)]);
+        let hair = TissueLayer {
+            tissue_layer: Some((
+                by_category("HEAD"),
+                Reference("HAIR".to_owned()),
+                Some(Choose::Choice2(Choose::Choice1(PositionEnum::Top))),
+            )),
+            ..Default::default()
+        };
+        assert_eq!(graph.add_tissue_layer(&hair), [at(0, 1)]);
+
+        let group = SetBpGroup {
+            set_bp_group: Some(BpCriteriaTokenArg::ByToken(Reference("REYE".to_owned()))),
+            plus_bp_group: vec![by_category("HEAD")],
+            ..Default::default()
+        };
+        assert_eq!(graph.select_bp_group(&group), [0, 1]);
+        let group = SetTlGroup {
+            set_tl_group: Some((
+                BpCriteriaTokenArg::ByType(BodyPartTypeEnum::Sight),
+                Reference("SKIN".to_owned()),
+            )),
+            plus_tl_group: vec![(by_category("HEAD"), Reference("HAIR".to_owned()))],
+            ..Default::default()
+        };
+        assert_eq!(graph.select_tl_group(&group), [at(0, 1), at(1, 0), at(2, 0)]);
+        let select = |tissue: &str, position| SelectTissueLayer {
+            select_tissue_layer: Some(Choose::Choice2((
+                Reference(tissue.to_owned()),
+                by_category("HEAD"),
+                position,
+            ))),
+            ..Default::default()
+        };
+        assert_eq!(graph.select_tissue_layer(&select("HAIR", Some(PositionEnum::Top))), [at(0, 1)]);
+        assert_eq!(graph.select_tissue_layer(&select("HAIR", Some(PositionEnum::Front))), []);
+        let all = SelectTissueLayer {
+            select_tissue_layer: Some(Choose::Choice1((AllEnum::All, None))),
+            ..Default::default()
+        };
+        assert_eq!(graph.select_tissue_layer(&all).len(), 4);
+        let effect = CeXTokenArg {
+            bp: vec![(by_category("EYE"), Reference("ALL".to_owned()))],
+            ..Default::default()
+        };
+        assert_eq!(graph.syndrome_targets(&effect), [at(1, 0), at(2, 0)]);
+    }
+}
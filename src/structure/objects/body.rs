@@ -1,4 +1,4 @@
-use crate::core::{Choose, Reference, ReferenceTo, Flag};
+use crate::core::{Choose, Reference, ReferenceTo, Flag, impl_referenceable};
 
 use serde::{Deserialize, Serialize};
 
@@ -26,6 +26,8 @@ pub struct BodyGlossToken {
     pub bodygloss: Option<(ReferenceTo<Self>, String, String, String, String)>,
 }
 
+impl_referenceable!(BodyGlossToken, "BODYGLOSS", bodygloss.0);
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct BodyToken {
@@ -37,6 +39,8 @@ pub struct BodyToken {
     pub bp: Vec<BodyPartToken>,
 }
 
+impl_referenceable!(BodyToken, "BODY");
+
 /// "STP stands for "Standard Plural" and it just adds an 's' to the singular word to save some
 /// typing. If you don't add something in that slot, the body part won't even load."
 ///
@@ -247,7 +251,7 @@ pub struct BodyPartToken {
 }
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum ConTypeEnum {
     /// Flags the body part as being able to wear upper body clothing like coats, breastplates etc.
@@ -257,6 +261,7 @@ pub enum ConTypeEnum {
     /// All default creatures with bodies have the upper body as the root of the body tree, making
     /// it impossible to chop off.
     #[serde(alias = "UPPERBODY")]
+    #[default]
     Upperbody,
     /// Flags the body part as being able to wear lower body clothing like skirts, pants, etc.
     ///
@@ -287,8 +292,3 @@ pub enum ConTypeEnum {
     #[serde(alias = "STANCE")]
     Stance,
 }
-impl Default for ConTypeEnum {
-    fn default() -> Self {
-        Self::Upperbody
-    }
-}
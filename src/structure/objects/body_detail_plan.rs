@@ -1,4 +1,4 @@
-use crate::core::{Choose, Reference, ReferenceTo};
+use crate::core::{Choose, Reference, ReferenceTo, impl_referenceable};
 
 use serde::{Deserialize, Serialize};
 
@@ -60,11 +60,14 @@ pub struct BodyDetailPlanToken {
     pub bp_relsize: Vec<(BpCriteriaTokenArg, u32)>,
 }
 
+impl_referenceable!(BodyDetailPlanToken, "BODY_DETAIL_PLAN");
+
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum PositionEnum {
     #[serde(alias = "FRONT")]
+    #[default]
     Front,
     #[serde(alias = "BACK")]
     Back,
@@ -77,18 +80,14 @@ pub enum PositionEnum {
     #[serde(alias = "BOTTOM")]
     Bottom,
 }
-impl Default for PositionEnum {
-    fn default() -> Self {
-        Self::Front
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum BpRelationEnum {
     /// Used to specify that the previously defined body part surrounds the following body part.
     #[serde(alias = "AROUND")]
+    #[default]
     Around,
     /// Used to specify that the previously defined body part is surrounded by the following body part.
     #[serde(alias = "SURROUNDED_BY")]
@@ -114,21 +113,17 @@ pub enum BpRelationEnum {
     #[serde(alias = "CLEANED_BY")]
     CleanedBy,
 }
-impl Default for BpRelationEnum {
-    fn default() -> Self {
-        Self::Around
-    }
-}
 
 // region: BP_LAYERS args =========================================================================
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum ArgEnum {
     /// Placeholder for the first tissue type fed into this body detail plan.
     /// For example, dwarves send into the `VERTEBRATE_TISSUE_LAYERS` body detail plan,
     /// the tissue `SKIN` for `ARG1`.
     #[serde(alias = "ARG1")]
+    #[default]
     Arg1,
     /// Placeholder for the second tissue type fed into this body detail plan.
     /// For example, dwarves send into the `VERTEBRATE_TISSUE_LAYERS` body detail plan,
@@ -151,11 +146,6 @@ pub enum ArgEnum {
     #[serde(alias = "ARG5")]
     Arg5,
 }
-impl Default for ArgEnum {
-    fn default() -> Self {
-        Self::Arg1
-    }
-}
 
 // TODO: research; can you have more than 5 of these total in one BP_LAYERS?
 #[serde_with::skip_serializing_none]
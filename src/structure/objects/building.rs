@@ -1,4 +1,6 @@
-use crate::core::{Choose, Clamp, DFChar, Reference, ReferenceTo, Flag};
+use crate::core::{
+    Choose, Clamp, DFChar, Reference, ReferenceTo, Flag, impl_referenceable, Referenceable,
+};
 use crate::structure::{ItemReferenceArg, KeyBindEnum, LaborEnum, MaterialTokenArg, NoneEnum};
 
 use serde::{Deserialize, Serialize};
@@ -17,6 +19,21 @@ impl Default for BuildingToken {
     }
 }
 
+/// Workshops and furnaces share their IDs.
+impl Referenceable for BuildingToken {
+    fn get_reference(&self) -> Option<ReferenceTo<Self>> {
+        let (BuildingToken::Workshop(token) | BuildingToken::Furnace(token)) = self;
+        token
+            .reference
+            .as_ref()
+            .map(|reference| ReferenceTo::new(reference.0.clone()))
+    }
+
+    fn get_ref_type() -> &'static str {
+        "BUILDING"
+    }
+}
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct BuildingGeneralToken {
@@ -106,6 +123,8 @@ pub struct BuildingGeneralToken {
     pub needs_magma: Flag,
 }
 
+impl_referenceable!(BuildingGeneralToken, "BUILDING");
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct BuildItemToken {
@@ -238,15 +257,10 @@ pub struct BuildItemToken {
 }
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum MatEnum {
     #[serde(alias = "MAT")]
+    #[default]
     Mat,
 }
-
-impl Default for MatEnum {
-    fn default() -> Self {
-        Self::Mat
-    }
-}
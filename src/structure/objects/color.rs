@@ -1,4 +1,4 @@
-use crate::core::ReferenceTo;
+use crate::core::{ReferenceTo, impl_referenceable};
 
 use serde::{Deserialize, Serialize};
 
@@ -17,3 +17,5 @@ pub struct ColorToken {
     #[serde(alias = "RGB")]
     pub rgb: Option<(u8, u8, u8)>,
 }
+
+impl_referenceable!(ColorToken, "COLOR");
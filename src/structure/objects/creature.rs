@@ -1,4 +1,6 @@
-use crate::core::{AllowEmpty, Any, Choose, Clamp, DFChar, Reference, ReferenceTo, Flag};
+use crate::core::{
+    AllowEmpty, Any, Choose, Clamp, DFChar, Reference, ReferenceTo, Flag, impl_referenceable,
+};
 
 use serde::{Deserialize, Serialize};
 
@@ -1962,3 +1964,5 @@ pub struct CreatureToken {
     #[serde(alias = "CV_CONVERT_TAG")]
     pub cv_convert_tag: Vec<CreatureCvConvertTag>,
 }
+
+impl_referenceable!(CreatureToken, "CREATURE");
@@ -87,19 +87,15 @@ pub struct Attack {
 }
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum AttackPriorityEnum {
     #[serde(alias = "MAIN")]
+    #[default]
     Main,
     #[serde(alias = "SECOND")]
     Second,
 }
-impl Default for AttackPriorityEnum {
-    fn default() -> Self {
-        Self::Main
-    }
-}
 
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -5,53 +5,42 @@ use serde::{Deserialize, Serialize};
 use crate::structure::{CreatureToken, PlantToken};
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum SingularOrPluralEnum {
     #[serde(alias = "SINGULAR")]
+    #[default]
     Singular,
     #[serde(alias = "PLURAL")]
     Plural,
 }
-impl Default for SingularOrPluralEnum {
-    fn default() -> Self {
-        Self::Singular
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum AlertOrPeacefulIntermittentEnum {
     #[serde(alias = "ALERT")]
+    #[default]
     Alert,
     #[serde(alias = "PEACEFUL_INTERMITTENT")]
     PeacefulIntermittent,
 }
-impl Default for AlertOrPeacefulIntermittentEnum {
-    fn default() -> Self {
-        Self::Alert
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum VocalizationEnum {
     #[serde(alias = "VOCALIZATION")]
+    #[default]
     Vocalization,
 }
-impl Default for VocalizationEnum {
-    fn default() -> Self {
-        Self::Vocalization
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum TissueModifierEnum {
     #[serde(alias = "LENGTH")]
+    #[default]
     Length,
     #[serde(alias = "DENSE")]
     Dense,
@@ -64,35 +53,27 @@ pub enum TissueModifierEnum {
     #[serde(alias = "WRINKLY")]
     Wrinkly,
 }
-impl Default for TissueModifierEnum {
-    fn default() -> Self {
-        Self::Length
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum AppGeneticModelEnum {
     #[serde(alias = "DOMINANT_MORE")]
+    #[default]
     DominantMore,
     #[serde(alias = "DOMINANT_LESS")]
     DominantLess,
     #[serde(alias = "MIX")]
     Mix,
 }
-impl Default for AppGeneticModelEnum {
-    fn default() -> Self {
-        Self::DominantMore
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum SecretionTriggerEnum {
     /// Secretion occurs once every 40 ticks in fortress mode, and every tick in adventurer mode.
     #[serde(alias = "CONTINUOUS")]
+    #[default]
     Continuous,
     /// Secretion occurs continuously (every 40 ticks in fortress mode, and every tick in adventurer
     /// mode) whilst the creature is at minimum `Tired` following physical exertion. Note that this
@@ -104,30 +85,22 @@ pub enum SecretionTriggerEnum {
     #[serde(alias = "EXTREME_EMOTION")]
     ExtremeEmotion,
 }
-impl Default for SecretionTriggerEnum {
-    fn default() -> Self {
-        Self::Continuous
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum LairCharacteristicEnum {
     #[serde(alias = "HAS_DOORS")]
+    #[default]
     HasDoors,
 }
-impl Default for LairCharacteristicEnum {
-    fn default() -> Self {
-        Self::HasDoors
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum LairTypeEnum {
     #[serde(alias = "SIMPLE_BURROW")]
+    #[default]
     SimpleBurrow,
     #[serde(alias = "SIMPLE_MOUND")]
     SimpleMound,
@@ -138,30 +111,22 @@ pub enum LairTypeEnum {
     #[serde(alias = "LABYRINTH")]
     Labyrinth,
 }
-impl Default for LairTypeEnum {
-    fn default() -> Self {
-        Self::SimpleBurrow
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum TestAllEnum {
     #[serde(alias = "TEST_ALL")]
+    #[default]
     TestAll,
 }
-impl Default for TestAllEnum {
-    fn default() -> Self {
-        Self::TestAll
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum HabitTypeEnum {
     #[serde(alias = "COLLECT_TROPHIES")]
+    #[default]
     CollectTrophies,
     #[serde(alias = "COOK_PEOPLE")]
     CookPeople,
@@ -182,81 +147,56 @@ pub enum HabitTypeEnum {
     #[serde(alias = "COLLECT_WEALTH")]
     CollectWealth,
 }
-impl Default for HabitTypeEnum {
-    fn default() -> Self {
-        Self::CollectTrophies
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum AnyHardStoneEnum {
     #[serde(alias = "ANY_HARD_STONE")]
+    #[default]
     AnyHardStone,
 }
-impl Default for AnyHardStoneEnum {
-    fn default() -> Self {
-        Self::AnyHardStone
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum RootEnum {
     #[serde(alias = "ROOT")]
+    #[default]
     Root,
 }
-impl Default for RootEnum {
-    fn default() -> Self {
-        Self::Root
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum TimescaleEnum {
     #[serde(alias = "DAILY")]
+    #[default]
     Daily,
     #[serde(alias = "YEARLY")]
     Yearly,
 }
-impl Default for TimescaleEnum {
-    fn default() -> Self {
-        Self::Daily
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum NoEndEnum {
     #[serde(alias = "NO_END")]
+    #[default]
     NoEnd,
 }
-impl Default for NoEndEnum {
-    fn default() -> Self {
-        Self::NoEnd
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum NormalEnum {
     /// _"Normal just skips the same was as none does. Could be an old compatibility thing? No idea."_
     ///
     /// -- [Toady](http://www.bay12forums.com/smf/index.php?topic=169696.msg8292042#msg8292042)
     #[serde(alias = "NORMAL")]
+    #[default]
     Normal,
 }
-impl Default for NormalEnum {
-    fn default() -> Self {
-        Self::Normal
-    }
-}
 
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use crate::core::Flag;
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum GaitTypeEnum {
     /// Used for moving normally over flat ground tiles, and up and down ramps and stairs.
@@ -11,6 +11,7 @@ pub enum GaitTypeEnum {
     /// Walking gaits are land-based gaits which require the creature to be standing up,
     /// and have more than half of their `[STANCE]` body parts, e.g. legs, intact and working.
     #[serde(alias = "WALK")]
+    #[default]
     Walk,
     /// Used for moving over ground tiles whilst
     /// [prone](https://dwarffortresswiki.org/index.php/Status_icon#Non-flashing).
@@ -50,14 +51,9 @@ pub enum GaitTypeEnum {
     #[serde(alias = "CLIMB")]
     Climb,
 }
-impl Default for GaitTypeEnum {
-    fn default() -> Self {
-        Self::Walk
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum NoBuildUpEnum {
     /// Can be specified instead of a `<start speed>` value to make the `<max speed>` instantly
@@ -68,13 +64,9 @@ pub enum NoBuildUpEnum {
     /// turning, and `<max turning speed>` cannot exceed `<max speed>`) so they should both be
     /// emitted when using `NO_BUILD_UP`.
     #[serde(alias = "NO_BUILD_UP")]
+    #[default]
     NoBuildUp,
 }
-impl Default for NoBuildUpEnum {
-    fn default() -> Self {
-        Self::NoBuildUp
-    }
-}
 
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
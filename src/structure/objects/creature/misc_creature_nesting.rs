@@ -118,6 +118,9 @@ pub struct BodyAppearanceModifier {
 }
 
 /// Begins a selection of body parts.
+///
+/// See [`BodyGraph::select_bp_group`](crate::BodyGraph::select_bp_group) for the parts it selects
+/// on an assembled body.
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct SetBpGroup {
@@ -195,6 +198,9 @@ pub struct BpAppearanceModifier {
 }
 
 /// Begins a selection of tissue layers.
+///
+/// See [`BodyGraph::select_tl_group`](crate::BodyGraph::select_tl_group) for the layers it selects
+/// on an assembled body.
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct SetTlGroup {
@@ -321,6 +327,9 @@ pub struct TissueStyleUnit {
 ///
 /// Non-argument Locations can be `FRONT`, `RIGHT`, `LEFT`, `TOP`, `BOTTOM`. Argument locations
 /// are `AROUND` and `CLEANS`, requiring a further body part and a % of coverage/cleansing
+///
+/// See [`BodyGraph::add_tissue_layer`](crate::BodyGraph::add_tissue_layer) for how it is added to
+/// an assembled body.
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct TissueLayer {
@@ -328,8 +337,7 @@ pub struct TissueLayer {
     pub tissue_layer: Option<(
         BpCriteriaTokenArg,
         Reference,
-        // TODO: investigate how all these tokens dealing with selecting tissues and BPs actually work,
-        // this is too many edge cases, it's ridiculous.
+        // Where the layer goes on each part, see `BodyGraph::add_tissue_layer` in `selector.rs`.
         Option<
             Choose<
                 NormalEnum,
@@ -365,21 +373,23 @@ pub struct TissueLayer {
 }
 
 /// Selects a tissue layer for descriptor and cosmetic purposes.
+///
+/// See [`BodyGraph::select_tissue_layer`](crate::BodyGraph::select_tissue_layer) for the layers it
+/// selects on an assembled body.
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct SelectTissueLayer {
     /// Arguments for `[SELECT_TISSUE_LAYER:...]`
     #[serde(alias = "SELECT_TISSUE_LAYER")]
-    // TODO: investigate how all these tokens dealing with selecting tissues and BPs actually work,
-    // this is too many edge cases, it's ridiculous.
+    // What this selects on a body, see `BodyGraph::select_tissue_layer` in `selector.rs`.
     pub select_tissue_layer: Option<
         Choose<
             (AllEnum, Option<(BpCriteriaTokenArg, Option<PositionEnum>)>),
             (Reference, BpCriteriaTokenArg, Option<PositionEnum>),
         >,
     >,
-    /// Adds a tissue to those selected
-    #[serde(alias = "PLUS_TISSUE_LAYER")] // TODO nest tissue select
+    /// Adds a tissue to those selected by the `SELECT_TISSUE_LAYER` it is nested under.
+    #[serde(alias = "PLUS_TISSUE_LAYER")]
     pub plus_tissue_layer: Vec<(Reference, BpCriteriaTokenArg)>,
     /// Sets a selected tissue layer to be made of a different tissue.
     #[serde(alias = "SET_LAYER_TISSUE")]
@@ -1,8 +1,9 @@
-use crate::core::{AllowEmpty, Any, Reference, ReferenceTo, Flag};
+use crate::core::{AllowEmpty, Any, Reference, ReferenceTo, Flag, impl_referenceable};
 
 use serde::{Deserialize, Serialize};
 
-// TODO: for all of these, support `!ARG` tokens, up to `!ARG65537` at least
+// `!ARG` placeholders are kept as written; they are filled in when the variation is applied,
+// see `crate::CreatureVariation`.
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct CreatureVariationToken {
@@ -151,6 +152,8 @@ pub struct CreatureVariationToken {
     pub cv_convert_ctag: Vec<CvConvertCTag>,
 }
 
+impl_referenceable!(CreatureVariationToken, "CREATURE_VARIATION");
+
 /// Starts a "conversion block" to modify the arguments of existing tokens on a creature.
 /// A conversion block contains one `CVCT_MASTER`, one `CVCT_TARGET`, and one `CVCT_REPLACEMENT`
 /// (note, `CVCT_REPLACEMENT` is optional, and leaving it out may be used to erase parts of
@@ -1,4 +1,4 @@
-use crate::core::{Choose, Clamp, Reference, ReferenceTo, Flag};
+use crate::core::{Choose, Clamp, Reference, ReferenceTo, Flag, impl_referenceable};
 
 use serde::{Deserialize, Serialize};
 
@@ -565,23 +565,18 @@ pub struct EntityToken {
     /// this item. The default rarity is equalvalent to `COMMON` (50% chance).
     #[serde(alias = "PANTS")]
     pub pants: Vec<(ReferenceTo<PantsToken>, Option<RarityEnum>)>,
-    ///
     #[serde(alias = "SHIELD")]
     pub shield: Vec<ReferenceTo<ShieldToken>>,
     /// Specify an item this entity type can use as shoes. You can optionally specify a rarity for
     /// this item. The default rarity is equalvalent to `COMMON` (50% chance).
     #[serde(alias = "SHOES")]
     pub shoes: Vec<(ReferenceTo<ShoesToken>, Option<RarityEnum>)>,
-    ///
     #[serde(alias = "SIEGEAMMO")]
     pub siegeammo: Vec<ReferenceTo<SiegeAmmoToken>>,
-    ///
     #[serde(alias = "TOOL")]
     pub tool: Vec<ReferenceTo<ToolToken>>,
-    ///
     #[serde(alias = "TOY")]
     pub toy: Vec<ReferenceTo<ToyToken>>,
-    ///
     #[serde(alias = "TRAPCOMP")]
     pub trapcomp: Vec<ReferenceTo<TrapCompToken>>,
     // endregion ===================================================================
@@ -757,6 +752,8 @@ pub struct EntityToken {
     // endregion ===================================================================
 }
 
+impl_referenceable!(EntityToken, "ENTITY");
+
 /// Start an animal definition.
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
@@ -824,14 +821,12 @@ pub struct Animal {
     pub animal_always_pack_animal: Flag,
 }
 
-///
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct Weapon {
     /// argument 1 of `WEAPON`
     #[serde(alias = "WEAPON")]
     pub reference: Option<(ReferenceTo<WeaponToken>, Option<RarityEnum>)>,
-    ///
     #[serde(alias = "AMMO")]
     pub ammo: Option<(ReferenceTo<AmmoToken>, Option<RarityEnum>)>, // TODO: semantic check, should only be nestable under an appropriate weapon
 }
@@ -855,10 +850,11 @@ pub struct TissueStyle {
 }
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum StylingEnum {
     #[serde(alias = "NEATLY_COMBED")]
+    #[default]
     NeatlyCombed,
     #[serde(alias = "BRAIDED")]
     Braided,
@@ -877,8 +873,3 @@ pub enum StylingEnum {
     #[serde(alias = "STANDARD_SIDEBURNS_SHAPINGS")]
     StandardSideburnsShapings,
 }
-impl Default for StylingEnum {
-    fn default() -> Self {
-        Self::NeatlyCombed
-    }
-}
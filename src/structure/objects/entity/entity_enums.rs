@@ -1,11 +1,12 @@
 use serde::{Deserialize, Serialize};
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum SiteTypeEnum {
     // TODO: update site type doc comment for all tokens using this enum
     #[serde(alias = "CITY")]
+    #[default]
     City,
     #[serde(alias = "TREE_CITY")]
     TreeCity,
@@ -20,17 +21,13 @@ pub enum SiteTypeEnum {
     #[serde(alias = "MONUMENT")]
     Monument,
 }
-impl Default for SiteTypeEnum {
-    fn default() -> Self {
-        Self::City
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum ConstructionEnum {
     #[serde(alias = "ROAD")]
+    #[default]
     Road,
     #[serde(alias = "TUNNEL")]
     Tunnel,
@@ -39,17 +36,13 @@ pub enum ConstructionEnum {
     #[serde(alias = "WALL")]
     Wall,
 }
-impl Default for ConstructionEnum {
-    fn default() -> Self {
-        Self::Road
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum ArtFacetEnum {
     #[serde(alias = "OWN_RACE")]
+    #[default]
     OwnRace,
     #[serde(alias = "FANCIFUL")]
     Fanciful,
@@ -58,17 +51,13 @@ pub enum ArtFacetEnum {
     #[serde(alias = "GOOD")]
     Good,
 }
-impl Default for ArtFacetEnum {
-    fn default() -> Self {
-        Self::OwnRace
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum ArtImageModifierEnum {
     #[serde(alias = "CREATURE")]
+    #[default]
     Creature,
     #[serde(alias = "PLANT")]
     Plant,
@@ -79,17 +68,13 @@ pub enum ArtImageModifierEnum {
     #[serde(alias = "ITEM")]
     Item,
 }
-impl Default for ArtImageModifierEnum {
-    fn default() -> Self {
-        Self::Creature
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum ItemImprovementModifierEnum {
     #[serde(alias = "ART_IMAGE")]
+    #[default]
     ArtImage,
     #[serde(alias = "COVERED")]
     Covered,
@@ -110,17 +95,13 @@ pub enum ItemImprovementModifierEnum {
     #[serde(alias = "SEWN_IMAGE")]
     SewnImage,
 }
-impl Default for ItemImprovementModifierEnum {
-    fn default() -> Self {
-        Self::ArtImage
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum SymbolNounEnum {
     #[serde(alias = "ALL")]
+    #[default]
     All,
     #[serde(alias = "REMAINING")]
     Remaining,
@@ -157,37 +138,29 @@ pub enum SymbolNounEnum {
     #[serde(alias = "WAR")]
     War,
 }
-impl Default for SymbolNounEnum {
-    fn default() -> Self {
-        Self::All
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum ReligionTypeEnum {
     /// The creatures will worship a group of gods, each aligned with their spheres
     /// and other appropriate ones as well.
     #[serde(alias = "PANTHEON")]
+    #[default]
     Pantheon,
     /// The creatures will worship a single force associated with the terrain of
     /// their initial biome.
     #[serde(alias = "REGIONAL_FORCE")]
     RegionalForce,
 }
-impl Default for ReligionTypeEnum {
-    fn default() -> Self {
-        Self::Pantheon
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum RarityEnum {
     /// The item has a 1/2 chance of being added to any given civilization of this entity type.
     #[serde(alias = "COMMON")]
+    #[default]
     Common,
     /// The item has a 1/10 chance of being added to any given civilization of this entity type.
     /// If the "item adding" algorithm fails to add any items the first time around, it will do
@@ -201,20 +174,16 @@ pub enum RarityEnum {
     #[serde(alias = "FORCED")]
     Forced,
 }
-impl Default for RarityEnum {
-    fn default() -> Self {
-        Self::Common
-    }
-}
 
 // `TODO` need to implement this still
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum EthicTypeEnum {
     /// The result of a tantrumming citizen attacking another in fortress mode. Other effects
     /// unknown.
     #[serde(alias = "ASSAULT")]
+    #[default]
     Assault,
     /// This determines if the race will sometimes devour defeated enemy combatants.
     #[serde(alias = "EAT_SAPIENT_KILL")]
@@ -285,16 +254,13 @@ pub enum EthicTypeEnum {
     /// This determines whether the civ will try to steal goods.
     #[serde(alias = "THEFT")]
     Theft,
-    ///
     #[serde(alias = "TORTURE_ANIMALS")]
     TortureAnimals,
     /// Civilization will sometimes execute non-combatants after defeating enemy defenders.
     #[serde(alias = "TORTURE_AS_EXAMPLE")]
     TortureAsExample,
-    ///
     #[serde(alias = "TORTURE_FOR_FUN")]
     TortureForFun,
-    ///
     #[serde(alias = "TORTURE_FOR_INFORMATION")]
     TortureForInformation,
     /// Protects position-holders from being murdered like everyone else – the reason that demon
@@ -310,17 +276,13 @@ pub enum EthicTypeEnum {
     #[serde(alias = "VANDALISM")]
     Vandalism,
 }
-impl Default for EthicTypeEnum {
-    fn default() -> Self {
-        Self::Assault
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum EthicReactionEnum {
     #[serde(alias = "NOT_APPLICABLE")]
+    #[default]
     NotApplicable,
     #[serde(alias = "ACCEPTABLE")]
     Acceptable,
@@ -355,15 +317,10 @@ pub enum EthicReactionEnum {
     #[serde(alias = "REQUIRED")]
     Required,
 }
-impl Default for EthicReactionEnum {
-    fn default() -> Self {
-        Self::NotApplicable
-    }
-}
 
 // `TODO` implement this
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum CulturalValueEnum {
     /// - +41 to +50: is an absolute believer in the rule of law
@@ -374,6 +331,7 @@ pub enum CulturalValueEnum {
     /// - −40 to −26: disdains the law
     /// - −50 to −41: finds the idea of laws abhorrent
     #[serde(alias = "LAW")]
+    #[default]
     Law,
     /// - +41 to +50: has the highest regard for loyalty
     /// - +26 to +40: greatly prizes loyalty
@@ -698,17 +656,13 @@ pub enum CulturalValueEnum {
     #[serde(alias = "KNOWLEDGE")]
     Knowledge,
 }
-impl Default for CulturalValueEnum {
-    fn default() -> Self {
-        Self::Law
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum SeasonEnum {
     #[serde(alias = "SPRING")]
+    #[default]
     Spring,
     #[serde(alias = "SUMMER")]
     Summer,
@@ -717,17 +671,13 @@ pub enum SeasonEnum {
     #[serde(alias = "WINTER")]
     Winter,
 }
-impl Default for SeasonEnum {
-    fn default() -> Self {
-        Self::Spring
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum ScholarTypeEnum {
     #[serde(alias = "ALL")]
+    #[default]
     All,
     #[serde(alias = "ASTRONOMER")]
     Astronomer,
@@ -748,8 +698,3 @@ pub enum ScholarTypeEnum {
     #[serde(alias = "PHILOSOPHER")]
     Philosopher,
 }
-impl Default for ScholarTypeEnum {
-    fn default() -> Self {
-        Self::All
-    }
-}
@@ -226,32 +226,24 @@ pub struct EntityPosition {
 }
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum SuccessionTypeEnum {
     #[serde(alias = "BY_HEIR")]
+    #[default]
     ByHeir,
     #[serde(alias = "BY_POSITION")]
     ByPosition,
 }
-impl Default for SuccessionTypeEnum {
-    fn default() -> Self {
-        Self::ByHeir
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum AsNeededEnum {
     #[serde(alias = "AS_NEEDED")]
+    #[default]
     AsNeeded,
 }
-impl Default for AsNeededEnum {
-    fn default() -> Self {
-        Self::AsNeeded
-    }
-}
 
 /// The position holder does a thing. See each enum value for what each responsibility does.
 #[serde_with::skip_serializing_none]
@@ -267,13 +259,13 @@ pub struct Responsibility {
 }
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum ResponsibilityEnum {
     /// Found on bookkeeper. Position will use record keeper skill to keep track of stocks.
     #[serde(alias = "ACCOUNTING")]
+    #[default]
     Accounting,
-    ///
     #[serde(alias = "ADVISE_LEADERS")]
     AdviseLeaders,
     /// Found on elven ranger captain and human warrior. Effect unknown.
@@ -282,13 +274,11 @@ pub enum ResponsibilityEnum {
     /// Found on champion. Position will lead military training exercises.
     #[serde(alias = "BUILD_MORALE")]
     BuildMorale,
-    ///
     #[serde(alias = "BUILDING_SAFETY")]
     BuildingSafety,
     /// Currently unused - was originally assigned to the tax collector.
     #[serde(alias = "COLLECT_TAXES")]
     CollectTaxes,
-    ///
     #[serde(alias = "CONSTRUCTION_PERMITS")]
     ConstructionPermits,
     /// Currently unused - was originally assigned to the arsenal dwarf.
@@ -313,17 +303,14 @@ pub enum ResponsibilityEnum {
     /// appropriate skill.
     #[serde(alias = "EXECUTIONS")]
     Executions,
-    ///
     #[serde(alias = "FIRE_SAFETY")]
     FireSafety,
-    ///
     #[serde(alias = "FOOD_SUPPLY")]
     FoodSupply,
     /// Found on chief medical dwarf. Position will use diagnostician skill to enable the z-menu
     /// health screen.
     #[serde(alias = "HEALTH_MANAGEMENT")]
     HealthManagement,
-    ///
     #[serde(alias = "JUDGE")]
     Judge,
     /// Found on sheriff/captain of the guard. Position and its subordinates are in charge of
@@ -338,16 +325,12 @@ pub enum ResponsibilityEnum {
     /// mode and they may be informed as to the site being the capital city for civ-level positions.
     #[serde(alias = "LAW_MAKING")]
     LawMaking,
-    ///
     #[serde(alias = "MAINTAIN_BRIDGES")]
     MaintainBridges,
-    ///
     #[serde(alias = "MAINTAIN_ROADS")]
     MaintainRoads,
-    ///
     #[serde(alias = "MAINTAIN_SEWERS")]
     MaintainSewers,
-    ///
     #[serde(alias = "MAINTAIN_TUNNELS")]
     MaintainTunnels,
     /// Position will make a 'social call' to an established foreign settlement, complimenting or
@@ -363,13 +346,10 @@ pub enum ResponsibilityEnum {
     /// Found on dungeon master.
     #[serde(alias = "MANAGE_ANIMALS")]
     ManageAnimals,
-    ///
     #[serde(alias = "MANAGE_LEADER_HOUSEHOLD_CLEANLINESS")]
     ManageLeaderHouseholdCleanliness,
-    ///
     #[serde(alias = "MANAGE_LEADER_HOUSEHOLD_DRINKS")]
     ManageLeaderHouseholdDrinks,
-    ///
     #[serde(alias = "MANAGE_LEADER_HOUSEHOLD_FOOD")]
     ManageLeaderHouseholdFood,
     /// Found on manager. Position enables the use of workshop profiles and uses the organizer skill
@@ -396,13 +376,11 @@ pub enum ResponsibilityEnum {
     /// fortress.
     #[serde(alias = "MILITARY_STRATEGY")]
     MilitaryStrategy,
-    ///
     #[serde(alias = "OVERSEE_LEADER_HOUSEHOLD")]
     OverseeLeaderHousehold,
     /// Found on elven ranger captain. Effect unknown.
     #[serde(alias = "PATROL_TERRITORY")]
     PatrolTerritory,
-    ///
     #[serde(alias = "PREPARE_LEADER_MEALS")]
     PrepareLeaderMeals,
     /// Found on monarch/landholder/leaders. Position uses the various social skills to hold
@@ -431,8 +409,3 @@ pub enum ResponsibilityEnum {
     #[serde(alias = "UPGRADE_SQUAD_EQUIPMENT")]
     UpgradeSquadEquipment,
 }
-impl Default for ResponsibilityEnum {
-    fn default() -> Self {
-        Self::Accounting
-    }
-}
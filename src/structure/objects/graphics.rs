@@ -1,4 +1,4 @@
-use crate::core::ReferenceTo;
+use crate::core::{ReferenceTo, impl_referenceable};
 use crate::structure::CreatureToken;
 
 use indexmap::IndexMap;
@@ -46,6 +46,8 @@ pub struct TilePageToken {
     pub page_dimensions: Option<(u32, u32)>,
 }
 
+impl_referenceable!(TilePageToken, "TILE_PAGE");
+
 type CreatureGraphicsTokenArg = (
     ReferenceTo<TilePageToken>,
     u32,
@@ -62,10 +64,11 @@ type TextureGraphicsTokenArg = (
 );
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum TextureTypeEnum {
     #[serde(alias = "DEFAULT")]
+    #[default]
     Default,
     #[serde(alias = "ADVENTURER")]
     Adventurer,
@@ -86,26 +89,17 @@ pub enum TextureTypeEnum {
     #[serde(alias = "GHOST")]
     Ghost,
 }
-impl Default for TextureTypeEnum {
-    fn default() -> Self {
-        Self::Default
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum ColorTypeEnum {
     #[serde(alias = "ADD_COLOR")]
+    #[default]
     AddColor,
     #[serde(alias = "AS_IS")]
     AsIs,
 }
-impl Default for ColorTypeEnum {
-    fn default() -> Self {
-        Self::AddColor
-    }
-}
 
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
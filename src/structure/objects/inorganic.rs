@@ -1,4 +1,4 @@
-use crate::core::{Choose, Clamp, DFChar, Reference, ReferenceTo, Flag};
+use crate::core::{Choose, Clamp, DFChar, Reference, ReferenceTo, Flag, impl_referenceable};
 use crate::structure::{
     AllOrAllSolidEnum, DietInfoEnum, MaterialStateEnum, NoneEnum, OverwriteSolidEnum, SphereEnum,
     StandardPluralEnum,
@@ -25,9 +25,8 @@ pub struct InorganicToken {
     pub environment: Vec<(EnvClassEnum, InclusionTypeEnum, Clamp<u8, 0, 100>)>,
     /// Specifies which specific minerals will contain this mineral.
     #[serde(alias = "ENVIRONMENT_SPEC")]
-    // TODO not sure if ReferenceTo<InorganicToken> is allowed as well or instead
     pub environment_spec: Vec<(
-        ReferenceTo<MaterialToken>,
+        ReferenceTo<InorganicToken>,
         InclusionTypeEnum,
         Clamp<u8, 0, 100>,
     )>,
@@ -39,11 +38,11 @@ pub struct InorganicToken {
     /// in the smelter. Each token with a non-zero chance causes the game to roll d100 four times,
     /// each time creating one bar of the type requested on success.
     #[serde(alias = "METAL_ORE")]
-    pub metal_ore: Vec<(ReferenceTo<MaterialToken>, Clamp<u8, 0, 100>)>,
+    pub metal_ore: Vec<(ReferenceTo<InorganicToken>, Clamp<u8, 0, 100>)>,
     /// Allows strands to be extracted from the metal at a
     /// [craftsdwarf's workshop](https://dwarffortresswiki.org/index.php/Craftsdwarf%27s_workshop).
     #[serde(alias = "THREAD_METAL")]
-    pub thread_metal: Option<(ReferenceTo<MaterialToken>, Clamp<u8, 0, 100>)>,
+    pub thread_metal: Option<(ReferenceTo<InorganicToken>, Clamp<u8, 0, 100>)>,
     /// Found on divine materials. Presumably links the material to a god of the same sphere.
     #[serde(alias = "SPHERE")]
     pub sphere: Option<SphereEnum>,
@@ -675,12 +674,15 @@ pub struct InorganicToken {
     // endregion ==================================================================================
 }
 
+impl_referenceable!(InorganicToken, "INORGANIC");
+
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum EnvClassEnum {
     /// Will appear in every stone.
     #[serde(alias = "ALL_STONE")]
+    #[default]
     AllStone,
     /// Will appear in igneous layers, both
     /// [intrusive](https://dwarffortresswiki.org/index.php/Igneous_intrusive_layer) and
@@ -714,14 +716,9 @@ pub enum EnvClassEnum {
     #[serde(alias = "ALLUVIAL")]
     Alluvial,
 }
-impl Default for EnvClassEnum {
-    fn default() -> Self {
-        Self::AllStone
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum InclusionTypeEnum {
     /// Large ovoids that occupy their entire 48x48 embark tile.
@@ -729,6 +726,7 @@ pub enum InclusionTypeEnum {
     ///
     /// When mined, stone has a 25% yield (as with layer stones).
     #[serde(alias = "CLUSTER")]
+    #[default]
     Cluster,
     /// Blobs of 3-9 tiles. Will always be successfully mined.
     /// [Red pyropes](https://dwarffortresswiki.org/index.php/Red_pyrope) are an example from vanilla.
@@ -749,8 +747,3 @@ pub enum InclusionTypeEnum {
     #[serde(alias = "VEIN")]
     Vein,
 }
-impl Default for InclusionTypeEnum {
-    fn default() -> Self {
-        Self::Cluster
-    }
-}
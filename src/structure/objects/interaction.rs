@@ -1,4 +1,4 @@
-use crate::core::{Choose, Reference, ReferenceTo, Flag};
+use crate::core::{Choose, Reference, ReferenceTo, Flag, impl_referenceable};
 use crate::structure::{
     BreathFlowEnum, BreathMaterialEnum, CasteFlagEnum, CreatureFlagEnum, CreatureToken,
     EffectLocationEnum, MaterialTokenArg, SphereEnum, SynTransmittionMethodEnum, SyndromeToken,
@@ -36,6 +36,8 @@ pub struct InteractionToken {
     pub generated: Flag,
 }
 
+impl_referenceable!(InteractionToken, "INTERACTION");
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct ISource {
@@ -314,23 +316,19 @@ pub struct IEffect {
 }
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub enum InteractionMaterialEmissionTypeTokenArg {
     /// Indicates the emission details should be obtained from `[CDI:MATERIAL]` or `[CDI:FLOW]`.
+    #[default]
     ContextMaterial,
     /// The emission will consist of the specified special flow type.
     Flow(BreathFlowEnum),
     /// The emission will consist of the specified material dispersed in the specified manner.
     Material((MaterialTokenArg, BreathMaterialEnum)),
 }
-impl Default for InteractionMaterialEmissionTypeTokenArg {
-    fn default() -> Self {
-        Self::ContextMaterial
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum SourceEnum {
     /// Specifies that the interaction may be used in conjunction with `[CAN_DO_INTERACTION]` and
@@ -338,6 +336,7 @@ pub enum SourceEnum {
     /// to allow for the inclusion of `IS_` tokens (detailed below) to be applied when the
     /// interaction is used in this context.
     #[serde(alias = "CREATURE_ACTION")]
+    #[default]
     CreatureAction,
     /// Allows the interaction to be used in conjunction with `[SPECIALATTACK_INTERACTION]` and
     /// `[CE_SPECIAL_ATTACK_INTERACTION]`.
@@ -372,14 +371,9 @@ pub enum SourceEnum {
     #[serde(alias = "EXPERIMENT")]
     Experiment,
 }
-impl Default for SourceEnum {
-    fn default() -> Self {
-        Self::CreatureAction
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum SecretGoalEnum {
     /// Ingame description: Unknown; if you know the ingame description, please open an issue on the
@@ -387,6 +381,7 @@ pub enum SecretGoalEnum {
     ///
     /// Gameplay effects: Unknown, possibly none.
     #[serde(alias = "STAY_ALIVE")]
+    #[default]
     StayAlive,
     /// Ingame description: Unknown; if you know the ingame description, please open an issue on the
     /// issue tracker, or tell us on the Discord server.
@@ -445,19 +440,15 @@ pub enum SecretGoalEnum {
     #[serde(alias = "IMMORTALITY")]
     Immortality,
 }
-impl Default for SecretGoalEnum {
-    fn default() -> Self {
-        Self::StayAlive
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum SecretLearnMethodEnum {
     /// Gods may gift the secret to their worshippers. Secrets with `[IS_SPHERE]` specified may only
     /// be granted by gods who have at least one matching sphere.
     #[serde(alias = "SUPERNATURAL_LEARNING_POSSIBLE")]
+    #[default]
     SupernaturalLearningPossible,
     /// The secret can be researched by mundane means. This doesn't do anything at present
     /// ([source](http://www.bay12forums.com/smf/index.php?topic=169696.msg8243222#msg8243222)).
@@ -471,18 +462,14 @@ pub enum SecretLearnMethodEnum {
     #[serde(alias = "MUNDANE_RECORDING_POSSIBLE")]
     MundaneRecordingPossible,
 }
-impl Default for SecretLearnMethodEnum {
-    fn default() -> Self {
-        Self::SupernaturalLearningPossible
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum LimitedUsageHintEnum {
     /// Used in divination dice blessings. Targets the roller.
     #[serde(alias = "MINOR_BLESSING")]
+    #[default]
     MinorBlessing,
     /// Used in divination dice blessings. Targets the roller.
     #[serde(alias = "MEDIUM_BLESSING")]
@@ -497,17 +484,13 @@ pub enum LimitedUsageHintEnum {
     #[serde(alias = "MAJOR_CURSE")]
     MajorCurse,
 }
-impl Default for LimitedUsageHintEnum {
-    fn default() -> Self {
-        Self::MinorBlessing
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum RegionTypeEnum {
     #[serde(alias = "ANY")]
+    #[default]
     Any,
     #[serde(alias = "DESERT")]
     Desert,
@@ -547,18 +530,14 @@ pub enum RegionTypeEnum {
     #[serde(alias = "SAVAGE_ONLY")]
     SavageOnly,
 }
-impl Default for RegionTypeEnum {
-    fn default() -> Self {
-        Self::Any
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum TargetTypeEnum {
     /// The target is a `CORPSE` or `CORPSEPIECE` item.
     #[serde(alias = "CORPSE")]
+    #[default]
     Corpse,
     /// The target is a unit.
     #[serde(alias = "CREATURE")]
@@ -577,17 +556,13 @@ pub enum TargetTypeEnum {
     #[serde(alias = "LOCATION")]
     Location,
 }
-impl Default for TargetTypeEnum {
-    fn default() -> Self {
-        Self::Corpse
-    }
-}
 
 // TODO semantic analysis to limit which values are allowed based on the parent `I_TARGET`
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub enum TargetLocationEnum {
     /// Used with `CREATURE` to target the whole unit.
+    #[default]
     ContextCreature,
     /// Used with `CREATURE` to target the body part specified in `[CDI:BP_REQUIRED]`.
     ContextBp,
@@ -609,14 +584,9 @@ pub enum TargetLocationEnum {
     /// path between the two locations must exist.
     RandomNearbyLocation((Reference, i32)),
 }
-impl Default for TargetLocationEnum {
-    fn default() -> Self {
-        Self::ContextCreature
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum EffectEnum {
     /// Adds one or more syndromes to a valid creature target. You must specify the syndrome details
@@ -624,6 +594,7 @@ pub enum EffectEnum {
     /// syndrome tokens. See [here](https://dwarffortresswiki.org/index.php/Syndrome#The_anatomy_of_a_syndrome)
     /// for more information.
     #[serde(alias = "ADD_SYNDROME")]
+    #[default]
     AddSyndrome,
     /// Raises the target corpse/bodypart as an undead unit. The zombie will always be hostile to
     /// life and will retain no information about its original personality/loyalties. Syndromes can
@@ -676,17 +647,13 @@ pub enum EffectEnum {
     #[serde(alias = "RAISE_GHOST")]
     RaiseGhost,
 }
-impl Default for EffectEnum {
-    fn default() -> Self {
-        Self::AddSyndrome
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum IntermittentFrequencyEnum {
     #[serde(alias = "WEEKLY")]
+    #[default]
     Weekly,
     #[serde(alias = "DAILY")] // TODO mark as broken, see #83
     Daily,
@@ -695,17 +662,13 @@ pub enum IntermittentFrequencyEnum {
     #[serde(alias = "YEARLY")] // TODO mark as broken, see #83
     Yearly,
 }
-impl Default for IntermittentFrequencyEnum {
-    fn default() -> Self {
-        Self::Weekly
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum WeatherEnum {
     #[serde(alias = "FOG_MIST")]
+    #[default]
     FogMist,
     #[serde(alias = "FOG_NORMAL")]
     FogNormal,
@@ -732,21 +695,12 @@ pub enum WeatherEnum {
     #[serde(alias = "CIRRUS")]
     Cirrus,
 }
-impl Default for WeatherEnum {
-    fn default() -> Self {
-        Self::FogMist
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum ArtifactEnum {
     #[serde(alias = "ARTIFACT")]
+    #[default]
     Artifact,
 }
-impl Default for ArtifactEnum {
-    fn default() -> Self {
-        Self::Artifact
-    }
-}
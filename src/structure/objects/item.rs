@@ -1,4 +1,6 @@
-use crate::core::{Choose, Clamp, DFChar, Reference, ReferenceTo, Flag};
+use crate::core::{
+    Choose, Clamp, DFChar, Reference, ReferenceTo, Flag, impl_referenceable, Referenceable,
+};
 
 use serde::{Deserialize, Serialize};
 
@@ -43,6 +45,33 @@ impl Default for ItemToken {
     }
 }
 
+/// Any item, no matter its type.
+impl Referenceable for ItemToken {
+    fn get_reference(&self) -> Option<ReferenceTo<Self>> {
+        let id = match self {
+            ItemToken::AmmoToken(token) => &token.reference.as_ref()?.0,
+            ItemToken::ArmorToken(token) => &token.reference.as_ref()?.0,
+            ItemToken::FoodToken(token) => &token.reference.as_ref()?.0,
+            ItemToken::GlovesToken(token) => &token.reference.as_ref()?.0,
+            ItemToken::HelmToken(token) => &token.reference.as_ref()?.0,
+            ItemToken::InstrumentToken(token) => &token.reference.as_ref()?.0,
+            ItemToken::PantsToken(token) => &token.reference.as_ref()?.0,
+            ItemToken::ShieldToken(token) => &token.reference.as_ref()?.0,
+            ItemToken::ShoesToken(token) => &token.reference.as_ref()?.0,
+            ItemToken::SiegeAmmoToken(token) => &token.reference.as_ref()?.0,
+            ItemToken::ToolToken(token) => &token.reference.as_ref()?.0,
+            ItemToken::ToyToken(token) => &token.reference.as_ref()?.0,
+            ItemToken::TrapCompToken(token) => &token.reference.as_ref()?.0,
+            ItemToken::WeaponToken(token) => &token.reference.as_ref()?.0,
+        };
+        Some(ReferenceTo::new(id.clone()))
+    }
+
+    fn get_ref_type() -> &'static str {
+        "ITEM"
+    }
+}
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct AmmoToken {
@@ -64,6 +93,8 @@ pub struct AmmoToken {
     pub attack: Option<ItemAttack>, // TODO: test ingame if WeaponAttack could be used instead here
 }
 
+impl_referenceable!(AmmoToken, "ITEM_AMMO");
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct ArmorToken {
@@ -192,6 +223,8 @@ pub struct ArmorToken {
     // endregion ==================================================================================
 }
 
+impl_referenceable!(ArmorToken, "ITEM_ARMOR");
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct FoodToken {
@@ -209,6 +242,8 @@ pub struct FoodToken {
     pub level: Option<Clamp<u8, 2, 4>>,
 }
 
+impl_referenceable!(FoodToken, "ITEM_FOOD");
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct GlovesToken {
@@ -318,6 +353,8 @@ pub struct GlovesToken {
     // endregion ==================================================================================
 }
 
+impl_referenceable!(GlovesToken, "ITEM_GLOVES");
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct HelmToken {
@@ -419,6 +456,8 @@ pub struct HelmToken {
     // endregion ==================================================================================
 }
 
+impl_referenceable!(HelmToken, "ITEM_HELM");
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct InstrumentToken {
@@ -616,10 +655,10 @@ pub struct InstrumentToken {
     /// The skill to determine effectiveness in melee with this tool. Required for weapons.
     #[serde(alias = "SKILL")]
     pub skill: Option<SkillEnum>,
-    /// Makes this tool a ranged weapon that uses the specified ammo. The specified skill
-    /// determines accuracy in ranged combat.
+    /// Makes this tool a ranged weapon that uses ammo of the specified `CLASS`. The specified
+    /// skill determines accuracy in ranged combat.
     #[serde(alias = "RANGED")]
-    pub ranged: Option<(SkillEnum, ReferenceTo<AmmoToken>)>,
+    pub ranged: Option<(SkillEnum, Reference)>,
     /// Creatures under this size (in cm^3) must use the tool two-handed. Required for weapons.
     #[serde(alias = "TWO_HANDED")]
     pub two_handed: Option<u32>,
@@ -638,28 +677,27 @@ pub struct InstrumentToken {
     // endregion ==================================================================================
 }
 
+impl_referenceable!(InstrumentToken, "ITEM_INSTRUMENT");
+
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum NameTypeEnum {
     #[serde(alias = "STANDARD")]
+    #[default]
     Standard,
     #[serde(alias = "ALWAYS_PLURAL")]
     AlwaysPlural,
     #[serde(alias = "ALWAYS_SINGULAR")]
     AlwaysSingular,
 }
-impl Default for NameTypeEnum {
-    fn default() -> Self {
-        Self::Standard
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum PitchMethodEnum {
     #[serde(alias = "MEMBRANE_POSITION")]
+    #[default]
     MembranePosition,
     #[serde(alias = "SUBPART_CHOICE")]
     SubpartChoice,
@@ -689,17 +727,13 @@ pub enum PitchMethodEnum {
     #[serde(alias = "FOOT_PEDALS")]
     FootPedals,
 }
-impl Default for PitchMethodEnum {
-    fn default() -> Self {
-        Self::MembranePosition
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum TuningMethodEnum {
     #[serde(alias = "PEGS")]
+    #[default]
     Pegs,
     #[serde(alias = "ADJUSTABLE_BRIDGES")]
     AdjustableBridhes,
@@ -710,17 +744,13 @@ pub enum TuningMethodEnum {
     #[serde(alias = "LEVERS")]
     Levers,
 }
-impl Default for TuningMethodEnum {
-    fn default() -> Self {
-        Self::Pegs
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum SoundProductionEnum {
     #[serde(alias = "PLUCKED_BY_BP")]
+    #[default]
     PluckedByBp,
     /// Requires two `INSTRUMENT_PIECE` tokens: actor, then target.
     #[serde(alias = "PLUCKED")]
@@ -772,17 +802,13 @@ pub enum SoundProductionEnum {
     #[serde(alias = "AIR_AGAINST_FIPPLE")]
     AirAgainstFipple,
 }
-impl Default for SoundProductionEnum {
-    fn default() -> Self {
-        Self::PluckedByBp
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum TimbreEnum {
     #[serde(alias = "CLEAR")]
+    #[default]
     Clear,
     #[serde(alias = "NOISY")]
     Noisy,
@@ -909,11 +935,6 @@ pub enum TimbreEnum {
     #[serde(alias = "SPARKLING")]
     Sparkling,
 }
-impl Default for TimbreEnum {
-    fn default() -> Self {
-        Self::Clear
-    }
-}
 
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
@@ -1033,6 +1054,8 @@ pub struct PantsToken {
     // endregion ==================================================================================
 }
 
+impl_referenceable!(PantsToken, "ITEM_PANTS");
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct ShieldToken {
@@ -1068,6 +1091,8 @@ pub struct ShieldToken {
     pub adjective: Option<String>,
 }
 
+impl_referenceable!(ShieldToken, "ITEM_SHIELD");
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct ShoesToken {
@@ -1177,11 +1202,14 @@ pub struct ShoesToken {
     // endregion ==================================================================================
 }
 
+impl_referenceable!(ShoesToken, "ITEM_SHOES");
+
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum LayerEnum {
     #[serde(alias = "UNDER")]
+    #[default]
     Under,
     #[serde(alias = "OVER")]
     Over,
@@ -1190,24 +1218,15 @@ pub enum LayerEnum {
     #[serde(alias = "COVER")]
     Cover,
 }
-impl Default for LayerEnum {
-    fn default() -> Self {
-        Self::Under
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum MaxEnum {
     #[serde(alias = "MAX")]
+    #[default]
     Max,
 }
-impl Default for MaxEnum {
-    fn default() -> Self {
-        Self::Max
-    }
-}
 
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
@@ -1224,18 +1243,16 @@ pub struct SiegeAmmoToken {
     pub class: Option<SiegeAmmoClassEnum>,
 }
 
+impl_referenceable!(SiegeAmmoToken, "ITEM_SIEGEAMMO");
+
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum SiegeAmmoClassEnum {
     #[serde(alias = "BALLISTA")]
+    #[default]
     Ballista,
 }
-impl Default for SiegeAmmoClassEnum {
-    fn default() -> Self {
-        Self::Ballista
-    }
-}
 
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
@@ -1360,10 +1377,10 @@ pub struct ToolToken {
     /// The skill to determine effectiveness in melee with this tool. Required for weapons.
     #[serde(alias = "SKILL")]
     pub skill: Option<SkillEnum>,
-    /// Makes this tool a ranged weapon that uses the specified ammo. The specified skill
-    /// determines accuracy in ranged combat.
+    /// Makes this tool a ranged weapon that uses ammo of the specified `CLASS`. The specified
+    /// skill determines accuracy in ranged combat.
     #[serde(alias = "RANGED")]
-    pub ranged: Option<(SkillEnum, ReferenceTo<AmmoToken>)>,
+    pub ranged: Option<(SkillEnum, Reference)>,
     /// Creatures under this size (in cm^3) must use the tool two-handed. Required for weapons.
     #[serde(alias = "TWO_HANDED")]
     pub two_handed: Option<u32>,
@@ -1381,12 +1398,15 @@ pub struct ToolToken {
     pub attack: Vec<WeaponAttack>,
 }
 
+impl_referenceable!(ToolToken, "ITEM_TOOL");
+
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum ToolUseEnum {
     /// Cauldron adventure mode decoration / weapon
     #[serde(alias = "LIQUID_COOKING")]
+    #[default]
     LiquidCooking,
     /// Ladle, an adventure mode decoration/weapon.
     #[serde(alias = "LIQUID_SCOOP")]
@@ -1464,11 +1484,6 @@ pub enum ToolUseEnum {
     #[serde(alias = "GAMES_OF_CHANCE")]
     GamesOfChance,
 }
-impl Default for ToolUseEnum {
-    fn default() -> Self {
-        Self::LiquidCooking
-    }
-}
 
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -1515,26 +1530,23 @@ impl Default for ImprovementTypeTokenArg {
 }
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum ItemSpecificEnum {
     #[serde(alias = "ROLLERS")]
+    #[default]
     Rollers,
     #[serde(alias = "HANDLE")]
     Handle,
 }
-impl Default for ItemSpecificEnum {
-    fn default() -> Self {
-        Self::Rollers
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum ImprovementMaterialFlagEnum {
     /// Permits the improvement to be made from any bone.
     #[serde(alias = "BONE_MAT")]
+    #[default]
     BoneMat,
     /// Permits the improvement to be made from any ceramic material.
     #[serde(alias = "CERAMIC_MAT")]
@@ -1580,11 +1592,6 @@ pub enum ImprovementMaterialFlagEnum {
     #[serde(alias = "WOOD_MAT")]
     WoodMat,
 }
-impl Default for ImprovementMaterialFlagEnum {
-    fn default() -> Self {
-        Self::BoneMat
-    }
-}
 
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
@@ -1601,6 +1608,8 @@ pub struct ToyToken {
     pub hard_mat: Flag,
 }
 
+impl_referenceable!(ToyToken, "ITEM_TOY");
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct TrapCompToken {
@@ -1640,6 +1649,8 @@ pub struct TrapCompToken {
     pub attack: Option<ItemAttack>,
 }
 
+impl_referenceable!(TrapCompToken, "ITEM_TRAPCOMP");
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct WeaponToken {
@@ -1699,6 +1710,8 @@ pub struct WeaponToken {
     pub attack: Vec<WeaponAttack>,
 }
 
+impl_referenceable!(WeaponToken, "ITEM_WEAPON");
+
 // region: Attack definitions
 
 /// Specifies the attack characteristics of this item.
@@ -1770,30 +1783,22 @@ pub struct WeaponAttack {
 }
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum AttackTypeEnum {
     #[serde(alias = "EDGE")]
+    #[default]
     Edge,
     #[serde(alias = "BLUNT")]
     Blunt,
 }
-impl Default for AttackTypeEnum {
-    fn default() -> Self {
-        Self::Edge
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum NoSubEnum {
     #[serde(alias = "NO_SUB")]
+    #[default]
     NoSub,
 }
-impl Default for NoSubEnum {
-    fn default() -> Self {
-        Self::NoSub
-    }
-}
 // endregion
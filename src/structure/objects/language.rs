@@ -1,4 +1,4 @@
-use crate::core::{AllowEmpty, ReferenceTo, Flag};
+use crate::core::{AllowEmpty, ReferenceTo, Flag, impl_referenceable};
 
 use serde::{Deserialize, Serialize};
 
@@ -25,11 +25,12 @@ pub struct TranslationToken {
     /// The reference for a Translation in other RAW files and tokens
     #[serde(alias = "TRANSLATION")]
     pub reference: Option<ReferenceTo<Self>>,
-    ///
     #[serde(alias = "T_WORD")]
     pub t_word: Vec<(ReferenceTo<WordToken>, String)>,
 }
 
+impl_referenceable!(TranslationToken, "TRANSLATION");
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct SymbolToken {
@@ -37,11 +38,12 @@ pub struct SymbolToken {
     /// The reference for a Symbol in other RAW files and tokens
     #[serde(alias = "SYMBOL")]
     pub reference: Option<ReferenceTo<Self>>,
-    ///
     #[serde(alias = "S_WORD")]
     pub s_word: Vec<ReferenceTo<WordToken>>,
 }
 
+impl_referenceable!(SymbolToken, "SYMBOL");
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct WordToken {
@@ -49,20 +51,18 @@ pub struct WordToken {
     /// The reference for a Word in other RAW files and tokens
     #[serde(alias = "WORD")]
     pub reference: Option<ReferenceTo<Self>>,
-    ///
     #[serde(alias = "NOUN")]
     pub nouns: Vec<NounToken>,
-    ///
     #[serde(alias = "ADJ")]
     pub adj: Vec<AdjToken>,
-    ///
     #[serde(alias = "VERB")]
     pub verb: Vec<VerbToken>,
-    ///
     #[serde(alias = "PREFIX")]
     pub prefix: Vec<PrefixToken>,
 }
 
+impl_referenceable!(WordToken, "WORD");
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct NounToken {
@@ -70,34 +70,24 @@ pub struct NounToken {
     ///
     #[serde(alias = "NOUN")]
     pub words: Option<(String, AllowEmpty<String>)>,
-    ///
     #[serde(alias = "FRONT_COMPOUND_NOUN_SING")]
     pub front_compound_noun_sing: Flag,
-    ///
     #[serde(alias = "REAR_COMPOUND_NOUN_SING")]
     pub read_compound_noun_sing: Flag,
-    ///
     #[serde(alias = "THE_COMPOUND_NOUN_SING")]
     pub the_compound_noun_sing: Flag,
-    ///
     #[serde(alias = "THE_NOUN_SING")]
     pub the_noun_sing: Flag,
-    ///
     #[serde(alias = "OF_NOUN_SING")]
     pub of_noun_sing: Flag,
-    ///
     #[serde(alias = "FRONT_COMPOUND_NOUN_PLUR")]
     pub front_compound_noun_plur: Flag,
-    ///
     #[serde(alias = "REAR_COMPOUND_NOUN_PLUR")]
     pub read_compound_noun_plur: Flag,
-    ///
     #[serde(alias = "THE_COMPOUND_NOUN_PLUR")]
     pub the_compound_noun_plur: Flag,
-    ///
     #[serde(alias = "THE_NOUN_PLUR")]
     pub the_noun_plur: Flag,
-    ///
     #[serde(alias = "OF_NOUN_PLUR")]
     pub of_noun_plur: Flag,
 }
@@ -108,16 +98,12 @@ pub struct VerbToken {
     /// Argument 1 of `VERB`
     #[serde(alias = "VERB")]
     pub words: Option<(String, String, String, String, String)>,
-    ///
     #[serde(alias = "STANDARD_VERB")]
     pub standard_verb: Flag,
-    ///
     #[serde(alias = "FRONT_COMPOUND_ADJ")]
     pub front_compound_adj: Flag,
-    ///
     #[serde(alias = "THE_COMPOUND_ADJ")]
     pub the_compound_adj: Flag,
-    ///
     #[serde(alias = "REAR_COMPOUND_ADJ")]
     pub rear_compound_adj: Flag,
 }
@@ -128,16 +114,12 @@ pub struct AdjToken {
     /// Argument 1 of `ADJ`
     #[serde(alias = "ADJ")]
     pub words: Option<String>,
-    ///
     #[serde(alias = "ADJ_DIST")]
     pub adj_dist: Option<i32>,
-    ///
     #[serde(alias = "FRONT_COMPOUND_ADJ")]
     pub front_compound_adj: Flag,
-    ///
     #[serde(alias = "THE_COMPOUND_ADJ")]
     pub the_compound_adj: Flag,
-    ///
     #[serde(alias = "REAR_COMPOUND_ADJ")]
     pub rear_compound_adj: Flag,
 }
@@ -148,10 +130,8 @@ pub struct PrefixToken {
     /// Argument 1 of `PREFIX`
     #[serde(alias = "PREFIX")]
     pub words: Option<String>,
-    ///
     #[serde(alias = "FRONT_COMPOUND_PREFIX")]
     pub front_compound_prefix: Flag,
-    ///
     #[serde(alias = "THE_COMPOUND_PREFIX")]
     pub the_compound_prefix: Flag,
 }
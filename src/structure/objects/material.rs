@@ -1,4 +1,4 @@
-use crate::core::{Choose, DFChar, Reference, ReferenceTo, Flag};
+use crate::core::{Choose, DFChar, Reference, ReferenceTo, Flag, impl_referenceable};
 use crate::structure::{AllOrAllSolidEnum, MaterialStateEnum, NoneEnum, StandardPluralEnum};
 use crate::structure::{
     ColorToken, InorganicToken, ItemReferenceArg, MaterialTokenArg, ReactionToken, SyndromeToken,
@@ -543,24 +543,23 @@ pub struct MaterialToken {
     // endregion ==================================================================================
 }
 
+impl_referenceable!(MaterialToken, "MATERIAL_TEMPLATE");
+
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum OverwriteSolidEnum {
     #[serde(alias = "OVERWRITE_SOLID")]
+    #[default]
     OverwriteSolid,
 }
-impl Default for OverwriteSolidEnum {
-    fn default() -> Self {
-        Self::OverwriteSolid
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum DietInfoEnum {
     #[serde(alias = "BLOOD")]
+    #[default]
     Blood,
     #[serde(alias = "SLIME")]
     Slime,
@@ -577,11 +576,6 @@ pub enum DietInfoEnum {
     #[serde(alias = "FILTH")]
     Filth,
 }
-impl Default for DietInfoEnum {
-    fn default() -> Self {
-        Self::Blood
-    }
-}
 
 /// Define a new material locally.
 #[serde_with::skip_serializing_none]
@@ -1,4 +1,4 @@
-use crate::core::ReferenceTo;
+use crate::core::{ReferenceTo, impl_referenceable};
 
 use serde::{Deserialize, Serialize};
 
@@ -14,11 +14,14 @@ pub struct PatternToken {
     pub cp_color: Vec<ReferenceTo<crate::ColorToken>>,
 }
 
+impl_referenceable!(PatternToken, "COLOR_PATTERN");
+
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum PatternEnum {
     #[serde(alias = "SPOTS")]
+    #[default]
     Spots,
     #[serde(alias = "STRIPES")]
     Stripes,
@@ -29,9 +32,3 @@ pub enum PatternEnum {
     #[serde(alias = "PUPIL_EYE")]
     PupilEye,
 }
-
-impl Default for PatternEnum {
-    fn default() -> Self {
-        Self::Spots
-    }
-}
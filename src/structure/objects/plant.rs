@@ -1,4 +1,4 @@
-use crate::core::{Choose, DFChar, Reference, ReferenceTo, Flag};
+use crate::core::{Choose, DFChar, Reference, ReferenceTo, Flag, impl_referenceable};
 use crate::structure::{
     AllEnum, AllOrAllSolidEnum, BiomeEnum, ColorToken, DietInfoEnum, InorganicToken,
     ItemReferenceArg, LocalMaterialToken, MaterialStateEnum, MaterialTokenArg, NoneEnum,
@@ -386,6 +386,8 @@ pub struct PlantToken {
     // endregion ==================================================================================
 }
 
+impl_referenceable!(PlantToken, "PLANT");
+
 /// Defines a plant growth.
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
@@ -404,7 +406,6 @@ pub struct Growth {
     /// trees.
     #[serde(alias = "GROWTH_HOST_TILE")]
     pub growth_host_tile: Vec<GrowthHostEnum>,
-    ///
     #[serde(alias = "GROWTH_TRUNK_HEIGHT_PERC")]
     pub growth_trunk_height_perc: Option<(i8, i8)>, // TODO: research, can both really be negative?
     /// Currently has no effect.
@@ -459,11 +460,12 @@ pub struct Growth {
 }
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum GrowthHostEnum {
     /// The growth will appear on the twigs.
     #[serde(alias = "TWIGS")]
+    #[default]
     Twigs,
     /// The growth will appear on branches.
     #[serde(alias = "BRANCHES")]
@@ -505,11 +507,6 @@ pub enum GrowthHostEnum {
     #[serde(alias = "DIRECTED_BRANCHES_AND_TRUNK")]
     DirectedBranchesAndTrunk,
 }
-impl Default for GrowthHostEnum {
-    fn default() -> Self {
-        Self::Twigs
-    }
-}
 
 /// Sets the basic material of the plant, using another defined material (local or otherwise).
 ///
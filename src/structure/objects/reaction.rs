@@ -1,4 +1,4 @@
-use crate::core::{AllowEmpty, Choose, Clamp, Reference, ReferenceTo, Flag};
+use crate::core::{AllowEmpty, Choose, Clamp, Reference, ReferenceTo, Flag, impl_referenceable};
 use crate::structure::{
     BuildingToken, ItemReferenceArg, KeyBindEnum, MaterialTokenArg, NoneEnum, SkillEnum,
 };
@@ -108,6 +108,8 @@ pub struct ReactionToken {
     pub products: Vec<ProductToken>,
 }
 
+impl_referenceable!(ReactionToken, "REACTION");
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct ReagentToken {
@@ -280,6 +282,8 @@ pub struct ReagentToken {
     pub worthless_stone_only: Flag,
 }
 
+impl_referenceable!(ReagentToken, "REAGENT", reference.0);
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct ProductToken {
@@ -358,11 +362,14 @@ pub struct ReactionCategoryToken {
     pub category_key: Option<Choose<KeyBindEnum, NoneEnum>>, // param is KeyBind
 }
 
+impl_referenceable!(ReactionCategoryToken, "CATEGORY");
+
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum ImprovementType {
     #[serde(alias = "COVERED")]
+    #[default]
     Covered,
     #[serde(alias = "GLAZED")]
     Glazed,
@@ -375,9 +382,3 @@ pub enum ImprovementType {
     #[serde(alias = "INSTRUMENT_PIECE")]
     InstrumentPiece,
 }
-
-impl Default for ImprovementType {
-    fn default() -> Self {
-        Self::Covered
-    }
-}
@@ -1,4 +1,4 @@
-use crate::core::{DFChar, Reference, ReferenceTo, Flag};
+use crate::core::{DFChar, Reference, ReferenceTo, Flag, impl_referenceable};
 
 use serde::{Deserialize, Serialize};
 
@@ -41,3 +41,5 @@ pub struct ShapeToken {
     #[serde(alias = "CATEGORY")]
     pub category: Vec<Reference>,
 }
+
+impl_referenceable!(ShapeToken, "SHAPE");
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 // region: Unique single CE_X enums ===============================================================
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum BpEnum {
     /// Specifies which body parts and tissues are to be affected by the syndrome. You can specify
@@ -22,119 +22,88 @@ pub enum BpEnum {
     ///
     /// This tag is overidden by (and therefore incompatible with) `LOCALIZED`.
     #[serde(alias = "BP")]
+    #[default]
     Bp,
 }
-impl Default for BpEnum {
-    fn default() -> Self {
-        Self::Bp
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum NameEnum {
     #[serde(alias = "NAME")]
+    #[default]
     Name,
 }
-impl Default for NameEnum {
-    fn default() -> Self {
-        Self::Name
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum TileEnum {
     #[serde(alias = "TILE")]
+    #[default]
     Tile,
 }
-impl Default for TileEnum {
-    fn default() -> Self {
-        Self::Tile
-    }
-}
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum FrequencyEnum {
     /// How many frames to use the normal tile, and how many frames to use the "syndrome" tile for.
     #[serde(alias = "FREQUENCY")]
+    #[default]
     Frequency,
 }
-impl Default for FrequencyEnum {
-    fn default() -> Self {
-        Self::Frequency
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum SpeedEnum {
     /// Modifies a creature's in-game speed by a specified percentage; higher numbers are faster.
     #[serde(alias = "SPEED_PERC")]
+    #[default]
     SpeedPerc,
     /// Adds to a creature's `[SPEED:XX]` token, with higher numbers being slower.
     /// Negative numbers are accepted, though will only reduce a creature's speed to zero.
     #[serde(alias = "SPEED_ADD")]
     SpeedAdd,
 }
-impl Default for SpeedEnum {
-    fn default() -> Self {
-        Self::SpeedPerc
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum PercEnum {
     /// Specifies a percentage of the creature's current skill.
     #[serde(alias = "PERC")]
+    #[default]
     Perc,
 }
-impl Default for PercEnum {
-    fn default() -> Self {
-        Self::Perc
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum PercOnEnum {
     /// Specifies the probability of the effect being applied on a particular skill roll.
     #[serde(alias = "PERC_ON")]
+    #[default]
     PercOn,
 }
-impl Default for PercOnEnum {
-    fn default() -> Self {
-        Self::PercOn
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum AppearanceModifierEnum {
     /// Selects a type of appearance modifier to be altered.
     #[serde(alias = "APPEARANCE_MODIFIER")]
+    #[default]
     AppearanceModifier,
 }
-impl Default for AppearanceModifierEnum {
-    fn default() -> Self {
-        Self::AppearanceModifier
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum AppModTypeEnum {
     /// The height of the body part.
     #[serde(alias = "HEIGHT")]
+    #[default]
     Height,
     /// The length of the body part.
     #[serde(alias = "LENGTH")]
@@ -143,114 +112,81 @@ pub enum AppModTypeEnum {
     #[serde(alias = "BROADNESS")]
     Broadness,
 }
-impl Default for AppModTypeEnum {
-    fn default() -> Self {
-        Self::Height
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum MatMultEnum {
     #[serde(alias = "MAT_MULT")]
+    #[default]
     MatMult,
 }
-impl Default for MatMultEnum {
-    fn default() -> Self {
-        Self::MatMult
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum InteractionEnum {
     /// References the interaction ID to be used for this special attack.
     #[serde(alias = "INTERACTION")]
+    #[default]
     Interaction,
 }
-impl Default for InteractionEnum {
-    fn default() -> Self {
-        Self::Interaction
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum MatTokenEnum {
     /// Specifies the name/ID of a local creature material.
     #[serde(alias = "MAT_TOKEN")]
+    #[default]
     MatToken,
 }
-impl Default for MatTokenEnum {
-    fn default() -> Self {
-        Self::MatToken
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum ReservedBloodEnum {
     /// `RESERVED_BLOOD` is a special body material token which can be used to
     /// specify the `[BLOOD]` material of any creature, regardless of the material's actual ID.
     #[serde(alias = "RESERVED_BLOOD")]
+    #[default]
     ReservedBlood,
 }
-impl Default for ReservedBloodEnum {
-    fn default() -> Self {
-        Self::ReservedBlood
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum ClassEnum {
     /// Specifies a creature class (for instance, `GENERAL_POISON`).
     #[serde(alias = "CLASS")]
+    #[default]
     Class,
 }
-impl Default for ClassEnum {
-    fn default() -> Self {
-        Self::Class
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum EmotionEnum {
     /// Specifies the emotion.
     #[serde(alias = "EMOTION")]
+    #[default]
     Emotion,
 }
-impl Default for EmotionEnum {
-    fn default() -> Self {
-        Self::Emotion
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum FacetEnum {
     /// Specifies a [personality trait/facet](https://dwarffortresswiki.org/index.php/Personality_trait#Facets).
     #[serde(alias = "FACET")]
+    #[default]
     Facet,
 }
-impl Default for FacetEnum {
-    fn default() -> Self {
-        Self::Facet
-    }
-}
 // endregion ======================================================================================
 
 // region: General CE enums =======================================================================
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum PeriodicTriggerEnum {
     /// When this token is placed after a syndrome effect, it will prevent that effect from working
@@ -270,15 +206,11 @@ pub enum PeriodicTriggerEnum {
     ///
     /// `MOON_PHASE` is currently the only valid period type.
     #[serde(alias = "PERIODIC")]
+    #[default]
     Periodic,
 }
-impl Default for PeriodicTriggerEnum {
-    fn default() -> Self {
-        Self::Periodic
-    }
-}
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum CounterTriggerEnum {
     /// Creatures in Dwarf Fortress possess internal counters which keep track of their various
@@ -306,15 +238,11 @@ pub enum CounterTriggerEnum {
     /// also be specified for the same effect, in which case both the periodic trigger and at least
     /// one counter trigger will need to have their conditions met for the effect to work.
     #[serde(alias = "COUNTER_TRIGGER")]
+    #[default]
     CounterTrigger,
 }
-impl Default for CounterTriggerEnum {
-    fn default() -> Self {
-        Self::CounterTrigger
-    }
-}
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum MoonPhaseEnum {
     /// The lunar cycle in Dwarf Fortress is composed of 28 segments (each slightly shorter than a
@@ -330,15 +258,11 @@ pub enum MoonPhaseEnum {
     /// - 23-26 = waxing gibbous
     /// - 27 = full moon
     #[serde(alias = "MOON_PHASE")]
+    #[default]
     MoonPhase,
 }
-impl Default for MoonPhaseEnum {
-    fn default() -> Self {
-        Self::MoonPhase
-    }
-}
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum CounterTypesEnum {
     /// For `[ALCOHOL_DEPENDENT]` creatures, this counter increases by 1 each tick, and is reset to
@@ -350,6 +274,7 @@ pub enum CounterTypesEnum {
     /// - 302400 (9 months) = and has gone without a drink for far, far too long
     /// - 403200 (1 year) = and can't even remember the last time (s)he had some
     #[serde(alias = "ALCOHOLIC")]
+    #[default]
     Alcoholic,
     /// For creatures with the `[CAVE_ADAPT]` token, this counter is created and increases by 1 each
     /// tick when the creature is in the Dark, and decreases by 10 each tick when Outside.
@@ -404,13 +329,8 @@ pub enum CounterTypesEnum {
     #[serde(alias = "PARTIED_OUT")]
     PartiedOut,
 }
-impl Default for CounterTypesEnum {
-    fn default() -> Self {
-        Self::Alcoholic
-    }
-}
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum RequiredEnum {
     /// `REQUIRED` implies that the effect won't proceed if the counter exists but doesn't lie within
@@ -418,13 +338,9 @@ pub enum RequiredEnum {
     /// both of these conditions; replacing it with `NONE` doesn't alter the way the trigger
     /// functions, though it will fail to work if this slot is left empty instead.
     #[serde(alias = "REQUIRED")]
+    #[default]
     Required,
 }
-impl Default for RequiredEnum {
-    fn default() -> Self {
-        Self::Required
-    }
-}
 // endregion ======================================================================================
 
 // TODO: append this to the bottom of each enum value when hovering for a description
@@ -438,13 +354,14 @@ impl Default for RequiredEnum {
 /// Note that positive thoughts have a negative number, as they _reduce_ stress. Numbers closer
 /// to 1 or -1 have the strongest effect on stress (remember, these are *divisors*).
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum EmotionTypeEnum {
     /// Emotion: Anything
     ///
     /// Divisor: 0
     #[serde(alias = "ANYTHING")]
+    #[default]
     Anything,
     /// Emotion: Acceptance
     ///
@@ -1092,8 +1009,3 @@ pub enum EmotionTypeEnum {
     #[serde(alias = "ADMIRATION")]
     Admiration,
 }
-impl Default for EmotionTypeEnum {
-    fn default() -> Self {
-        Self::Anything
-    }
-}
@@ -573,7 +573,6 @@ pub struct CeCanDoInteraction {
     /// Arguments for `[CE_CAN_DO_INTERACTION:...]`
     #[serde(alias = "CE_CAN_DO_INTERACTION")]
     pub ce_can_do_interaction: Option<CeXNoSevTokenArg>,
-    ///
     #[serde(alias = "CDI")]
     pub cdi: Vec<CdiTokenArg>,
 }
@@ -1,4 +1,4 @@
-use crate::core::{Choose, ReferenceTo, Flag};
+use crate::core::{Choose, ReferenceTo, Flag, impl_referenceable};
 use crate::structure::{MaterialStateEnum, MaterialTokenArg, PluralEnum};
 
 use serde::{Deserialize, Serialize};
@@ -166,13 +166,16 @@ pub struct TissueToken {
     // endregion ==================================================================================
 }
 
+impl_referenceable!(TissueToken, "TISSUE_TEMPLATE");
+
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 /// The shape of the tissue
 pub enum TissueShapeEnum {
     /// Regular layer tissue.
     #[serde(alias = "LAYER")]
+    #[default]
     Layer,
     /// Can be spun into thread at a farmer's workshop.
     /// Edge attacks will pass right through the tissue.
@@ -188,8 +191,3 @@ pub enum TissueShapeEnum {
     #[serde(alias = "CUSTOM")]
     Custom,
 }
-impl Default for TissueShapeEnum {
-    fn default() -> Self {
-        Self::Layer
-    }
-}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum BodyAttributeEnum {
     /// Alters the damage done in melee (increases velocity of weapon swings), increases muscle mass
@@ -20,6 +20,7 @@ pub enum BodyAttributeEnum {
     /// - 251–500: unquestionably weak
     /// - 0–250: unfathomably weak
     #[serde(alias = "STRENGTH")]
+    #[default]
     Strength,
     /// This attribute affects the speed of a creature's fast-moving gaits in the same way as
     /// strength — a creature with maximum agility and strength can run faster than a creature with
@@ -93,14 +94,9 @@ pub enum BodyAttributeEnum {
     #[serde(alias = "DISEASE_RESISTANCE")]
     DiseaseResistance,
 }
-impl Default for BodyAttributeEnum {
-    fn default() -> Self {
-        Self::Strength
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum SoulAttributeEnum {
     /// The dwarf median analytical ability of 1250 is slightly above average.
@@ -114,6 +110,7 @@ pub enum SoulAttributeEnum {
     /// - 251–500: a lousy intellect
     /// - 0–250: a stunning lack of analytical ability
     #[serde(alias = "ANALYTICAL_ABILITY")]
+    #[default]
     AnalyticalAbility,
     /// The dwarf median focus of 1500 is above average.
     /// - 2542–5000: unbreakable focus
@@ -266,8 +263,3 @@ pub enum SoulAttributeEnum {
     #[serde(alias = "SOCIAL_AWARENESS")]
     SocialAwareness,
 }
-impl Default for SoulAttributeEnum {
-    fn default() -> Self {
-        Self::AnalyticalAbility
-    }
-}
@@ -1,11 +1,12 @@
 use serde::{Deserialize, Serialize};
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum BiomeEnum {
     /// Mountain (ID: 0)
     #[serde(alias = "MOUNTAIN", alias = "MOUNTAINS")]
+    #[default]
     Mountain,
     /// Glacier (ID: 1)
     #[serde(alias = "GLACIER")]
@@ -248,9 +249,3 @@ pub enum BiomeEnum {
     #[serde(alias = "ANY_DESERT")]
     AnyDesert,
 }
-
-impl Default for BiomeEnum {
-    fn default() -> Self {
-        Self::Mountain
-    }
-}
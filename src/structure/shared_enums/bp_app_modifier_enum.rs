@@ -4,11 +4,12 @@ use serde::{Deserialize, Serialize};
 // the wiki groups these according to body part category, but categories are arbitrary and user definable!
 // TODO: Fill this out; this likely matches to specific description string ingame; maybe use that here.
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum BpAppModifersEnum {
     /// The height of the body part.
     #[serde(alias = "HEIGHT")]
+    #[default]
     Height,
     /// The length of the body part.
     #[serde(alias = "LENGTH")]
@@ -49,8 +50,3 @@ pub enum BpAppModifersEnum {
     #[serde(alias = "RASPY_VOICE")]
     RaspyVoice,
 }
-impl Default for BpAppModifersEnum {
-    fn default() -> Self {
-        Self::Height
-    }
-}
@@ -3,10 +3,11 @@ use serde::{Deserialize, Serialize};
 // TODO: maybe add descriptions for these where possible/practical;
 // copy from applicable creature tokens.
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum CreatureFlagEnum {
     #[serde(alias = "ALL_CASTES_ALIVE")]
+    #[default]
     AllCastesAlive,
     #[serde(alias = "ARTIFICIAL_HIVEABLE")]
     ArtificialHiveable,
@@ -246,17 +247,13 @@ pub enum CreatureFlagEnum {
     #[serde(alias = "VERMIN_SOIL_COLONY")]
     VerminSoilColony,
 }
-impl Default for CreatureFlagEnum {
-    fn default() -> Self {
-        Self::AllCastesAlive
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum CasteFlagEnum {
     #[serde(alias = "ADOPTS_OWNER")]
+    #[default]
     AdoptsOwner,
     #[serde(alias = "ALCOHOL_DEPENDENT")]
     AlcoholDependent,
@@ -615,8 +612,3 @@ pub enum CasteFlagEnum {
     #[serde(alias = "WEBIMMUNE")]
     Webimmune,
 }
-impl Default for CasteFlagEnum {
-    fn default() -> Self {
-        Self::AdoptsOwner
-    }
-}
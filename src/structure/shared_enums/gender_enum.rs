@@ -1,17 +1,12 @@
 use serde::{Deserialize, Serialize};
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum MaleOrFemaleEnum {
     #[serde(alias = "MALE")]
+    #[default]
     Male,
     #[serde(alias = "FEMALE")]
     Female,
 }
-
-impl Default for MaleOrFemaleEnum {
-    fn default() -> Self {
-        Self::Male
-    }
-}
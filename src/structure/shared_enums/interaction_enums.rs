@@ -1,11 +1,12 @@
 use serde::{Deserialize, Serialize};
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum EffectLocationEnum {
     /// A depth of 1/7 is sufficient for `IN_WATER`.
     #[serde(alias = "IN_WATER")]
+    #[default]
     InWater,
     /// A depth of 1/7 is sufficient for `IN_MAGMA`.
     #[serde(alias = "IN_MAGMA")]
@@ -19,21 +20,17 @@ pub enum EffectLocationEnum {
     #[serde(alias = "OUTSIDE")]
     Outside,
 }
-impl Default for EffectLocationEnum {
-    fn default() -> Self {
-        Self::InWater
-    }
-}
 
 // TODO: most of these are creature tokens; check if ANY creature token can be used for this
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum TargetPropertyEnum {
     /// Any corpse or body part that can become a zombie (heads, hands, etc.)
     ///
     /// Adding or removing this tag via a syndrome seems to have no effect.
     #[serde(alias = "FIT_FOR_ANIMATION")]
+    #[default]
     FitForAnimation,
     /// The target corpse's `UPPERBODY` must be attached.
     ///
@@ -124,14 +121,9 @@ pub enum TargetPropertyEnum {
     #[serde(alias = "UTTERANCES")]
     Utterances,
 }
-impl Default for TargetPropertyEnum {
-    fn default() -> Self {
-        Self::FitForAnimation
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum SynTransmittionMethodEnum {
     /// If the syndrome is tied to a material, creatures who eat or drink substances comprising,
@@ -142,6 +134,7 @@ pub enum SynTransmittionMethodEnum {
     /// This also applies to grazing creatures which happen to munch on a grass that has an
     /// ingestion-triggered syndrome tied to any of its constituent materials.
     #[serde(alias = "SYN_INGESTED")]
+    #[default]
     SynIngested,
     /// If the syndrome is tied to a material, the injection of this material into a creature's
     /// bloodstream will cause it to contract the syndrome if this token is included. Injection can
@@ -208,18 +201,14 @@ pub enum SynTransmittionMethodEnum {
     #[serde(alias = "SYN_INHALED")] // TODO mark as broken, see #83
     SynInhaled,
 }
-impl Default for SynTransmittionMethodEnum {
-    fn default() -> Self {
-        Self::SynIngested
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum BreathFlowEnum {
     /// Emits a wide cone of dragon fire that burns target creatures at a scorching 50000 °U .
     #[serde(alias = "DRAGONFIRE")]
+    #[default]
     Dragonfire,
     /// Emits a narrow cone of fire that burns target creatures at 11000 °U .
     #[serde(alias = "FIREJET")]
@@ -228,14 +217,9 @@ pub enum BreathFlowEnum {
     #[serde(alias = "FIREBALL")]
     Fireball,
 }
-impl Default for BreathFlowEnum {
-    fn default() -> Self {
-        Self::Dragonfire
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum BreathMaterialEnum {
     /// Shoots a trail of solid dust at the target. Appears to use cave-in dust physics, as the
@@ -244,6 +228,7 @@ pub enum BreathMaterialEnum {
     /// over walls. Creatures caught in the dust cloud will be covered with dust; this will
     /// trigger any associated contact syndromes.
     #[serde(alias = "TRAILING_DUST_FLOW")]
+    #[default]
     TrailingDustFlow,
     /// Shoots a trail of liquid mist at the target. Creatures caught in the vapor will be covered
     /// with the condensed liquid; this will trigger any associated contact syndromes.
@@ -325,8 +310,3 @@ pub enum BreathMaterialEnum {
     #[serde(alias = "WEB_SPRAY")]
     WebSpray,
 }
-impl Default for BreathMaterialEnum {
-    fn default() -> Self {
-        Self::TrailingDustFlow
-    }
-}
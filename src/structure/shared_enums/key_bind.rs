@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 /// For more key binds see: issue #76
 pub enum KeyBindEnum {
@@ -10,6 +10,7 @@ pub enum KeyBindEnum {
     #[serde(alias = "HOTKEY_KITCHEN_RENDER_FAT")]
     HotkeyKitchenRenderFat,
     #[serde(alias = "CUSTOM_A")]
+    #[default]
     CustomA,
     #[serde(alias = "CUSTOM_B")]
     CustomB,
@@ -218,9 +219,3 @@ pub enum KeyBindEnum {
     #[serde(alias = "CUSTOM_ALT_Z")]
     CustomAltZ,
 }
-
-impl Default for KeyBindEnum {
-    fn default() -> Self {
-        Self::CustomA
-    }
-}
@@ -1,11 +1,12 @@
 use serde::{Deserialize, Serialize};
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum LaborEnum {
     /// [Mining](https://dwarffortresswiki.org/index.php/Mining)
     #[serde(alias = "MINE")]
+    #[default]
     Mine,
     /// [Stone hauling](https://dwarffortresswiki.org/index.php/Stone_hauling)
     #[serde(alias = "HAUL_STONE")]
@@ -248,9 +249,3 @@ pub enum LaborEnum {
     #[serde(alias = "BUILD_CONSTRUCTION")]
     BuildConstruction,
 }
-
-impl Default for LaborEnum {
-    fn default() -> Self {
-        Self::Mine
-    }
-}
@@ -1,24 +1,20 @@
 use serde::{Deserialize, Serialize};
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum AllOrAllSolidEnum {
     /// All material states.
     #[serde(alias = "ALL")]
+    #[default]
     All,
     /// All solid material states.
     #[serde(alias = "ALL_SOLID")]
     AllSolid,
 }
-impl Default for AllOrAllSolidEnum {
-    fn default() -> Self {
-        Self::All
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum MaterialStateEnum {
     /// Material is in a solid state.
@@ -26,6 +22,7 @@ pub enum MaterialStateEnum {
     /// - Iron at room temperature
     /// - Water blow freezing (ice)
     #[serde(alias = "SOLID")]
+    #[default]
     Solid,
     /// Material is in a liquid state.
     /// Like:
@@ -58,8 +55,3 @@ pub enum MaterialStateEnum {
     #[serde(alias = "SOLID_PRESSED")]
     SolidPressed,
 }
-impl Default for MaterialStateEnum {
-    fn default() -> Self {
-        Self::Solid
-    }
-}
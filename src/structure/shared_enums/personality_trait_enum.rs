@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum PersonalityTraitEnum {
     /// - 91-100: is always in love with somebody and easily develops positive feelings
@@ -12,6 +12,7 @@ pub enum PersonalityTraitEnum {
     /// - 10-24: is not the type to fall in love or even develop positive feelings
     /// - 0-9: never falls in love or develops positive feelings toward anything
     #[serde(alias = "LOVE_PROPENSITY")]
+    #[default]
     LovePropensity,
     /// - 91-100: is often inflamed by hatred and easily develops hatred toward things
     /// - 76-90: is prone to hatreds and often develops negative feelings
@@ -498,8 +499,3 @@ pub enum PersonalityTraitEnum {
     #[serde(alias = "ART_INCLINED")]
     ArtInclined,
 }
-impl Default for PersonalityTraitEnum {
-    fn default() -> Self {
-        Self::LovePropensity
-    }
-}
@@ -1,10 +1,11 @@
 use serde::{Deserialize, Serialize};
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum WeaponSkillEnum {
     #[serde(alias = "AXE")]
+    #[default]
     Axe,
     #[serde(alias = "SWORD")]
     Sword,
@@ -29,17 +30,13 @@ pub enum WeaponSkillEnum {
     #[serde(alias = "BLOWGUN")]
     Blowgun,
 }
-impl Default for WeaponSkillEnum {
-    fn default() -> Self {
-        Self::Axe
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum MusicSkillEnum {
     #[serde(alias = "PLAY_KEYBOARD_INSTRUMENT")]
+    #[default]
     PlayKeyboardInstrument,
     #[serde(alias = "PLAY_STRINGED_INSTRUMENT")]
     PlayStringedInstrument,
@@ -48,17 +45,13 @@ pub enum MusicSkillEnum {
     #[serde(alias = "PLAY_PERCUSSION_INSTRUMENT")]
     PlayPercussionInstrument,
 }
-impl Default for MusicSkillEnum {
-    fn default() -> Self {
-        Self::PlayKeyboardInstrument
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum SkillEnum {
     #[serde(alias = "MINING")]
+    #[default]
     Mining,
     #[serde(alias = "WOODCUTTING")]
     Woodcutting,
@@ -333,8 +326,3 @@ pub enum SkillEnum {
     #[serde(alias = "RIDING")]
     Riding,
 }
-impl Default for SkillEnum {
-    fn default() -> Self {
-        Self::Mining
-    }
-}
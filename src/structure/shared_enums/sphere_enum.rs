@@ -1,12 +1,13 @@
 use serde::{Deserialize, Serialize};
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum SphereEnum {
     /// Sphere: `AGRICULTURE`
     /// - Friend Spheres: `FOOD`, `FERTILITY`, `RAIN`
     #[serde(alias = "AGRICULTURE")]
+    #[default]
     Agriculture,
     /// Sphere: `ANIMALS`
     /// - Friend Spheres: `PLANTS`
@@ -591,9 +592,3 @@ pub enum SphereEnum {
     #[serde(alias = "YOUTH")]
     Youth,
 }
-
-impl Default for SphereEnum {
-    fn default() -> Self {
-        Self::Agriculture
-    }
-}
@@ -1,11 +1,12 @@
 use serde::{Deserialize, Serialize};
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum UnitTypeEnum {
     /// https://dwarffortresswiki.org/index.php/Miner
     #[serde(alias = "MINER")]
+    #[default]
     Miner,
     /// https://dwarffortresswiki.org/index.php/Woodworker
     #[serde(alias = "WOODWORKER")]
@@ -410,9 +411,3 @@ pub enum UnitTypeEnum {
     #[serde(alias = "MESSENGER")]
     Messenger,
 }
-
-impl Default for UnitTypeEnum {
-    fn default() -> Self {
-        Self::Miner
-    }
-}
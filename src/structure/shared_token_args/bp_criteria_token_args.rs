@@ -1,15 +1,14 @@
-use crate::core::{Reference, ReferenceTo};
+use crate::core::Reference;
 
 use serde::{Deserialize, Serialize};
 
-use crate::structure::BodyToken;
-
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum BodyPartTypeEnum {
     /// Marks the body part as an opening in the body. If it is `[EMBEDDED]`, it cannot be gouged.
     #[serde(alias = "APERTURE")]
+    #[default]
     Aperture,
     /// Body part is used to breathe. If all body parts with `[BREATHE]` are damaged or destroyed,
     /// the creature will suffocate unless it has the `[NOBREATHE]` tag. Note that bruising counts
@@ -169,11 +168,6 @@ pub enum BodyPartTypeEnum {
     #[serde(alias = "GELDABLE")]
     Geldable,
 }
-impl Default for BodyPartTypeEnum {
-    fn default() -> Self {
-        Self::Aperture
-    }
-}
 
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -185,7 +179,7 @@ pub enum BpCriteriaTokenArg {
     /// Most tokens applied to a given body part count as "types".
     ByType(BodyPartTypeEnum),
     /// Select specific body parts by their token ID/reference.
-    ByToken(ReferenceTo<BodyToken>),
+    ByToken(Reference), // TODO: ref is a BP from inside BodyToken
 }
 impl Default for BpCriteriaTokenArg {
     fn default() -> Self {
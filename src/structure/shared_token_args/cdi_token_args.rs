@@ -7,11 +7,12 @@ use crate::structure::{
 use serde::{Deserialize, Serialize};
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum CdiTargetTypeEnum {
     /// The source needs to be able to see the target
     #[serde(alias = "LINE_OF_SIGHT")]
+    #[default]
     LineOfSight,
     /// The source needs to be able to touch the target
     #[serde(alias = "TOUCHABLE")]
@@ -27,18 +28,14 @@ pub enum CdiTargetTypeEnum {
     #[serde(alias = "SELF_ONLY")]
     SelfOnly,
 }
-impl Default for CdiTargetTypeEnum {
-    fn default() -> Self {
-        Self::LineOfSight
-    }
-}
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum UsageHintEnum {
     /// Creatures will target 'friendly' creatures, usually at random.
     #[serde(alias = "GREETING")]
+    #[default]
     Greeting,
     /// Targets enemy creatures in combat. If the interaction specifies `SELF_ONLY`, CPU-controlled
     /// creatures will never use it.
@@ -80,11 +77,6 @@ pub enum UsageHintEnum {
     #[serde(alias = "MAJOR_CURSE")]
     MajorCurse,
 }
-impl Default for UsageHintEnum {
-    fn default() -> Self {
-        Self::Greeting
-    }
-}
 
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -10,14 +10,10 @@ pub struct ItemReferenceArg {
 }
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum NoSubtypeEnum {
     #[serde(alias = "NONE", alias = "NO_SUBTYPE")]
+    #[default]
     None,
 }
-impl Default for NoSubtypeEnum {
-    fn default() -> Self {
-        Self::None
-    }
-}
@@ -150,10 +150,11 @@ impl Default for MaterialTypeEnum {
 }
 
 #[serde_with::skip_serializing_none]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 
 pub enum CoalMaterialEnum {
     #[serde(alias = "CHARCOAL")]
+    #[default]
     Charcoal,
     #[serde(alias = "COKE")]
     Coke,
@@ -162,8 +163,3 @@ pub enum CoalMaterialEnum {
     #[serde(alias = "NO_MATGLOSS")]
     NoMatgloss,
 }
-impl Default for CoalMaterialEnum {
-    fn default() -> Self {
-        Self::Charcoal
-    }
-}
@@ -0,0 +1,241 @@
+//! Raw tokens as plain text, for the parts of the game that work on tokens before they are
+//! parsed: creature variations, `COPY_TAGS_FROM` and the like.
+
+use std::fmt;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::encoding::read_raw_file;
+use crate::raw_set::{RawObject, SourcedObject};
+use crate::structure::DFRaw;
+use crate::writer::write_raw_object;
+
+/// A single `[NAME:ARG:ARG]` token.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Tag {
+    pub name: String,
+    pub args: Vec<String>,
+}
+
+impl Tag {
+    pub fn new(name: impl Into<String>, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            name: name.into(),
+            args: args.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Parses the inside of a token, e.g. `BODY:QUADRUPED:2EYES`.
+    pub fn parse(text: &str) -> Self {
+        let mut parts = split_args(text).into_iter();
+        Self {
+            name: parts.next().unwrap_or_default(),
+            args: parts.collect(),
+        }
+    }
+
+    /// The name followed by the arguments.
+    pub fn parts(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.name.as_str()).chain(self.args.iter().map(String::as_str))
+    }
+
+    /// The inside of the token, e.g. `BODY:QUADRUPED:2EYES`.
+    pub fn text(&self) -> String {
+        self.parts().collect::<Vec<_>>().join(":")
+    }
+
+    /// Whether this token starts with all parts of `prefix`, comparing whole arguments:
+    /// `[BODY:HUMANOID:2EYES]` starts with `[BODY:HUMANOID]` but not with `[BODY:HUMAN]`.
+    pub fn starts_with(&self, prefix: &Tag) -> bool {
+        let mut parts = self.parts();
+        prefix.parts().all(|part| parts.next() == Some(part))
+    }
+
+    pub fn is(&self, name: &str) -> bool {
+        self.name == name
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}]", self.text())
+    }
+}
+
+/// Splits token text on `:`, keeping quoted characters like `':'` whole.
+fn split_args(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut parts = vec![];
+    let mut current = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if current.is_empty() && chars[i] == '\'' && chars.get(i + 2) == Some(&'\'') {
+            current.extend(&chars[i..i + 3]);
+            i += 3;
+            continue;
+        }
+        match chars[i] {
+            ':' => parts.push(std::mem::take(&mut current)),
+            c => current.push(c),
+        }
+        i += 1;
+    }
+    parts.push(current);
+    parts
+}
+
+/// Finds every token in raw text, with the 0-based line it is on. Anything outside brackets is
+/// a comment, and so are tokens that aren't closed on the same line.
+pub fn parse_tags_with_lines(source: &str) -> Vec<(usize, Tag)> {
+    let mut tags = vec![];
+    for (number, line) in source.lines().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            if chars[i] != '[' {
+                i += 1;
+                continue;
+            }
+            let start = i + 1;
+            let mut end = start;
+            while end < chars.len() && chars[end] != ']' {
+                // Skip over quoted characters, which may be `]` themselves.
+                if chars[end] == '\''
+                    && chars.get(end + 2) == Some(&'\'')
+                    && (end == start || chars[end - 1] == ':')
+                {
+                    end += 3;
+                } else {
+                    end += 1;
+                }
+            }
+            if end >= chars.len() {
+                break;
+            }
+            let text: String = chars[start..end].iter().collect();
+            tags.push((number, Tag::parse(&text)));
+            i = end + 1;
+        }
+    }
+    tags
+}
+
+/// Finds every token in raw text.
+pub fn parse_tags(source: &str) -> Vec<Tag> {
+    parse_tags_with_lines(source)
+        .into_iter()
+        .map(|(_, tag)| tag)
+        .collect()
+}
+
+/// Writes tokens one per line.
+pub fn write_tags<'a>(tags: impl IntoIterator<Item = &'a Tag>) -> String {
+    tags.into_iter().map(|tag| format!("{}\n", tag)).collect()
+}
+
+/// Parses tokens as a raw file with a single `[OBJECT:...]` of the given type.
+///
+/// Fails with the [`ParseReport`](crate::ParseReport) when df_ls has diagnostics for any of the
+/// tokens, naming the tokens they are on, instead of leaving those tokens out.
+pub(crate) fn parse_tags_as_raw(object_type: &str, tags: &[Tag]) -> Result<DFRaw> {
+    let header = format!("tags\n\n[OBJECT:{}]\n\n", object_type);
+    let report = crate::parse_lossy(&format!("{}{}", header, write_tags(tags)))?;
+    if report.diagnostics.is_empty() {
+        return Ok(report.raw);
+    }
+    // `write_tags` writes a token per line, so the line of a diagnostic gives its token.
+    let first_line = header.lines().count();
+    let mut rejected: Vec<String> = report
+        .diagnostics
+        .iter()
+        .filter_map(|diagnostic| diagnostic.span.start.line.checked_sub(first_line))
+        .filter_map(|index| tags.get(index))
+        .map(Tag::to_string)
+        .collect();
+    rejected.dedup();
+    let message = format!("df_ls does not accept {}", rejected.join(", "));
+    Err(anyhow::Error::new(report).context(message))
+}
+
+/// Every token that starts an object of the given `[OBJECT:...]` type.
+pub(crate) fn object_headers(object_type: &str) -> &'static [&'static str] {
+    match object_type {
+        "BODY" => &["BODY", "BODYGLOSS"],
+        "BUILDING" => &["BUILDING_WORKSHOP", "BUILDING_FURNACE"],
+        "DESCRIPTOR_COLOR" => &["COLOR"],
+        "DESCRIPTOR_PATTERN" => &["COLOR_PATTERN"],
+        "DESCRIPTOR_SHAPE" => &["SHAPE"],
+        "GRAPHICS" => &["TILE_PAGE", "CREATURE_GRAPHICS"],
+        "ITEM" => &[
+            "ITEM_AMMO",
+            "ITEM_ARMOR",
+            "ITEM_FOOD",
+            "ITEM_GLOVES",
+            "ITEM_HELM",
+            "ITEM_INSTRUMENT",
+            "ITEM_PANTS",
+            "ITEM_SHIELD",
+            "ITEM_SHOES",
+            "ITEM_SIEGEAMMO",
+            "ITEM_TOOL",
+            "ITEM_TOY",
+            "ITEM_TRAPCOMP",
+            "ITEM_WEAPON",
+        ],
+        "LANGUAGE" => &["WORD", "SYMBOL", "TRANSLATION"],
+        "BODY_DETAIL_PLAN" => &["BODY_DETAIL_PLAN"],
+        "CREATURE" => &["CREATURE"],
+        "CREATURE_VARIATION" => &["CREATURE_VARIATION"],
+        "ENTITY" => &["ENTITY"],
+        "INTERACTION" => &["INTERACTION"],
+        "INORGANIC" => &["INORGANIC"],
+        "MATERIAL_TEMPLATE" => &["MATERIAL_TEMPLATE"],
+        "PLANT" => &["PLANT"],
+        "REACTION" => &["REACTION"],
+        "TISSUE_TEMPLATE" => &["TISSUE_TEMPLATE"],
+        _ => &[],
+    }
+}
+
+impl RawObject {
+    /// The object written back out as tokens. These are in the order of our structures, not
+    /// the order of the source; use [`SourcedObject::tags`] when the order matters.
+    pub fn tags(&self) -> Result<Vec<Tag>> {
+        Ok(parse_tags(&write_raw_object(self)?))
+    }
+}
+
+impl SourcedObject {
+    /// The tokens of this object as written in its source file, header first. Objects without
+    /// a known source line are written back out instead, see [`RawObject::tags`].
+    pub fn tags(&self) -> Result<Vec<Tag>> {
+        let line = match self.source.line {
+            Some(line) => line,
+            None => return self.object.tags(),
+        };
+        let source = read_raw_file(&self.source.path, self.source.encoding)?;
+        self.tags_from(&parse_tags_with_lines(&source), line)
+    }
+
+    /// The tokens of this object, taken from the tokens of its source file.
+    pub(crate) fn tags_from(&self, source: &[(usize, Tag)], line: usize) -> Result<Vec<Tag>> {
+        let header = self.object.header_token();
+        let headers = object_headers(self.object.object_type());
+        let mut tags = source
+            .iter()
+            .skip_while(|(number, tag)| *number < line || !tag.is(header))
+            .map(|(_, tag)| tag.clone());
+        let mut object: Vec<Tag> = tags.next().into_iter().collect();
+        object.extend(
+            tags.take_while(|tag| !tag.is("OBJECT") && !headers.contains(&tag.name.as_str())),
+        );
+        anyhow::ensure!(
+            !object.is_empty(),
+            "{}: could not find [{}] in the source",
+            self.source,
+            header
+        );
+        Ok(object)
+    }
+}
@@ -0,0 +1,273 @@
+//! The physical units of material properties, which DF keeps as whole numbers: temperatures in
+//! Urist degrees, where 10000 is freezing and each degree is a Fahrenheit degree, yields and
+//! fractures in kPa, strains at yield in 1/100000, densities in kg/m³, molar masses in mg/mol and
+//! specific heats in J/(kg·K).
+//!
+//! In YAML, these fields take a value with a unit too, like `melting_point: 1370C` or
+//! `tensile_yield: 430MPa`.
+
+use anyhow::{bail, Result};
+
+/// A temperature in Urist degrees, like `MELTING_POINT` takes it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Temperature(pub u32);
+
+/// A pressure in kPa, like `TENSILE_YIELD` takes it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pressure(pub u32);
+
+/// A strain in 1/100000, like `TENSILE_STRAIN_AT_YIELD` takes it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Strain(pub u32);
+
+/// A density in kg/m³, like `SOLID_DENSITY` takes it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Density(pub u32);
+
+/// A molar mass in mg/mol, like `MOLAR_MASS` takes it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MolarMass(pub u32);
+
+/// A specific heat in J/(kg·K), like `SPEC_HEAT` takes it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SpecificHeat(pub u32);
+
+/// The Urist temperature of 0 °F.
+const URIST_AT_ZERO_F: f64 = 9968.0;
+
+impl Temperature {
+    pub fn from_fahrenheit(fahrenheit: f64) -> Self {
+        Self(round(fahrenheit + URIST_AT_ZERO_F))
+    }
+
+    pub fn from_celsius(celsius: f64) -> Self {
+        Self::from_fahrenheit(celsius * 9.0 / 5.0 + 32.0)
+    }
+
+    pub fn from_kelvin(kelvin: f64) -> Self {
+        Self::from_celsius(kelvin - 273.15)
+    }
+
+    pub fn fahrenheit(self) -> f64 {
+        self.0 as f64 - URIST_AT_ZERO_F
+    }
+
+    pub fn celsius(self) -> f64 {
+        (self.fahrenheit() - 32.0) * 5.0 / 9.0
+    }
+
+    pub fn kelvin(self) -> f64 {
+        self.celsius() + 273.15
+    }
+}
+
+impl Pressure {
+    pub fn from_pascals(pascals: f64) -> Self {
+        Self(round(pascals / 1000.0))
+    }
+
+    pub fn pascals(self) -> f64 {
+        self.0 as f64 * 1000.0
+    }
+
+    pub fn megapascals(self) -> f64 {
+        self.0 as f64 / 1000.0
+    }
+}
+
+impl Strain {
+    /// A strain as a ratio, like 0.0094 for 0.94%.
+    pub fn from_ratio(ratio: f64) -> Self {
+        Self(round(ratio * 100_000.0))
+    }
+
+    pub fn ratio(self) -> f64 {
+        self.0 as f64 / 100_000.0
+    }
+}
+
+impl Density {
+    pub fn from_grams_per_cm3(density: f64) -> Self {
+        Self(round(density * 1000.0))
+    }
+
+    pub fn grams_per_cm3(self) -> f64 {
+        self.0 as f64 / 1000.0
+    }
+}
+
+impl MolarMass {
+    pub fn from_grams_per_mol(molar_mass: f64) -> Self {
+        Self(round(molar_mass * 1000.0))
+    }
+
+    pub fn grams_per_mol(self) -> f64 {
+        self.0 as f64 / 1000.0
+    }
+}
+
+impl SpecificHeat {
+    pub fn from_joules_per_gram_kelvin(specific_heat: f64) -> Self {
+        Self(round(specific_heat * 1000.0))
+    }
+
+    pub fn joules_per_gram_kelvin(self) -> f64 {
+        self.0 as f64 / 1000.0
+    }
+}
+
+/// What a field measures.
+#[derive(Clone, Copy, PartialEq)]
+enum Quantity {
+    Temperature,
+    Pressure,
+    Strain,
+    Density,
+    MolarMass,
+    SpecificHeat,
+}
+
+/// The fields with units by name, or by the end of their name when it starts with `_`. The first
+/// that matches counts, so strains come before yields.
+const FIELD_QUANTITIES: &[(&str, Quantity)] = &[
+    ("spec_heat", Quantity::SpecificHeat),
+    ("heatdam_point", Quantity::Temperature),
+    ("colddam_point", Quantity::Temperature),
+    ("ignite_point", Quantity::Temperature),
+    ("melting_point", Quantity::Temperature),
+    ("boiling_point", Quantity::Temperature),
+    ("mat_fixed_temp", Quantity::Temperature),
+    ("solid_density", Quantity::Density),
+    ("liquid_density", Quantity::Density),
+    ("molar_mass", Quantity::MolarMass),
+    ("_strain_at_yield", Quantity::Strain),
+    ("_yield", Quantity::Pressure),
+    ("_fracture", Quantity::Pressure),
+];
+
+/// Turns a value in some unit into the DF value.
+type Convert = fn(f64) -> u32;
+
+/// The units of each quantity, with what turns a value in that unit into the DF value.
+const UNITS: &[(Quantity, &str, Convert)] = &[
+    (Quantity::Temperature, "U", round),
+    (Quantity::Temperature, "F", |value| {
+        Temperature::from_fahrenheit(value).0
+    }),
+    (Quantity::Temperature, "C", |value| {
+        Temperature::from_celsius(value).0
+    }),
+    (Quantity::Temperature, "K", |value| {
+        Temperature::from_kelvin(value).0
+    }),
+    (Quantity::Pressure, "Pa", |value| {
+        Pressure::from_pascals(value).0
+    }),
+    (Quantity::Pressure, "kPa", round),
+    (Quantity::Pressure, "MPa", |value| {
+        Pressure::from_pascals(value * 1e6).0
+    }),
+    (Quantity::Pressure, "GPa", |value| {
+        Pressure::from_pascals(value * 1e9).0
+    }),
+    (Quantity::Strain, "%", |value| {
+        Strain::from_ratio(value / 100.0).0
+    }),
+    (Quantity::Density, "kg/m3", round),
+    (Quantity::Density, "g/cm3", |value| {
+        Density::from_grams_per_cm3(value).0
+    }),
+    (Quantity::MolarMass, "mg/mol", round),
+    (Quantity::MolarMass, "g/mol", |value| {
+        MolarMass::from_grams_per_mol(value).0
+    }),
+    (Quantity::SpecificHeat, "J/kgK", round),
+    (Quantity::SpecificHeat, "J/gK", |value| {
+        SpecificHeat::from_joules_per_gram_kelvin(value).0
+    }),
+];
+
+/// The DF value of a field written with a unit, like `1370C` for `melting_point`. `None` when the
+/// field has no unit or the text isn't a number with a unit, like `NONE`.
+pub fn parse_quantity(field: &str, text: &str) -> Result<Option<u32>> {
+    let quantity = FIELD_QUANTITIES
+        .iter()
+        .find(|(name, _)| match name.strip_prefix('_') {
+            Some(_) => field.ends_with(name),
+            None => field == *name,
+        });
+    let quantity = match quantity {
+        Some((_, quantity)) => *quantity,
+        None => return Ok(None),
+    };
+    let text = text.trim();
+    let split = text
+        .find(|char: char| !(char.is_ascii_digit() || ".+-".contains(char)))
+        .unwrap_or(text.len());
+    let (number, unit) = (&text[..split], text[split..].trim());
+    if unit.is_empty() {
+        return Ok(None);
+    }
+    let number: f64 = match number.parse() {
+        Ok(number) => number,
+        Err(_) => return Ok(None),
+    };
+    let units = UNITS.iter().filter(|(other, _, _)| *other == quantity);
+    match units.clone().find(|(_, name, _)| *name == unit) {
+        Some((_, _, convert)) => Ok(Some(convert(number))),
+        None => bail!(
+            "unknown unit `{}`, expected one of {}",
+            unit,
+            units
+                .map(|(_, name, _)| *name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn round(value: f64) -> u32 {
+    value.round().max(0.0) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn units() -> Result<()> {
+        use crate::core::Choose;
+
+        assert_eq!(Temperature(10000).celsius(), 0.0);
+        assert_eq!(Temperature::from_celsius(1510.0), Temperature(12718));
+        assert_eq!(Temperature::from_kelvin(273.15), Temperature(10000));
+        assert_eq!(Temperature(10212).fahrenheit(), 244.0);
+        assert_eq!(Pressure(430000).megapascals(), 430.0);
+        assert_eq!(Strain::from_ratio(0.0094), Strain(940));
+        assert_eq!(Density::from_grams_per_cm3(7.85), Density(7850));
+        assert_eq!(MolarMass(55845).grams_per_mol(), 55.845);
+        assert_eq!(parse_quantity("melting_point", "1370C")?, Some(12466));
+        assert_eq!(parse_quantity("tensile_strain_at_yield", "0.94%")?, Some(940));
+        assert_eq!(parse_quantity("melting_point", "NONE")?, None);
+        assert_eq!(parse_quantity("material_value", "30C")?, None);
+        assert!(parse_quantity("tensile_yield", "430psi").is_err());
+
+        let definitions = Definitions::from_yaml(
+            "
+steel:
+  type: inorganic
+  melting_point: 1510C
+  tensile_yield: 430MPa
+",
+        )?;
+        let steel = definitions.evaluate("steel")?;
+        assert_eq!(steel.fields["melting_point"], 12718);
+        assert_eq!(steel.fields["tensile_yield"], 430000);
+
+        let raw = from_yaml("inorganic:\n  IRON: { melting_point: 1538C, shear_yield: 1GPa }")?;
+        let iron = &raw.object_tokens[0].inorganic_tokens[0];
+        assert_eq!(iron.melting_point, Some(Choose::Choice1(12768)));
+        assert_eq!(iron.shear_yield, Some(1000000));
+        Ok(())
+    }
+}
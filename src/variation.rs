@@ -0,0 +1,634 @@
+//! Applying creature variations the way the game does when it loads creatures.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::encoding::read_raw_file;
+use crate::raw_set::{LoadError, RawObject, RawSet, SourcedObject};
+use crate::report::ParseReport;
+use crate::structure::{CreatureToken, CreatureVariationToken};
+use crate::tags::{parse_tags_as_raw, parse_tags_with_lines, Tag};
+
+/// The `CV_*` tokens of a creature variation, ready to be applied to the tokens of a creature.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct CreatureVariation {
+    /// `CV_NEW_TAG` and `CV_NEW_CTAG`, in order.
+    pub new_tags: Vec<VariationTag>,
+    /// `CV_REMOVE_TAG` and `CV_REMOVE_CTAG`.
+    pub remove_tags: Vec<VariationTag>,
+    /// `CV_CONVERT_TAG` and `CV_CONVERT_CTAG` blocks, in order.
+    pub conversions: Vec<Conversion>,
+}
+
+/// Only apply when argument `arg` (1-based) of `APPLY_CREATURE_VARIATION` is `value`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Condition {
+    pub arg: usize,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct VariationTag {
+    pub condition: Option<Condition>,
+    pub tag: Tag,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Conversion {
+    pub condition: Option<Condition>,
+    /// Only tokens starting with this are converted.
+    pub master: Tag,
+    pub target: String,
+    /// What `target` is replaced with; it is removed when this is empty.
+    pub replacement: String,
+}
+
+impl CreatureVariation {
+    /// Collects the `CV_*` tokens, ignoring everything else.
+    pub fn from_tags<'a>(tags: impl IntoIterator<Item = &'a Tag>) -> Self {
+        let mut variation = Self::default();
+        for tag in tags {
+            let args = &tag.args;
+            match tag.name.as_str() {
+                "CV_NEW_TAG" | "CV_ADD_TAG" => variation.new_tags.extend(VariationTag::new(args)),
+                "CV_REMOVE_TAG" => variation.remove_tags.extend(VariationTag::new(args)),
+                "CV_NEW_CTAG" | "CV_ADD_CTAG" => {
+                    variation.new_tags.extend(VariationTag::conditional(args))
+                }
+                "CV_REMOVE_CTAG" => variation
+                    .remove_tags
+                    .extend(VariationTag::conditional(args)),
+                "CV_CONVERT_TAG" => variation.conversions.push(Conversion::default()),
+                "CV_CONVERT_CTAG" => variation.conversions.push(Conversion {
+                    condition: Condition::parse(args),
+                    ..Default::default()
+                }),
+                "CVCT_MASTER" | "CVCT_TARGET" | "CVCT_REPLACEMENT" => {
+                    let conversion = match variation.conversions.last_mut() {
+                        Some(conversion) => conversion,
+                        None => continue,
+                    };
+                    let value = args.join(":");
+                    match tag.name.as_str() {
+                        "CVCT_MASTER" => conversion.master = Tag::parse(&value),
+                        "CVCT_TARGET" => conversion.target = value,
+                        _ => conversion.replacement = value,
+                    }
+                }
+                _ => {}
+            }
+        }
+        variation
+    }
+
+    pub fn from_token(token: &CreatureVariationToken) -> Result<Self> {
+        Ok(Self::from_tags(
+            &RawObject::CreatureVariation(token.clone()).tags()?,
+        ))
+    }
+
+    /// Applies the variation to the tokens of a creature, the way `APPLY_CREATURE_VARIATION`
+    /// does: every token starting with a removed tag is removed, the rest are converted (the
+    /// last conversion block first) and the new tags are inserted at `insert_at`, which is moved
+    /// past them.
+    ///
+    /// `args` are the arguments given after the variation ID, which replace `!ARGn`.
+    pub fn apply(&self, tags: &mut Vec<Tag>, insert_at: &mut usize, args: &[String]) {
+        let applies = |condition: &Option<Condition>| match condition {
+            Some(condition) => condition.is_met(args),
+            None => true,
+        };
+        let remove_tags: Vec<Tag> = self
+            .remove_tags
+            .iter()
+            .filter(|remove| applies(&remove.condition))
+            .map(|remove| substitute_tag(&remove.tag, args))
+            .collect();
+        let conversions: Vec<Conversion> = self
+            .conversions
+            .iter()
+            .filter(|conversion| applies(&conversion.condition) && !conversion.target.is_empty())
+            .map(|conversion| Conversion {
+                condition: None,
+                master: substitute_tag(&conversion.master, args),
+                target: substitute_args(&conversion.target, args),
+                replacement: substitute_args(&conversion.replacement, args),
+            })
+            .collect();
+
+        let mut kept = Vec::with_capacity(tags.len());
+        for (index, mut tag) in std::mem::take(tags).into_iter().enumerate() {
+            if remove_tags.iter().any(|remove| tag.starts_with(remove)) {
+                if index < *insert_at {
+                    *insert_at -= 1;
+                }
+                continue;
+            }
+            for conversion in conversions.iter().rev() {
+                if tag.starts_with(&conversion.master) {
+                    let text = tag
+                        .text()
+                        .replace(&conversion.target, &conversion.replacement);
+                    tag = Tag::parse(&text);
+                }
+            }
+            kept.push(tag);
+        }
+        *tags = kept;
+
+        *insert_at = (*insert_at).min(tags.len());
+        for new in &self.new_tags {
+            if !applies(&new.condition) {
+                continue;
+            }
+            let tag = substitute_tag(&new.tag, args);
+            // The only token a variation can't add.
+            if tag.is("APPLY_CREATURE_VARIATION") {
+                continue;
+            }
+            tags.insert(*insert_at, tag);
+            *insert_at += 1;
+        }
+    }
+}
+
+impl VariationTag {
+    fn new(args: &[String]) -> Option<Self> {
+        Some(Self {
+            condition: None,
+            tag: tag_from_args(args)?,
+        })
+    }
+
+    /// `n:value:TAG:...`
+    fn conditional(args: &[String]) -> Option<Self> {
+        Some(Self {
+            condition: Some(Condition::parse(args)?),
+            tag: tag_from_args(args.get(2..)?)?,
+        })
+    }
+}
+
+impl Condition {
+    fn parse(args: &[String]) -> Option<Self> {
+        Some(Self {
+            arg: args.first()?.parse().ok()?,
+            value: args.get(1)?.clone(),
+        })
+    }
+
+    fn is_met(&self, args: &[String]) -> bool {
+        self.arg
+            .checked_sub(1)
+            .and_then(|index| args.get(index))
+            .is_some_and(|arg| *arg == self.value)
+    }
+}
+
+fn tag_from_args(args: &[String]) -> Option<Tag> {
+    if args.is_empty() {
+        return None;
+    }
+    Some(Tag::parse(&args.join(":")))
+}
+
+fn substitute_tag(tag: &Tag, args: &[String]) -> Tag {
+    Tag::parse(&substitute_args(&tag.text(), args))
+}
+
+/// Replaces `!ARGn` with the `n`th argument, turning `|` in the argument into `:`.
+///
+/// Like the game, `n` is read with at most as many digits as the number of arguments has, and
+/// placeholders past the last argument are left alone. With three arguments, `!ARG5` stays
+/// `!ARG5` and `!ARG10` becomes the first argument followed by `0`.
+pub fn substitute_args(text: &str, args: &[String]) -> String {
+    let digits = args.len().to_string().len();
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(position) = rest.find("!ARG") {
+        out.push_str(&rest[..position]);
+        rest = &rest[position + 4..];
+        let length = rest
+            .bytes()
+            .take(digits)
+            .take_while(u8::is_ascii_digit)
+            .count();
+        match rest[..length].parse::<usize>() {
+            Ok(n) if (1..=args.len()).contains(&n) => {
+                out.push_str(&args[n - 1].replace('|', ":"));
+                rest = &rest[length..];
+            }
+            _ => out.push_str("!ARG"),
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Where [`apply_creature_variations`] looks up what a creature refers to.
+pub trait CreatureSource {
+    fn creature_variation(&mut self, id: &str) -> Result<Option<CreatureVariation>>;
+
+    /// The tokens of another creature for `COPY_TAGS_FROM`, header included and already
+    /// expanded.
+    fn creature_tags(&mut self, id: &str) -> Result<Option<Vec<Tag>>>;
+}
+
+/// Expands the tokens of a creature (header first) the way the game does while loading it:
+///
+/// - `COPY_TAGS_FROM` inserts the tokens of the other creature, after expanding it.
+/// - `APPLY_CREATURE_VARIATION` applies a variation to the tokens so far.
+/// - `CV_*` tokens written in the creature itself are collected and applied by the next
+///   `APPLY_CURRENT_CREATURE_VARIATION`.
+/// - `GO_TO_START`, `GO_TO_END` and `GO_TO_TAG` move the point where later tokens are inserted,
+///   which is otherwise the end of the creature.
+pub fn apply_creature_variations(
+    creature: &[Tag],
+    source: &mut impl CreatureSource,
+) -> Result<Vec<Tag>> {
+    let mut tags = Vec::with_capacity(creature.len());
+    let mut insert_at = 0;
+    let mut current = vec![];
+    for tag in creature {
+        match tag.name.as_str() {
+            "COPY_TAGS_FROM" => {
+                let id = tag.args.first().context("COPY_TAGS_FROM without an ID")?;
+                let copied = source
+                    .creature_tags(id)?
+                    .with_context(|| format!("Unknown creature `{}`", id))?;
+                // Everything but the header of the other creature.
+                let copied = copied.into_iter().skip(1);
+                let count = copied.len();
+                tags.splice(insert_at..insert_at, copied);
+                insert_at += count;
+            }
+            "APPLY_CREATURE_VARIATION" => {
+                let (id, args) = tag
+                    .args
+                    .split_first()
+                    .context("APPLY_CREATURE_VARIATION without an ID")?;
+                source
+                    .creature_variation(id)?
+                    .with_context(|| format!("Unknown creature variation `{}`", id))?
+                    .apply(&mut tags, &mut insert_at, args);
+            }
+            "APPLY_CURRENT_CREATURE_VARIATION" => {
+                CreatureVariation::from_tags(&current).apply(&mut tags, &mut insert_at, &[]);
+                current.clear();
+            }
+            name if name.starts_with("CV_") || name.starts_with("CVCT_") => {
+                current.push(tag.clone())
+            }
+            // Right after the header.
+            "GO_TO_START" => insert_at = tags.len().min(1),
+            "GO_TO_END" => insert_at = tags.len(),
+            "GO_TO_TAG" => {
+                let target = Tag::parse(&tag.args.join(":"));
+                insert_at = tags
+                    .iter()
+                    .position(|tag| tag.starts_with(&target))
+                    .map_or(tags.len(), |index| index + 1);
+            }
+            _ => {
+                tags.insert(insert_at, tag.clone());
+                insert_at += 1;
+            }
+        }
+    }
+    Ok(tags)
+}
+
+/// Parses the tokens of a single creature, header included. Fails with the
+/// [`ParseReport`](crate::ParseReport) when df_ls does not accept some of them.
+pub fn parse_creature_tags(tags: &[Tag]) -> Result<CreatureToken> {
+    parse_tags_as_raw("CREATURE", tags)?
+        .object_tokens
+        .into_iter()
+        .flat_map(|object_token| object_token.creature_tokens)
+        .next()
+        .context("No creature in the given tokens")
+}
+
+/// Expands the creatures of a raw set, reading each source file and expanding each creature
+/// only once.
+pub struct CreatureExpander<'a> {
+    raw_set: &'a RawSet,
+    files: HashMap<&'a Path, Vec<(usize, Tag)>>,
+    variations: HashMap<String, Option<CreatureVariation>>,
+    creatures: HashMap<String, Vec<Tag>>,
+    /// Creatures being expanded, to catch `COPY_TAGS_FROM` loops.
+    expanding: Vec<String>,
+}
+
+impl<'a> CreatureExpander<'a> {
+    pub fn new(raw_set: &'a RawSet) -> Self {
+        Self {
+            raw_set,
+            files: HashMap::new(),
+            variations: HashMap::new(),
+            creatures: HashMap::new(),
+            expanding: vec![],
+        }
+    }
+
+    /// Like [`SourcedObject::tags`], but reads every file only once.
+    fn object_tags(&mut self, sourced: &'a SourcedObject) -> Result<Vec<Tag>> {
+        let line = match sourced.source.line {
+            Some(line) => line,
+            None => return sourced.object.tags(),
+        };
+        let path = sourced.source.path.as_path();
+        if !self.files.contains_key(path) {
+            let source = read_raw_file(path, sourced.source.encoding)?;
+            self.files.insert(path, parse_tags_with_lines(&source));
+        }
+        sourced.tags_from(&self.files[path], line)
+    }
+
+    /// The tokens of an object as written in its source, if there is one with this ID.
+    pub(crate) fn find_tags(&mut self, object_type: &str, id: &str) -> Result<Option<Vec<Tag>>> {
+        match self.raw_set.find(object_type, id) {
+            Some(sourced) => self.object_tags(sourced).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// The tokens of a creature as the game sees them, see [`apply_creature_variations`].
+    pub fn creature_tags(&mut self, id: &str) -> Result<Vec<Tag>> {
+        if let Some(tags) = self.creatures.get(id) {
+            return Ok(tags.clone());
+        }
+        let sourced = self
+            .raw_set
+            .find("CREATURE", id)
+            .with_context(|| format!("Unknown creature `{}`", id))?;
+        anyhow::ensure!(
+            !self.expanding.iter().any(|expanding| expanding == id),
+            "`COPY_TAGS_FROM` loop: {} -> {}",
+            self.expanding.join(" -> "),
+            id
+        );
+        let creature = self.object_tags(sourced)?;
+        self.expanding.push(id.to_owned());
+        let tags = apply_creature_variations(&creature, self);
+        self.expanding.pop();
+        let tags =
+            tags.with_context(|| format!("{}: could not expand `{}`", sourced.source, id))?;
+        self.creatures.insert(id.to_owned(), tags.clone());
+        Ok(tags)
+    }
+
+    /// A self-contained creature, as the game sees it.
+    pub fn creature(&mut self, id: &str) -> Result<CreatureToken> {
+        parse_creature_tags(&self.creature_tags(id)?)
+    }
+}
+
+impl CreatureSource for CreatureExpander<'_> {
+    fn creature_variation(&mut self, id: &str) -> Result<Option<CreatureVariation>> {
+        if let Some(variation) = self.variations.get(id) {
+            return Ok(variation.clone());
+        }
+        let variation = self
+            .find_tags("CREATURE_VARIATION", id)?
+            .map(|tags| CreatureVariation::from_tags(&tags));
+        self.variations.insert(id.to_owned(), variation.clone());
+        Ok(variation)
+    }
+
+    fn creature_tags(&mut self, id: &str) -> Result<Option<Vec<Tag>>> {
+        if self.raw_set.find("CREATURE", id).is_none() {
+            return Ok(None);
+        }
+        CreatureExpander::creature_tags(self, id).map(Some)
+    }
+}
+
+impl RawSet {
+    /// Finds a creature variation by ID, read from its source so `!ARGn` placeholders are kept
+    /// exactly as written. The one loaded last wins.
+    pub fn creature_variation(&self, id: &str) -> Result<Option<CreatureVariation>> {
+        CreatureExpander::new(self).creature_variation(id)
+    }
+
+    /// The tokens of a creature with `COPY_TAGS_FROM` and its creature variations applied.
+    pub fn creature_tags(&self, id: &str) -> Result<Vec<Tag>> {
+        CreatureExpander::new(self).creature_tags(id)
+    }
+
+    /// A creature as the game sees it, with `COPY_TAGS_FROM` and its creature variations
+    /// applied.
+    pub fn expanded_creature(&self, id: &str) -> Result<CreatureToken> {
+        CreatureExpander::new(self).creature(id)
+    }
+
+    /// Replaces every creature with its expanded form, so queries see the creatures the game
+    /// sees. Creatures that can't be expanded, or whose expanded tokens df_ls does not accept,
+    /// are left as they are, with an error in `errors`.
+    pub fn expand_creatures(&mut self) {
+        let mut expanded = vec![];
+        let mut errors = vec![];
+        let mut expander = CreatureExpander::new(self);
+        for (index, sourced) in self.objects.iter().enumerate() {
+            let id = match (&sourced.object, sourced.object.id()) {
+                (RawObject::Creature(_), Some(id)) => id,
+                _ => continue,
+            };
+            match expander.creature(id) {
+                Ok(creature) => expanded.push((index, creature)),
+                Err(error) => {
+                    // Like the loader does for files, keep the diagnostics of tokens df_ls
+                    // does not accept once expanded.
+                    let diagnostics = error
+                        .downcast_ref::<ParseReport>()
+                        .map(|report| report.diagnostics.clone())
+                        .unwrap_or_default();
+                    let message = error
+                        .chain()
+                        .filter(|cause| !cause.is::<ParseReport>())
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(": ");
+                    errors.push(LoadError::Expand {
+                        path: sourced.source.path.clone(),
+                        id: id.to_owned(),
+                        message,
+                        diagnostics,
+                    })
+                }
+            }
+        }
+        for (index, creature) in expanded {
+            self.objects[index].object = RawObject::Creature(creature);
+        }
+        self.errors.extend(errors);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    /// Creatures and creature variations by ID, for expanding creatures without a raw set.
+    #[derive(Default)]
+    struct TestCreatures {
+        creatures: std::collections::HashMap<String, Vec<Tag>>,
+        variations: std::collections::HashMap<String, CreatureVariation>,
+    }
+    impl CreatureSource for TestCreatures {
+        fn creature_variation(&mut self, id: &str) -> Result<Option<CreatureVariation>> {
+            Ok(self.variations.get(id).cloned())
+        }
+        fn creature_tags(&mut self, id: &str) -> Result<Option<Vec<Tag>>> {
+            match self.creatures.get(id).cloned() {
+                Some(tags) => apply_creature_variations(&tags, self).map(Some),
+                None => Ok(None),
+            }
+        }
+    }
+    #[test]
+    fn creature_variation() -> Result<()> {
+        let variation = CreatureVariation::from_tags(&parse_tags(
+            "[CREATURE_VARIATION:ANIMAL_PERSON]
+                [CV_REMOVE_TAG:BODY:QUADRUPED]
+                [CV_NEW_TAG:BODY:HUMANOID:!ARG1]
+                [CV_NEW_TAG:NAME:!ARG2]
+                [CV_NEW_CTAG:3:SPEAKS:CAN_SPEAK]
+                [CV_NEW_CTAG:3:MUTE:MUTE]
+                [CV_CONVERT_TAG]
+                    [CVCT_MASTER:DESCRIPTION]
+                    [CVCT_TARGET:dog]
+                    [CVCT_REPLACEMENT:dog man]",
+        ));
+        let creature = parse_tags(
+            "[CREATURE:DOG_MAN]
+                [DESCRIPTION:A dog.]
+                [BODY:QUADRUPED:TAIL]
+                [BODY:QUADRUPED_NECK]
+                [BODY_SIZE:0:0:1000]
+                [APPLY_CREATURE_VARIATION:ANIMAL_PERSON:2EYES:dog man|dog men|dog man:SPEAKS]
+                [PET]
+                [CV_REMOVE_TAG:PET]
+                [APPLY_CURRENT_CREATURE_VARIATION]",
+        );
+        let mut source = TestCreatures::default();
+        source
+            .variations
+            .insert("ANIMAL_PERSON".to_owned(), variation);
+        let expanded = apply_creature_variations(&creature, &mut source)?;
+        assert_eq!(
+            write_tags(&expanded),
+            "[CREATURE:DOG_MAN]\n\
+             [DESCRIPTION:A dog man.]\n\
+             [BODY:QUADRUPED_NECK]\n\
+             [BODY_SIZE:0:0:1000]\n\
+             [BODY:HUMANOID:2EYES]\n\
+             [NAME:dog man:dog men:dog man]\n\
+             [CAN_SPEAK]\n"
+        );
+
+        let args = ["one".to_owned(), "two".to_owned(), "three".to_owned()];
+        assert_eq!(substitute_args("!ARG3:!ARG5:!ARG10", &args), "three:!ARG5:one0");
+        Ok(())
+    }
+    #[test]
+    fn unaccepted_creature_tags() {
+        let creature = parse_tags("[CREATURE:TOAD][BODY_SIZE:0:0:10][NOT_A_TOKEN:1]");
+        let error = parse_creature_tags(&creature).unwrap_err();
+        assert!(error.downcast_ref::<ParseReport>().is_some());
+        assert!(error.to_string().contains("[NOT_A_TOKEN:1]"), "{}", error);
+    }
+    #[test]
+    fn copy_tags_from() -> Result<()> {
+        let mut source = TestCreatures::default();
+        source.creatures.insert(
+            "MOTH".to_owned(),
+            parse_tags("[CREATURE:MOTH][NAME:moth:moths:moth][BODY:INSECT][CASTE:FEMALE][FEMALE]"),
+        );
+        source.variations.insert(
+            "GIANT".to_owned(),
+            CreatureVariation::from_tags(&parse_tags(
+                "[CV_REMOVE_TAG:NAME][CV_NEW_TAG:CHANGE_BODY_SIZE_PERC:500]",
+            )),
+        );
+        let creature = parse_tags(
+            "[CREATURE:GIANT_MOTH]
+                [COPY_TAGS_FROM:MOTH]
+                [APPLY_CREATURE_VARIATION:GIANT]
+                [CV_REMOVE_TAG:CHANGE_BODY_SIZE_PERC]
+                [APPLY_CURRENT_CREATURE_VARIATION]
+                [GO_TO_END]
+                [SELECT_CASTE:ALL]
+                [CHANGE_BODY_SIZE_PERC:2000]
+                [GO_TO_START]
+                [NAME:giant moth:giant moths:giant moth]
+                [GO_TO_TAG:BODY]
+                [BODY_SIZE:0:0:10]",
+        );
+        assert_eq!(
+            write_tags(&apply_creature_variations(&creature, &mut source)?),
+            "[CREATURE:GIANT_MOTH]\n\
+             [NAME:giant moth:giant moths:giant moth]\n\
+             [BODY:INSECT]\n\
+             [BODY_SIZE:0:0:10]\n\
+             [CASTE:FEMALE]\n\
+             [FEMALE]\n\
+             [SELECT_CASTE:ALL]\n\
+             [CHANGE_BODY_SIZE_PERC:2000]\n"
+        );
+        Ok(())
+    }
+    #[test]
+    fn unaccepted_expansion() -> Result<()> {
+        /// Removes the file when dropped, so it is cleaned up when the test fails too.
+        struct TempFile(std::path::PathBuf);
+        impl Drop for TempFile {
+            fn drop(&mut self) {
+                let _ = std::fs::remove_file(&self.0);
+            }
+        }
+        let file = TempFile(std::env::temp_dir().join(format!(
+            "domni_unaccepted_expansion_{}.txt",
+            std::process::id()
+        )));
+        std::fs::write(
+            &file.0,
+            "creature_unaccepted
+
+[OBJECT:CREATURE_VARIATION]
+
+[CREATURE_VARIATION:BROKEN]
+\t[CV_NEW_TAG:NOT_A_TOKEN]
+
+[OBJECT:CREATURE]
+
+[CREATURE:TOAD]
+\t[BODY_SIZE:0:0:10]
+\t[APPLY_CREATURE_VARIATION:BROKEN]
+",
+        )?;
+        let mut raws = RawSet::load([&file.0]);
+        assert!(raws.errors.is_empty(), "{:#?}", raws.errors);
+        let loaded = raws.objects.clone();
+        raws.expand_creatures();
+        match raws.errors.as_slice() {
+            [LoadError::Expand {
+                id,
+                message,
+                diagnostics,
+                ..
+            }] => {
+                assert_eq!(id, "TOAD");
+                assert!(message.contains("[NOT_A_TOKEN]"), "{}", message);
+                assert!(!diagnostics.is_empty());
+            }
+            errors => panic!("expected one expand error, got {:#?}", errors),
+        }
+        assert_eq!(raws.objects, loaded);
+        Ok(())
+    }
+}
@@ -0,0 +1,325 @@
+//! Writes the structures back out as Dwarf Fortress raw text.
+//!
+//! The writer works on the serde representation of the structures, so it picks up new tokens
+//! without changes here. Token names come from the field and variant names, see `token_names`
+//! for the exceptions.
+
+mod token_names;
+
+use std::fmt::Write;
+
+use anyhow::Result;
+
+use crate::core::DFChar;
+use crate::encoding::encode_cp437;
+use crate::node::{to_node, Node};
+use crate::raw_set::RawObject;
+use crate::structure::{DFRaw, ObjectToken};
+use token_names::{field_token, variant_token};
+
+/// Argument structs where every field is written as its own keyword followed by its value,
+/// e.g. `SEV:50:PROB:100:RESISTABLE`.
+const KEYWORD_ARG_STRUCTS: &[&str] = &[
+    "CeXTokenArg",
+    "CeXNoTargetTokenArg",
+    "CeXNoSevTokenArg",
+    "GaitFlagTokenArg",
+];
+
+/// Nested objects that open a scope in which most of their parent's tokens are also valid.
+/// They are written after every other nested object so nothing ends up inside them by accident.
+const SCOPE_STRUCTS: &[&str] = &["Caste", "SelectCaste"];
+
+/// Writes a complete raw file, including the header line and `[OBJECT:...]` tokens.
+pub fn write_raw(raw: &DFRaw) -> Result<String> {
+    let mut writer = RawWriter::default();
+    writer.out.push_str(&raw.header);
+    writer.out.push('\n');
+    for object_token in &raw.object_tokens {
+        writer.object_token(object_token)?;
+    }
+    Ok(writer.out)
+}
+
+/// Writes a complete raw file encoded as code page 437, the way the game stores its raws.
+pub fn write_raw_cp437(raw: &DFRaw) -> Result<Vec<u8>> {
+    encode_cp437(&write_raw(raw)?)
+}
+
+/// Writes the objects of an `ObjectToken`, each list preceded by its `[OBJECT:...]` token.
+pub fn write_object_token(object_token: &ObjectToken) -> Result<String> {
+    let mut writer = RawWriter::default();
+    writer.object_token(object_token)?;
+    Ok(writer.out)
+}
+
+/// Writes a single object, without an `[OBJECT:...]` token.
+pub fn write_raw_object(object: &RawObject) -> Result<String> {
+    let mut writer = RawWriter::default();
+    writer.object(&to_node(object)?, None, 0)?;
+    Ok(writer.out)
+}
+
+#[derive(Default)]
+struct RawWriter {
+    out: String,
+}
+
+impl RawWriter {
+    fn object_token(&mut self, object_token: &ObjectToken) -> Result<()> {
+        let fields = match to_node(object_token)? {
+            Node::Struct(_, fields) => fields,
+            node => anyhow::bail!("Expected `ObjectToken` to be a struct, got {:?}", node),
+        };
+        for (field, value) in fields {
+            let objects = match value {
+                Node::Seq(objects) if !objects.is_empty() => objects,
+                _ => continue,
+            };
+            let object_type = token_name("ObjectToken", field);
+            write!(self.out, "\n[OBJECT:{}]\n", object_type)?;
+            for object in &objects {
+                self.out.push('\n');
+                self.object(object, None, 0)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes an object with its header token at `indent` and its content one level deeper.
+    fn object(&mut self, node: &Node, header: Option<&str>, indent: usize) -> Result<()> {
+        match node {
+            Node::Struct(name, fields) => self.block(name, fields, header, indent),
+            Node::Variant {
+                enum_name,
+                variant,
+                value: Some(value),
+            } => self.object(value, variant_token(enum_name, variant).or(header), indent),
+            node => anyhow::bail!("Expected an object, got {:?}", node),
+        }
+    }
+
+    fn block(
+        &mut self,
+        name: &str,
+        fields: &[(&'static str, Node)],
+        header: Option<&str>,
+        indent: usize,
+    ) -> Result<()> {
+        let ((first_field, first_value), fields) = match fields.split_first() {
+            Some(split) => split,
+            None => return Ok(()),
+        };
+        let header = header.map_or_else(|| token_name(name, first_field), str::to_owned);
+        self.token(&header, first_value, indent)?;
+
+        let mut nested = vec![];
+        let mut scopes = vec![];
+        for (field, value) in fields {
+            match nested_kind(value) {
+                Some(kind) if SCOPE_STRUCTS.contains(&kind) => scopes.push(value),
+                Some(_) => nested.push(value),
+                None => self.field(name, field, value, indent + 1)?,
+            }
+        }
+        for value in nested.into_iter().chain(scopes) {
+            match value {
+                Node::Seq(objects) => {
+                    for object in objects {
+                        self.object(object, None, indent + 1)?;
+                    }
+                }
+                object => self.object(object, None, indent + 1)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn field(&mut self, struct_name: &str, field: &str, value: &Node, indent: usize) -> Result<()> {
+        match value {
+            Node::None => Ok(()),
+            Node::Seq(values) => {
+                let token = token_name(struct_name, field);
+                for value in values {
+                    self.token(&token, value, indent)?;
+                }
+                Ok(())
+            }
+            // Maps hold tokens keyed by their name, like the creature graphics.
+            Node::Map(entries) => {
+                for (key, value) in entries {
+                    let mut token = vec![];
+                    args(key, &mut token);
+                    let token = token.join(":");
+                    match value {
+                        Node::Seq(values) => {
+                            for value in values {
+                                self.token(&token, value, indent)?;
+                            }
+                        }
+                        value => self.token(&token, value, indent)?,
+                    }
+                }
+                Ok(())
+            }
+            value => self.token(&token_name(struct_name, field), value, indent),
+        }
+    }
+
+    fn token(&mut self, token: &str, value: &Node, indent: usize) -> Result<()> {
+        let mut arguments = vec![];
+        args(value, &mut arguments);
+        for _ in 0..indent {
+            self.out.push('\t');
+        }
+        self.out.push('[');
+        self.out.push_str(token);
+        for argument in arguments {
+            self.out.push(':');
+            self.out.push_str(&argument);
+        }
+        self.out.push_str("]\n");
+        Ok(())
+    }
+}
+
+/// Returns the struct name if the value is a nested object (or a list of them) instead of the
+/// arguments of a token.
+fn nested_kind(value: &Node) -> Option<&'static str> {
+    match value {
+        Node::Struct(name, _) if !is_arg_struct(name) => Some(name),
+        Node::Variant {
+            value: Some(value), ..
+        } => match value.as_ref() {
+            Node::Struct(name, _) if !is_arg_struct(name) => Some(name),
+            _ => None,
+        },
+        Node::Seq(values) => values.first().and_then(nested_kind),
+        _ => None,
+    }
+}
+
+fn is_arg_struct(name: &str) -> bool {
+    name.ends_with("Arg") || name == "Clamp"
+}
+
+/// Flattens a value into token arguments.
+fn args(value: &Node, out: &mut Vec<String>) {
+    match value {
+        Node::None | Node::Unit => {}
+        Node::Bool(value) => out.push(if *value { "1" } else { "0" }.to_owned()),
+        Node::Number(value) => out.push(value.to_string()),
+        Node::Char(value) => out.push(char_arg(*value)),
+        Node::Str(value) | Node::Reference(_, value) => out.push(value.clone()),
+        Node::Seq(values) | Node::Tuple(values) => {
+            for value in values {
+                args(value, out);
+            }
+        }
+        Node::Map(entries) => {
+            for (key, value) in entries {
+                args(key, out);
+                args(value, out);
+            }
+        }
+        Node::Struct(name, fields) => {
+            let keyword = KEYWORD_ARG_STRUCTS.contains(name);
+            for (field, value) in fields {
+                if !keyword {
+                    args(value, out);
+                    continue;
+                }
+                match value {
+                    Node::None => {}
+                    Node::Seq(values) => {
+                        for value in values {
+                            out.push(field.to_uppercase());
+                            args(value, out);
+                        }
+                    }
+                    value => {
+                        out.push(field.to_uppercase());
+                        args(value, out);
+                    }
+                }
+            }
+        }
+        Node::Variant {
+            enum_name,
+            variant,
+            value,
+        } => match (*enum_name, value) {
+            ("AllowEmpty", None) => out.push(String::new()),
+            ("AllowEmpty" | "Any", Some(value)) => args(value, out),
+            (_, value) => {
+                out.push(variant_name(enum_name, variant));
+                if let Some(value) = value {
+                    args(value, out);
+                }
+            }
+        },
+    }
+}
+
+/// Tiles are written as quoted characters when possible, and as their tile index otherwise.
+fn char_arg(value: char) -> String {
+    if value.is_ascii_graphic() {
+        return format!("'{}'", value);
+    }
+    match DFChar(value).tile() {
+        Some(tile) => tile.to_string(),
+        None => (value as u32).to_string(),
+    }
+}
+
+pub(crate) fn token_name(struct_name: &str, field: &str) -> String {
+    field_token(struct_name, field)
+        .map(str::to_owned)
+        .unwrap_or_else(|| field.to_uppercase())
+}
+
+pub(crate) fn variant_name(enum_name: &str, variant: &str) -> String {
+    if let Some(token) = variant_token(enum_name, variant) {
+        return token.to_owned();
+    }
+    let mut token = String::with_capacity(variant.len() + 4);
+    let mut previous: Option<char> = None;
+    for c in variant.chars() {
+        if c.is_ascii_uppercase()
+            && matches!(previous, Some(p) if p.is_ascii_lowercase() || p.is_ascii_digit())
+        {
+            token.push('_');
+        }
+        token.push(c.to_ascii_uppercase());
+        previous = Some(c);
+    }
+    token
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Context;
+
+    use crate::*;
+
+    #[test]
+    fn write_round_trip() -> Result<()> {
+        for entry in std::fs::read_dir("./raw/objects")? {
+            let path = entry?.path();
+            if !path.is_file() {
+                continue;
+            }
+            let source = read_raw_file(&path, Encoding::Auto)
+                .with_context(|| format!("could not read {}", path.display()))?;
+            let raw = parse(&source)
+                .with_context(|| format!("could not parse {}", path.display()))?
+                .raw;
+            let written = write_raw(&raw)?;
+            let reparsed = parse(&written)
+                .with_context(|| format!("could not parse {} as written", path.display()))?
+                .raw;
+            assert!(raw == reparsed, "{} does not round trip", path.display());
+        }
+        Ok(())
+    }
+}
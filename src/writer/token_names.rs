@@ -0,0 +1,306 @@
+//! Token names that can not be derived from the Rust names.
+//!
+//! Struct fields are written as their upper-cased name and enum variants as their
+//! `SCREAMING_SNAKE_CASE` name. Everything below is the exception to that rule, taken from the
+//! `alias` attributes in `crate::structure`. The tests check both against those attributes.
+
+/// The tokens of struct fields, by struct name and field name.
+#[rustfmt::skip]
+const FIELD_TOKENS: &[(&str, &str, &str)] = &[
+    ("AdjToken", "words", "ADJ"),
+    ("AmmoToken", "reference", "ITEM_AMMO"),
+    ("Animal", "reference", "ANIMAL"),
+    ("ArmorToken", "reference", "ITEM_ARMOR"),
+    ("Attack", "reference_and_bp", "ATTACK"),
+    ("BodyDetailPlanToken", "reference", "BODY_DETAIL_PLAN"),
+    ("BodyToken", "reference", "BODY"),
+    ("BuildItemToken", "build_material", "BUILDMAT"),
+    ("BuildItemToken", "contains", "CONTAINS_LYE"),
+    ("BuildingGeneralToken", "reference", "BUILDING_WORKSHOP"),
+    ("CanDoInteraction", "reference", "CAN_DO_INTERACTION"),
+    ("Caste", "attacks", "ATTACK"),
+    ("Caste", "reference", "CASTE"),
+    ("ColorToken", "reference", "COLOR"),
+    ("CreatureGraphicsToken", "reference", "CREATURE_GRAPHICS"),
+    ("CreatureToken", "attacks", "ATTACK"),
+    ("CreatureToken", "castes", "CASTE"),
+    ("CreatureToken", "reference", "CREATURE"),
+    ("CreatureToken", "select_castes", "SELECT_CASTE"),
+    ("CreatureVariationToken", "reference", "CREATURE_VARIATION"),
+    ("EboItem", "reference", "EBO_ITEM"),
+    ("EntityPosition", "reference", "POSITION"),
+    ("EntityToken", "reference", "ENTITY"),
+    ("ExtraButcherObject", "reference", "EXTRA_BUTCHER_OBJECT"),
+    ("FoodToken", "reference", "ITEM_FOOD"),
+    ("GlovesToken", "reference", "ITEM_GLOVES"),
+    ("Growth", "reference", "GROWTH"),
+    ("HelmToken", "reference", "ITEM_HELM"),
+    ("IEffect", "reference", "I_EFFECT"),
+    ("ISource", "reference", "I_SOURCE"),
+    ("ITarget", "reference", "I_TARGET"),
+    ("InorganicToken", "reference", "INORGANIC"),
+    ("InstrumentToken", "reference", "ITEM_INSTRUMENT"),
+    ("InstrumentToken", "volume_mb", "VOLUME_mB"),
+    ("InteractionToken", "reference", "INTERACTION"),
+    ("LocalMaterialToken", "reference", "MATERIAL"),
+    ("LocalTissueToken", "material", "TISSUE_MATERIAL"),
+    ("LocalTissueToken", "name", "TISSUE_NAME"),
+    ("LocalTissueToken", "reference", "TISSUE"),
+    ("LocalTissueToken", "thickness_on_energy_storage", "THICKENS_ON_ENERGY_STORAGE"),
+    ("LocalTissueToken", "thickness_on_strength", "THICKENS_ON_STRENGTH"),
+    ("LocalTissueToken", "though", "THOUGHT"),
+    ("MaterialToken", "reference", "MATERIAL_TEMPLATE"),
+    ("NounToken", "read_compound_noun_plur", "REAR_COMPOUND_NOUN_PLUR"),
+    ("NounToken", "read_compound_noun_sing", "REAR_COMPOUND_NOUN_SING"),
+    ("NounToken", "words", "NOUN"),
+    ("ObjectToken", "body_detail_plan_tokens", "BODY_DETAIL_PLAN"),
+    ("ObjectToken", "body_tokens", "BODY"),
+    ("ObjectToken", "building_tokens", "BUILDING"),
+    ("ObjectToken", "color_tokens", "DESCRIPTOR_COLOR"),
+    ("ObjectToken", "creature_tokens", "CREATURE"),
+    ("ObjectToken", "creature_variation_tokens", "CREATURE_VARIATION"),
+    ("ObjectToken", "entity_tokens", "ENTITY"),
+    ("ObjectToken", "graphics_tokens", "GRAPHICS"),
+    ("ObjectToken", "inorganic_tokens", "INORGANIC"),
+    ("ObjectToken", "interaction_tokens", "INTERACTION"),
+    ("ObjectToken", "item_tokens", "ITEM"),
+    ("ObjectToken", "language_tokens", "LANGUAGE"),
+    ("ObjectToken", "material_tokens", "MATERIAL_TEMPLATE"),
+    ("ObjectToken", "pattern_tokens", "DESCRIPTOR_PATTERN"),
+    ("ObjectToken", "plant_tokens", "PLANT"),
+    ("ObjectToken", "reaction_tokens", "REACTION"),
+    ("ObjectToken", "shape_tokens", "DESCRIPTOR_SHAPE"),
+    ("ObjectToken", "tissue_template_tokens", "TISSUE_TEMPLATE"),
+    ("PantsToken", "reference", "ITEM_PANTS"),
+    ("PatternToken", "reference", "COLOR_PATTERN"),
+    ("PlantToken", "reference", "PLANT"),
+    ("PrefixToken", "words", "PREFIX"),
+    ("ProductToken", "reference", "PRODUCT"),
+    ("ReactionCategoryToken", "reference", "CATEGORY"),
+    ("ReactionToken", "products", "PRODUCT"),
+    ("ReactionToken", "reagents", "REAGENT"),
+    ("ReactionToken", "reference", "REACTION"),
+    ("ReagentToken", "build_material", "BUILDMAT"),
+    ("ReagentToken", "reference", "REAGENT"),
+    ("Responsibility", "reference", "RESPONSIBILITY"),
+    ("SelectCaste", "attacks", "ATTACK"),
+    ("SelectCaste", "reference", "SELECT_CASTE"),
+    ("SelectMaterial", "reference", "SELECT_MATERIAL"),
+    ("SelectTissue", "material", "TISSUE_MATERIAL"),
+    ("SelectTissue", "name", "TISSUE_NAME"),
+    ("SelectTissue", "reference", "SELECT_TISSUE"),
+    ("SelectTissue", "thickness_on_energy_storage", "THICKENS_ON_ENERGY_STORAGE"),
+    ("SelectTissue", "thickness_on_strength", "THICKENS_ON_STRENGTH"),
+    ("SelectTissue", "though", "THOUGHT"),
+    ("ShapeToken", "reference", "SHAPE"),
+    ("ShieldToken", "reference", "ITEM_SHIELD"),
+    ("ShoesToken", "reference", "ITEM_SHOES"),
+    ("SiegeAmmoToken", "reference", "ITEM_SIEGEAMMO"),
+    ("SymbolToken", "reference", "SYMBOL"),
+    ("TilePageToken", "page_dimensions", "PAGE_DIM"),
+    ("TilePageToken", "reference", "TILE_PAGE"),
+    ("TilePageToken", "tile_dimensions", "TILE_DIM"),
+    ("TissueStyle", "reference", "TISSUE_STYLE"),
+    ("TissueToken", "material", "TISSUE_MATERIAL"),
+    ("TissueToken", "name", "TISSUE_NAME"),
+    ("TissueToken", "reference", "TISSUE_TEMPLATE"),
+    ("TissueToken", "thickness_on_energy_storage", "THICKENS_ON_ENERGY_STORAGE"),
+    ("TissueToken", "thickness_on_strength", "THICKENS_ON_STRENGTH"),
+    ("TissueToken", "though", "THOUGHT"),
+    ("ToolToken", "reference", "ITEM_TOOL"),
+    ("ToyToken", "reference", "ITEM_TOY"),
+    ("TranslationToken", "reference", "TRANSLATION"),
+    ("TrapCompToken", "reference", "ITEM_TRAPCOMP"),
+    ("UseMaterial", "reference", "USE_MATERIAL"),
+    ("UseMaterialTemplate", "reference", "USE_MATERIAL_TEMPLATE"),
+    ("UseTissue", "material", "TISSUE_MATERIAL"),
+    ("UseTissue", "name", "TISSUE_NAME"),
+    ("UseTissue", "reference", "USE_TISSUE"),
+    ("UseTissue", "thickness_on_energy_storage", "THICKENS_ON_ENERGY_STORAGE"),
+    ("UseTissue", "thickness_on_strength", "THICKENS_ON_STRENGTH"),
+    ("UseTissue", "though", "THOUGHT"),
+    ("UseTissueTemplate", "material", "TISSUE_MATERIAL"),
+    ("UseTissueTemplate", "name", "TISSUE_NAME"),
+    ("UseTissueTemplate", "reference", "USE_TISSUE_TEMPLATE"),
+    ("UseTissueTemplate", "thickness_on_energy_storage", "THICKENS_ON_ENERGY_STORAGE"),
+    ("UseTissueTemplate", "thickness_on_strength", "THICKENS_ON_STRENGTH"),
+    ("UseTissueTemplate", "though", "THOUGHT"),
+    ("VerbToken", "words", "VERB"),
+    ("Weapon", "reference", "WEAPON"),
+    ("WeaponToken", "reference", "ITEM_WEAPON"),
+    ("WordToken", "nouns", "NOUN"),
+    ("WordToken", "reference", "WORD"),
+];
+
+/// The tokens of enum variants, by enum name and variant name.
+#[rustfmt::skip]
+const VARIANT_TOKENS: &[(&str, &str, &str)] = &[
+    ("BodyObjectToken", "BodyGlossToken", "BODYGLOSS"),
+    ("BodyObjectToken", "BodyToken", "BODY"),
+    ("BuildingToken", "Furnace", "BUILDING_FURNACE"),
+    ("BuildingToken", "Workshop", "BUILDING_WORKSHOP"),
+    ("CasteFlagEnum", "BoneCarn", "BONECARN"),
+    ("CasteFlagEnum", "CanOpenDoors", "CANOPENDOORS"),
+    ("CasteFlagEnum", "FishItem", "FISHITEM"),
+    ("CasteFlagEnum", "FleeQuick", "FLEEQUICK"),
+    ("CasteFlagEnum", "HasShell", "HASSHELL"),
+    ("CasteFlagEnum", "ItemCorpse", "ITEMCORPSE"),
+    ("CasteFlagEnum", "NoBones", "NOBONES"),
+    ("CasteFlagEnum", "NoBreathe", "NOBREATHE"),
+    ("CasteFlagEnum", "NoEmotion", "NOEMOTION"),
+    ("CasteFlagEnum", "NoExert", "NOEXERT"),
+    ("CasteFlagEnum", "NoFear", "NOFEAR"),
+    ("CasteFlagEnum", "NoMeat", "NOMEAT"),
+    ("CasteFlagEnum", "NoNausea", "NONAUSEA"),
+    ("CasteFlagEnum", "NoPain", "NOPAIN"),
+    ("CasteFlagEnum", "NoSkin", "NOSKIN"),
+    ("CasteFlagEnum", "NoSkull", "NOSKULL"),
+    ("CasteFlagEnum", "NoSmellyRot", "NOSMELLYROT"),
+    ("CasteFlagEnum", "NoStuckins", "NOSTUCKINS"),
+    ("CasteFlagEnum", "NoStun", "NOSTUN"),
+    ("CasteFlagEnum", "NoThought", "NOTHOUGHT"),
+    ("CasteFlagEnum", "SemiMegabeast", "SEMIMEGABEAST"),
+    ("CasteFlagEnum", "VerminNoFish", "VERMIN_NOFISH"),
+    ("CasteFlagEnum", "VerminNoRoam", "VERMIN_NOROAM"),
+    ("CasteFlagEnum", "VerminNoTrap", "VERMIN_NOTRAP"),
+    ("ItemToken", "AmmoToken", "ITEM_AMMO"),
+    ("ItemToken", "ArmorToken", "ITEM_ARMOR"),
+    ("ItemToken", "FoodToken", "ITEM_FOOD"),
+    ("ItemToken", "GlovesToken", "ITEM_GLOVES"),
+    ("ItemToken", "HelmToken", "ITEM_HELM"),
+    ("ItemToken", "InstrumentToken", "ITEM_INSTRUMENT"),
+    ("ItemToken", "PantsToken", "ITEM_PANTS"),
+    ("ItemToken", "ShieldToken", "ITEM_SHIELD"),
+    ("ItemToken", "ShoesToken", "ITEM_SHOES"),
+    ("ItemToken", "SiegeAmmoToken", "ITEM_SIEGEAMMO"),
+    ("ItemToken", "ToolToken", "ITEM_TOOL"),
+    ("ItemToken", "ToyToken", "ITEM_TOY"),
+    ("ItemToken", "TrapCompToken", "ITEM_TRAPCOMP"),
+    ("ItemToken", "WeaponToken", "ITEM_WEAPON"),
+    ("LaborEnum", "Alchemy", "ALCHEMIST"),
+    ("LaborEnum", "AnimalCare", "ANIMALCARE"),
+    ("LaborEnum", "AnimalTrainer", "ANIMALTRAIN"),
+    ("LaborEnum", "Armoring", "FORGE_ARMOR"),
+    ("LaborEnum", "Blacksmithing", "FORGE_FURNITURE"),
+    ("LaborEnum", "BoneCarving", "BONE_CARVE"),
+    ("LaborEnum", "Brewing", "BREWER"),
+    ("LaborEnum", "Butchery", "BUTCHER"),
+    ("LaborEnum", "CheeseMaking", "MAKE_CHEESE"),
+    ("LaborEnum", "ClothesMaking", "CLOTHESMAKER"),
+    ("LaborEnum", "Cooking", "COOK"),
+    ("LaborEnum", "CutWood", "CUTWOOD"),
+    ("LaborEnum", "Diagnoser", "DIAGNOSE"),
+    ("LaborEnum", "Dyeing", "DYER"),
+    ("LaborEnum", "Farming", "PLANT"),
+    ("LaborEnum", "FishCleaning", "CLEAN_FISH"),
+    ("LaborEnum", "FishDissection", "DISSECT_FISH"),
+    ("LaborEnum", "Fishing", "FISH"),
+    ("LaborEnum", "FurnaceOperating", "SMELT"),
+    ("LaborEnum", "Gelding", "GELD"),
+    ("LaborEnum", "GemCutting", "CUT_GEM"),
+    ("LaborEnum", "GemSetting", "ENCRUST_GEM"),
+    ("LaborEnum", "GlassMaking", "GLASSMAKER"),
+    ("LaborEnum", "Hunting", "HUNT"),
+    ("LaborEnum", "Leatherworker", "LEATHER"),
+    ("LaborEnum", "Mechanics", "MECHANIC"),
+    ("LaborEnum", "MetalCrafting", "METAL_CRAFT"),
+    ("LaborEnum", "Milking", "MILK"),
+    ("LaborEnum", "Milling", "MILLER"),
+    ("LaborEnum", "PlantGathering", "HERBALIST"),
+    ("LaborEnum", "PlantProcessing", "PROCESS_PLANT"),
+    ("LaborEnum", "PumpOperating", "OPERATE_PUMP"),
+    ("LaborEnum", "RecoveringWounded", "RECOVER_WOUNDED"),
+    ("LaborEnum", "Shearing", "SHEARER"),
+    ("LaborEnum", "SiegeEngineering", "SIEGECRAFT"),
+    ("LaborEnum", "SiegeOperating", "SIEGEOPERATE"),
+    ("LaborEnum", "SmallAnimalDissection", "DISSECT_VERMIN"),
+    ("LaborEnum", "SoapMaking", "SOAP_MAKER"),
+    ("LaborEnum", "Spinning", "SPINNER"),
+    ("LaborEnum", "StoneCrafting", "STONE_CRAFT"),
+    ("LaborEnum", "StrandExtraction", "EXTRACT_STRAND"),
+    ("LaborEnum", "Tanning", "TANNER"),
+    ("LaborEnum", "WeaponSmithing", "FORGE_WEAPON"),
+    ("LaborEnum", "Weaving", "WEAVER"),
+    ("LaborEnum", "WoodBurning", "BURN_WOOD"),
+    ("LaborEnum", "WoodCrafting", "WOOD_CRAFT"),
+    ("LanguageToken", "SymbolToken", "SYMBOL"),
+    ("LanguageToken", "TranslationToken", "TRANSLATION"),
+    ("LanguageToken", "WordToken", "WORD"),
+    ("NotApplicableEnum", "NotApplicable", "NA"),
+    ("SecretGoalEnum", "BecomeALegendaryWarrior", "BECOME_A_LEGENDARY_WARRIOR"),
+    ("SecretGoalEnum", "CraftAMasterwork", "CRAFT_A_MASTERWORK"),
+    ("SecretGoalEnum", "CreateAGreatWorkOfArt", "CREATE_A_GREAT_WORK_OF_ART"),
+    ("SecretGoalEnum", "MasterASkill", "MASTER_A_SKILL"),
+    ("SecretGoalEnum", "StartAFamily", "START_A_FAMILY"),
+    ("SiteTypeEnum", "Rare", "DARK_FORTRESS"),
+    ("TargetPropertyEnum", "ExtraVision", "EXTRAVISION"),
+    ("TargetPropertyEnum", "NoBreathe", "NOBREATHE"),
+    ("TargetPropertyEnum", "NoEmotion", "NOEMOTION"),
+    ("TargetPropertyEnum", "NoExert", "NOEXERT"),
+    ("TargetPropertyEnum", "NoFear", "NOFEAR"),
+    ("TargetPropertyEnum", "NoNausea", "NONAUSEA"),
+    ("TargetPropertyEnum", "NoPain", "NOPAIN"),
+    ("TargetPropertyEnum", "NoStun", "NOSTUN"),
+    ("TargetPropertyEnum", "NoThought", "NOTHOUGHT"),
+    ("TargetPropertyEnum", "ParalyzeImmune", "PARALYZEIMMUNE"),
+    ("TextureTypeEnum", "RoyalGuard", "ROYALGUARD"),
+    ("TuningMethodEnum", "AdjustableBridhes", "ADJUSTABLE_BRIDGES"),
+];
+
+pub(crate) fn field_token(struct_name: &str, field: &str) -> Option<&'static str> {
+    find(FIELD_TOKENS, struct_name, field)
+}
+
+pub(crate) fn variant_token(enum_name: &str, variant: &str) -> Option<&'static str> {
+    find(VARIANT_TOKENS, enum_name, variant)
+}
+
+fn find(tokens: &[(&str, &str, &'static str)], item: &str, member: &str) -> Option<&'static str> {
+    tokens
+        .iter()
+        .find(|(name, name_member, _)| *name == item && *name_member == member)
+        .map(|(_, _, token)| *token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FIELD_TOKENS, VARIANT_TOKENS};
+    use crate::aliases::structure_aliases;
+    use crate::writer::{token_name, variant_name};
+
+    #[test]
+    fn token_names_match_aliases() {
+        let aliases = structure_aliases();
+        for (item, member, token) in FIELD_TOKENS.iter().chain(VARIANT_TOKENS) {
+            let member_aliases = aliases
+                .get(*item)
+                .and_then(|members| members.get(*member))
+                .unwrap_or_else(|| panic!("{}::{} does not exist", item, member));
+            assert!(
+                member_aliases.is_empty() || member_aliases.iter().any(|alias| alias == token),
+                "{}::{} is written as {} but has the aliases {:?}",
+                item,
+                member,
+                token,
+                member_aliases
+            );
+        }
+        for (item, members) in &aliases {
+            for (member, member_aliases) in members {
+                let token = if member.starts_with(|c: char| c.is_ascii_lowercase()) {
+                    token_name(item, member)
+                } else {
+                    variant_name(item, member)
+                };
+                assert!(
+                    member_aliases.is_empty() || member_aliases.contains(&token),
+                    "{}::{} is written as {} but has the aliases {:?}",
+                    item,
+                    member,
+                    token,
+                    member_aliases
+                );
+            }
+        }
+    }
+}